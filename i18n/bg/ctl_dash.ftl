@@ -1,32 +1,305 @@
 app-title = CTL Dash
 about = Относно
+settings = Настройки
+command-palette = Команден панел
+command-palette-placeholder = Търсене в системни и потребителски услуги…
+command-palette-hint = Пишете, за да търсите услуги по име или описание.
+no-matching-units = Няма съвпадащи услуги
+failure-notifications = Известия за сривове
+unit-list = Списък с модули
+keep-search = Запазване на търсенето при смяна на страницата
+compact-list = Компактни редове
+appearance = Външен вид
+theme = Тема
+match-desktop = Като работния плот
+dark = Тъмна
+light = Светла
+startup-page = Страница при стартиране
+last-opened-page = Последно отворената
+poll-interval = Интервал на опресняване
+poll-interval-note = Използва се само когато известията за промени от systemd не могат да бъдат получени
+every-seconds = { $seconds ->
+    [one] Всяка секунда
+   *[other] На всеки { $seconds } секунди
+}
+log-lines = Брой редове от журнала
+polling-off = Изключено, ръчно опресняване
+notify-system-failures = Известие при срив на системна единица
+notify-user-failures = Известие при срив на потребителска единица
+run-in-background = Продължи да работи във фонов режим
+run-in-background-note = Известията продължават да пристигат след затваряне на прозореца. Влиза в сила при следващото стартиране на приложението.
+service-failed = { $service } се срина
+system-service-failed-body = Системна единица премина в състояние на срив.
+user-service-failed-body = Потребителска единица премина в състояние на срив.
+service-finished = { $service } приключи
+watched-system-unit-body = Наблюдавана системна услуга промени състоянието си.
+watched-user-unit-body = Наблюдавана потребителска услуга промени състоянието си.
+watch = Известие, когато единицата приключи или се срине
+stop-watching = Без известия за тази единица
 repository = Хранилище
 support = Поддръжка
+reload-unit-files = Презареждане на unit файловете
 view = Изглед
+action-succeeded = { $action }: { $service } – успешно
+undo = Отмени
+action-failed = { $action }: { $service } – неуспешно: { $error }
+daemon-reload-failed = Презареждането на unit файловете е неуспешно: { $error }
+needs-daemon-reload = Файловете на модула са променени на диска, промените ще се приложат след презареждането им
+jobs-running = { $count ->
+    [one] 1 задача се изпълнява
+   *[other] { $count } задачи се изпълняват
+}
+file = Файл
+export = Експортиране
+export-csv = Експортиране като CSV…
+export-json = Експортиране като JSON…
+export-succeeded = Експортирано в { $path }
+export-failed = Експортирането е неуспешно: { $error }
+confirm-action-title = { $action } { $service }?
+confirm-action-body = { $service } може да е важна за системата. Сигурни ли сте, че искате да продължите?
+confirm-shown-title = { $action } на { $count } единици?
+confirm-shown-body = Действието засяга всяка единица, показана в списъка:
+confirm-batch-body = Някои от тези единици може да са важни за системата:
+preview-start = Преглед на стартирането
+start-preview-title = Стартиране на { $service }?
+start-preview-loading = Проследяване на зависимостите…
+start-preview-body = { $count ->
+    [one] Това ще стартира и един неактивен модул:
+   *[other] Това ще стартира и { $count } неактивни модула:
+}
+start-preview-nothing-else = Всичко, от което зависи, вече е активно.
+start-preview-failed = Зависимостите не можаха да бъдат проследени: { $error }
+cancel = Отказ
+close = Затвори
 
 # Navigation
 system-services = Системни услуги
 user-services = Потребителски услуги
+system = Системна
+user = Потребителска
+timers = Таймери
+sockets = Сокети
+targets = Цели
 
 # Service List
 search-placeholder = Търсене на услуги...
+recent-searches = Скорошни търсения
 service = Услуга
 description = Описание
 active-state = Активно състояние
 sub-state = Подсъстояние
-loading-services = Зареждане на услуги...
+next-run = Следващо изпълнение
+listen = Слуша на
+columns = Колони
 no-services-found = Няма открити услуги
 no-services-match = Няма услуги, отговарящи на търсенето
+scope-unavailable = Неуспешно извличане на единиците: { $error }
+state-all = Всички
+state-active = Активни
+state-inactive = Неактивни
+state-failed = Неуспешни
+file-state-label = Файл на модула:
+file-state-enabled = Активирани
+file-state-disabled = Деактивирани
+file-state-masked = Маскирани
+file-state-static = Статични
+system-degraded = Някои единици са неуспешни, системата е в деградирало състояние
+user-session-degraded = Някои от потребителските ви единици са неуспешни
+system-needs-authentication = Управлението на системните модули ще изисква парола
+reconnecting = Връзката със systemd е загубена, свързване отново…
+status-connected = Следене на промените
+status-polling = Периодична проверка за промени
+status-reconnecting = Повторно свързване…
+status-not-loaded = Все още не е заредено
+show-failed-units = Показване на неуспешните единици
+favorites = Любими
+all-units = Всички единици
+page-of = Страница { $page } от { $total }
+group-services = Групиране
+hide-vendor-units = Само инсталируеми
+hide-vendor-units-tooltip = Скриване на статичните, непреките и генерираните модули, които не могат да бъдат активирани или деактивирани
+refresh = Опресняване
+refreshing = Опресняване…
+updated-ago = Обновено преди { $elapsed }
+time-ago = преди { $elapsed }
+selected-count = { $count } избрани
+summary-total = { $count } общо
+summary-failed = { $count } неуспешни
+summary-active = { $count } активни
+clear-selection = Изчистване на избора
+shown-count = { $count } показани
+start-all-shown = Стартирай всички показани
+stop-all-shown = Спри всички показани
+batch-summary = { $action }: { $succeeded } успешни, { $failed } неуспешни
+dismiss = Затваряне
+show-changes = Покажи промените
+unit-file-changes-title = { $action }: промени във файловете на модулите
+unit-file-changes-body = { $count ->
+    [0] Не бяха създадени или премахнати символни връзки.
+    [one] systemctl промени една символна връзка:
+   *[other] systemctl промени { $count } символни връзки:
+}
+no-unit-file-changes = Няма променени символни връзки
+enable = Активиране
+disable = Деактивиране
 
 # Service Detail
 all-system-services = Всички системни услуги
 all-user-services = Всички потребителски услуги
+all-timers = Всички таймери
+all-sockets = Всички сокети
+all-targets = Всички цели
 description-label = Описание:
 load-state-label = Състояние на зареждане:
 enabled-label = Активирана:
+instance-toggle-note = Отнася се само за инстанцията { $instance }, не и за { $template }
 status-label = Статус:
+running-since-label = Работи от:
+inactive-since-label = Неактивна от:
+result-label = Последен резултат:
+restarts-label = Рестартирания:
+restart-loop = Рестартира многократно
+result-success = Успешно
+result-exit-code = Неуспешно, с код за грешка
+result-signal = Прекратено от сигнал
+result-core-dump = Сриване с дъмп на паметта
+result-timeout = Изтекло време
+result-watchdog = Спря да отговаря на watchdog
+result-start-limit-hit = Рестартирано твърде често
+result-resources = Недостатъчни ресурси
+result-oom-kill = Прекратено поради липса на памет
+result-protocol = Нарушен протокол при стартиране
+exited-with-status = завърши с код { $status }
+killed-by-signal = прекратено от { $signal }
+dumped-core = дъмп на паметта след { $signal }
+watchdog-label = Наблюдение:
+watchdog-not-configured = Не е настроено
+watchdog-healthy = Изправно
+watchdog-late = Пропуснат сигнал
+watchdog-interval = на всеки { $interval }
+watchdog-last-ping = последен сигнал { $time }
+conditions-label = Условия:
+asserts-label = Проверки:
+not-checked = Още не са проверени
+check-passed = Изпълнени
+check-failed = Неизпълнени
+frozen-label = Замразена:
+static-note = Активирана чрез зависимост
+enabled-runtime-note = Активирана до рестартиране
+unit-file-state-enabled = Стартира при зареждане на системата.
+unit-file-state-enabled-runtime = Активирана само до следващото рестартиране.
+unit-file-state-linked = Файлът на модула е свързан извън пътя за търсене.
+unit-file-state-alias = Това име е псевдоним на друг модул.
+unit-file-state-masked = Маскираните модули не могат да бъдат стартирани.
+unit-file-state-static = Няма секция за инсталиране и се стартира само когато друг модул има нужда от нея.
+unit-file-state-indirect = Активирана чрез друг модул, посочен в настройката Also=.
+unit-file-state-disabled = Не стартира при зареждане на системата.
+unit-file-state-generated = Създадена динамично от генератор.
+unit-file-state-transient = Създадена по време на работа и изчезва след рестартиране.
+unit-file-state-unknown = systemd не съобщи дали този модул стартира при зареждане.
+load-failed = Файлът на модула не можа да бъде зареден ({ $state })
+not-loaded = Не е зареден
+edit-drop-in = Редактирай заместване…
+open-file-location = Отваряне на местоположението
+open-file-location-failed = Отварянето на местоположението не успя: { $error }
+edit-drop-in-title = Заместване на { $service }
+copy-to-user = Копирай в потребителските услуги
+copied-to-user = Копирано в { $path }
+copied-to-user-without = Копирано в { $path }, без { $directives }
+copy-to-user-failed = Копирането не бе успешно: { $error }
+save = Запази
+overwrite = Презапиши
+drop-in-overwrite-warning = Това ще замени съществуващото заместване. Запазете отново за потвърждение.
+drop-in-empty = Заместването не може да е празно.
+drop-in-saved = Заместването за { $service } е запазено
+new-transient-service = Нова временна услуга…
+transient-service-body = Изпълнява команда като услуга, която съществува, докато командата не приключи, подобно на systemd-run.
+transient-command = Команда
+transient-command-placeholder = напр. sleep 600
+transient-description-placeholder = По подразбиране е командата
+transient-scope = Изпълнение като
+transient-invalid-command = Въведете команда за изпълнение, със затворени кавички.
+transient-started = Стартирана е { $service }
+note-placeholder = Вашите бележки за този модул…
+drop-in-load-failed = Заместването за { $service } не можа да бъде прочетено: { $error }
 unit-path-label = Път до unit файла:
+documentation-label = Документация:
+next-run-label = Следващо изпълнение:
+last-run-label = Последно изпълнение:
+listen-label = Слуша на:
+wanted-by-label = Изискван от:
+activates-label = Активира:
+command-label = Команда:
+main-pid-label = Основен PID:
+memory-label = Памет:
+cpu-label = Процесор:
+cgroup-label = Контролна група:
+processes = Процеси ({ $count })
 start = Стартиране
 stop = Спиране
 restart = Рестартиране
+reload = Презареждане
+reload-or-restart = Презареждане или рестартиране
+mask = Маскиране
+unmask = Демаскиране
+masked = маскирана
+reset-failed = Изчистване на грешката
+kill = Убий…
+kill-with = Изпрати { $signal } до
+confirm-kill-body = { $service } ще бъде спряна незабавно, без възможност да приключи работата си. Сигурни ли сте, че искате да продължите?
+confirm-isolate-body = Изолирането на { $service } спира всеки модул, който тя не изисква, включително и работната ви сесия. Сигурни ли сте, че искате да продължите?
+service-gone = { $service } вече не съществува
+unit-not-found = Няма модул с име { $service }
+freeze = Замрази
+thaw = Размрази
+isolate = Изолиране
 logs = Логове
+unit-file = Unit файл
+loading-unit-file = Зареждане на unit файла...
+no-unit-file = Тази единица няма unit файл, създадена е по време на работа.
+dependencies = Зависимости
+loading-dependencies = Зареждане на зависимостите...
+requires = Изисква
+wants = Желае
+after = След
+before = Преди
+requirements = Изисквания
+dependency-tree = Дърво на зависимостите
+dependency-cycle = (цикъл)
+none = Няма
+follow-logs = Следене на логовете
+relative-times = Относително време
+search-logs = Търсене в логовете...
+load-older-logs = Зареждане на по-стари записи
+loading-older-logs = Зареждане на по-стари записи…
+logs-start = Начало на журнала
+include-related-units = Включване на свързаните модули
+collapse-logs = Свиване на журнала
+expand-logs = Разгъване на журнала
+older-logs-failed = По-старите записи не можаха да бъдат заредени: { $error }
+since-label = От
+boot-label = Зареждане
+all-boots = Всички зареждания
+current-boot = Текущо зареждане, от { $started }
+previous-boot = Зареждане { $offset }, започнало { $started }
+refresh-boots = Обнови списъка със зареждания
+boots-failed = Списъкът със зареждания не можа да бъде зареден: { $error }
+since-placeholder = напр. -1h или 2024-01-01 10:00
+until-label = До
+until-placeholder = напр. now
+apply = Приложи
+clear = Изчисти
+limit-log-lines = Само последните { $lines } реда
+invalid-log-time = "{ $input }" не е време, което journalctl разбира. Използвайте дата като 2024-01-01 10:00 или отместване като -1h.
+match-count = { $current } от { $total }
+log-priority = Приоритет:
+copy-logs = Копиране
+save-logs = Запазване…
+logs-copied = Логовете са копирани в клипборда
+copied = Копирано в клипборда
+copy-name = Копирай името на единицата
+copy-path = Копирай пътя на единицата
+logs-saved = Логовете са запазени в { $path }
+logs-save-failed = Запазването на логовете е неуспешно: { $error }
+open-in-terminal = Следене в терминал
+open-in-terminal-failed = Неуспешно отваряне на терминал: { $error }
@@ -1,32 +1,305 @@
 app-title = CTL Dash
 about = About
+settings = Settings
+command-palette = Command Palette
+command-palette-placeholder = Search system and user services…
+command-palette-hint = Type to search services by name or description.
+no-matching-units = No matching services
+failure-notifications = Failure Notifications
+unit-list = Unit List
+keep-search = Keep the search when switching pages
+compact-list = Compact rows
+appearance = Appearance
+theme = Theme
+match-desktop = Match desktop
+dark = Dark
+light = Light
+startup-page = Page at startup
+last-opened-page = Last opened
+poll-interval = Refresh interval
+poll-interval-note = Only used when systemd's change notifications can't be received
+every-seconds = { $seconds ->
+    [one] Every second
+   *[other] Every { $seconds } seconds
+}
+log-lines = Log lines to load
+polling-off = Off, refresh by hand
+notify-system-failures = Notify when a system unit fails
+notify-user-failures = Notify when a user unit fails
+run-in-background = Keep running in the background
+run-in-background-note = Notifications keep arriving after the window is closed. Takes effect the next time the app starts.
+service-failed = { $service } failed
+system-service-failed-body = A system unit entered the failed state.
+user-service-failed-body = A user unit entered the failed state.
+service-finished = { $service } finished
+watched-system-unit-body = A watched system service changed state.
+watched-user-unit-body = A watched user service changed state.
+watch = Notify when this unit finishes or fails
+stop-watching = Stop notifying about this unit
 repository = Repository
 support = Support
+reload-unit-files = Reload unit files
 view = View
+action-succeeded = { $action }: { $service } succeeded
+undo = Undo
+action-failed = { $action }: { $service } failed: { $error }
+daemon-reload-failed = Reloading unit files failed: { $error }
+needs-daemon-reload = This unit's files changed on disk, the changes apply once they're reloaded
+jobs-running = { $count ->
+    [one] 1 job running
+   *[other] { $count } jobs running
+}
+file = File
+export = Export
+export-csv = Export as CSV…
+export-json = Export as JSON…
+export-succeeded = Exported to { $path }
+export-failed = Export failed: { $error }
+confirm-action-title = { $action } { $service }?
+confirm-action-body = { $service } may be essential to your system. Are you sure you want to continue?
+confirm-shown-title = { $action } { $count } units?
+confirm-shown-body = The action applies to every unit the list shows:
+confirm-batch-body = Some of these units may be essential to your system:
+preview-start = Preview start
+start-preview-title = Start { $service }?
+start-preview-loading = Following its dependencies…
+start-preview-body = { $count ->
+    [one] This also starts one unit that isn't active:
+   *[other] This also starts { $count } units that aren't active:
+}
+start-preview-nothing-else = Everything it pulls in is already active.
+start-preview-failed = Could not follow its dependencies: { $error }
+cancel = Cancel
+close = Close
 
 # Navigation
 system-services = System Services
 user-services = User Services
+system = System
+user = User
+timers = Timers
+sockets = Sockets
+targets = Targets
 
 # Service List
 search-placeholder = Search services...
+recent-searches = Recent searches
 service = Service
 description = Description
 active-state = Active State
 sub-state = Sub State
-loading-services = Loading services...
+next-run = Next Run
+listen = Listen
+columns = Columns
 no-services-found = No services found
 no-services-match = No services match your search
+scope-unavailable = Could not list units: { $error }
+state-all = All
+state-active = Active
+state-inactive = Inactive
+state-failed = Failed
+file-state-label = Unit file:
+file-state-enabled = Enabled
+file-state-disabled = Disabled
+file-state-masked = Masked
+file-state-static = Static
+system-degraded = Some units have failed, the system is degraded
+user-session-degraded = Some of your user units have failed
+system-needs-authentication = Managing system units will ask for your password
+reconnecting = Lost the connection to systemd, reconnecting…
+status-connected = Following changes
+status-polling = Polling for changes
+status-reconnecting = Reconnecting…
+status-not-loaded = Not loaded yet
+show-failed-units = Show failed units
+favorites = Favorites
+all-units = All Units
+page-of = Page { $page } of { $total }
+group-services = Group
+hide-vendor-units = Installable only
+hide-vendor-units-tooltip = Hide static, indirect and generated units, which can't be enabled or disabled
+refresh = Refresh
+refreshing = Refreshing…
+updated-ago = Updated { $elapsed } ago
+time-ago = { $elapsed } ago
+selected-count = { $count } selected
+summary-total = { $count } total
+summary-failed = { $count } failed
+summary-active = { $count } active
+clear-selection = Clear selection
+shown-count = { $count } shown
+start-all-shown = Start All Shown
+stop-all-shown = Stop All Shown
+batch-summary = { $action }: { $succeeded } succeeded, { $failed } failed
+dismiss = Dismiss
+show-changes = Show changes
+unit-file-changes-title = { $action }: unit file changes
+unit-file-changes-body = { $count ->
+    [0] No symlinks were created or removed.
+    [one] systemctl changed one symlink:
+   *[other] systemctl changed { $count } symlinks:
+}
+no-unit-file-changes = No symlinks changed
+enable = Enable
+disable = Disable
 
 # Service Detail
 all-system-services = All System Services
 all-user-services = All User Services
+all-timers = All Timers
+all-sockets = All Sockets
+all-targets = All Targets
 description-label = Description:
 load-state-label = Load State:
 enabled-label = Enabled:
+instance-toggle-note = Applies to the { $instance } instance only, not to { $template }
 status-label = Status:
+running-since-label = Running since:
+inactive-since-label = Inactive since:
+result-label = Last result:
+restarts-label = Restarts:
+restart-loop = Restarting repeatedly
+result-success = Succeeded
+result-exit-code = Failed with an error exit code
+result-signal = Killed by a signal
+result-core-dump = Crashed and dumped core
+result-timeout = Timed out
+result-watchdog = Stopped responding to the watchdog
+result-start-limit-hit = Restarted too often
+result-resources = Ran out of resources
+result-oom-kill = Killed for running out of memory
+result-protocol = Broke the startup protocol
+exited-with-status = exited with status { $status }
+killed-by-signal = killed by { $signal }
+dumped-core = dumped core after { $signal }
+watchdog-label = Watchdog:
+watchdog-not-configured = Not configured
+watchdog-healthy = Healthy
+watchdog-late = Missed its ping
+watchdog-interval = every { $interval }
+watchdog-last-ping = last ping { $time }
+conditions-label = Conditions:
+asserts-label = Asserts:
+not-checked = Not checked yet
+check-passed = Met
+check-failed = Not met
+frozen-label = Frozen:
+static-note = Enabled by dependency
+enabled-runtime-note = Enabled until reboot
+unit-file-state-enabled = Starts on boot.
+unit-file-state-enabled-runtime = Enabled only until the next reboot.
+unit-file-state-linked = The unit file is linked in from outside the search path.
+unit-file-state-alias = This name is an alias of another unit.
+unit-file-state-masked = Masked units can't be started at all.
+unit-file-state-static = Has no install section and only starts when another unit needs it.
+unit-file-state-indirect = Enabled through another unit listed in its Also= setting.
+unit-file-state-disabled = Doesn't start on boot.
+unit-file-state-generated = Created dynamically by a generator.
+unit-file-state-transient = Created at runtime and gone after a reboot.
+unit-file-state-unknown = systemd didn't report whether this unit starts on boot.
+load-failed = The unit file failed to load ({ $state })
+not-loaded = Not loaded
+edit-drop-in = Edit Override…
+open-file-location = Open File Location
+open-file-location-failed = Opening the file location failed: { $error }
+edit-drop-in-title = Override { $service }
+copy-to-user = Copy to User Services
+copied-to-user = Copied to { $path }
+copied-to-user-without = Copied to { $path }, without { $directives }
+copy-to-user-failed = Copying failed: { $error }
+save = Save
+overwrite = Overwrite
+drop-in-overwrite-warning = This replaces the existing override. Save again to confirm.
+drop-in-empty = The override can't be empty.
+drop-in-saved = Saved the override for { $service }
+new-transient-service = New transient service…
+transient-service-body = Runs a command as a service that lasts until it exits, like systemd-run.
+transient-command = Command
+transient-command-placeholder = e.g. sleep 600
+transient-description-placeholder = Defaults to the command
+transient-scope = Run as
+transient-invalid-command = Enter a command to run, with any quotes closed.
+transient-started = Started { $service }
+note-placeholder = Your notes on this unit…
+drop-in-load-failed = Could not read the override for { $service }: { $error }
 unit-path-label = Unit Path:
+documentation-label = Documentation:
+next-run-label = Next Run:
+last-run-label = Last Run:
+listen-label = Listen:
+wanted-by-label = Wanted by:
+activates-label = Activates:
+command-label = Command:
+main-pid-label = Main PID:
+memory-label = Memory:
+cpu-label = CPU:
+cgroup-label = Control Group:
+processes = Processes ({ $count })
 start = Start
 stop = Stop
 restart = Restart
+reload = Reload
+reload-or-restart = Reload or Restart
+mask = Mask
+unmask = Unmask
+masked = masked
+reset-failed = Clear Failed State
+kill = Kill…
+kill-with = Send { $signal } to
+confirm-kill-body = { $service } will be stopped immediately, without a chance to clean up. Are you sure you want to continue?
+confirm-isolate-body = Isolating { $service } stops every unit it doesn't pull in, which can include your desktop session. Are you sure you want to continue?
+service-gone = { $service } no longer exists
+unit-not-found = No unit named { $service }
+freeze = Freeze
+thaw = Thaw
+isolate = Isolate
 logs = Logs
+unit-file = Unit File
+loading-unit-file = Loading unit file...
+no-unit-file = This unit has no unit file, it was created at runtime.
+dependencies = Dependencies
+loading-dependencies = Loading dependencies...
+requires = Requires
+wants = Wants
+after = After
+before = Before
+requirements = Requirements
+dependency-tree = Dependency Tree
+dependency-cycle = (cycle)
+none = None
+follow-logs = Follow logs
+relative-times = Relative times
+search-logs = Search logs...
+load-older-logs = Load older entries
+loading-older-logs = Loading older entries…
+logs-start = Start of the journal
+include-related-units = Include related units
+collapse-logs = Collapse logs
+expand-logs = Expand logs
+older-logs-failed = Could not load older entries: { $error }
+since-label = Since
+boot-label = Boot
+all-boots = All boots
+current-boot = Current boot, since { $started }
+previous-boot = Boot { $offset }, started { $started }
+refresh-boots = Refresh the list of boots
+boots-failed = Listing the boots failed: { $error }
+since-placeholder = e.g. -1h or 2024-01-01 10:00
+until-label = Until
+until-placeholder = e.g. now
+apply = Apply
+clear = Clear
+limit-log-lines = Only the last { $lines } lines
+invalid-log-time = "{ $input }" is not a time journalctl understands. Use a date like 2024-01-01 10:00 or an offset like -1h.
+match-count = { $current } of { $total }
+log-priority = Priority:
+copy-logs = Copy
+save-logs = Save…
+logs-copied = Logs copied to the clipboard
+copied = Copied to the clipboard
+copy-name = Copy the unit's name
+copy-path = Copy the unit's path
+logs-saved = Logs saved to { $path }
+logs-save-failed = Saving logs failed: { $error }
+open-in-terminal = Follow in Terminal
+open-in-terminal-failed = Opening a terminal failed: { $error }
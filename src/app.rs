@@ -1,38 +1,211 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use crate::args::Args;
+use crate::config::{self, Config};
+use crate::debounce::{Debounce, KeyedDebounce};
 use crate::fl;
+use crate::loading::{Loading, UnitList};
 use crate::message::Message;
-use crate::systemd::{ServiceScope, SystemdService};
-use crate::types::{ContextPage, MenuAction, Page};
+use crate::remote;
+use crate::search::ListSearch;
+use crate::systemd::{error_message, is_watched_transition, newly_failed, parse_job_signal, reconnect_delay, unit_object_path, Boot, DependencyKind, DependencyNode, JobEvent, LogPriority, LogRange, ServiceAction, ServiceScope, SystemdManager, SystemdService, UnitDependencies};
+use crate::types::{ContextPage, DetailTab, FileStateFilter, ListColumn, LogTimestampMode, MenuAction, Page, SortColumn, StateFilter};
 use crate::views;
 use cosmic::app::context_drawer;
-use cosmic::iced::{Length, Subscription};
-use cosmic::widget::{self, about::About, icon, menu, nav_bar};
+use cosmic::cosmic_config;
+use cosmic::iced::widget::{scrollable, text_editor};
+use cosmic::iced::{keyboard, Length, Subscription};
+use cosmic::widget::{self, about::About, icon, menu, nav_bar, segmented_button};
 use cosmic::prelude::*;
-use std::collections::HashMap;
+use futures_util::{SinkExt, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
 
 const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
 const APP_ICON: &[u8] = include_bytes!("../resources/icons/hicolor/scalable/apps/icon.svg");
 
+/// An override drop-in being edited, like `systemctl edit` does.
+pub struct DropInEditor {
+    pub(crate) scope: ServiceScope,
+    pub(crate) service_name: String,
+    /// Where it will be written, for display.
+    pub(crate) path: String,
+    pub(crate) content: text_editor::Content,
+    /// Whether saving replaces a drop-in that already exists.
+    pub(crate) exists: bool,
+    /// Set after the first save of an existing drop-in, which only asks to confirm.
+    pub(crate) confirm_overwrite: bool,
+    pub(crate) error: Option<String>,
+    pub(crate) saving: bool,
+}
+
+/// A command being set up to run as a transient service, like `systemd-run`.
+pub struct TransientService {
+    pub(crate) scope: ServiceScope,
+    pub(crate) command: String,
+    pub(crate) description: String,
+    pub(crate) error: Option<String>,
+    pub(crate) starting: bool,
+}
+
+/// A start waiting to be confirmed, with the units it would pull in.
+pub struct StartPreview {
+    pub(crate) scope: ServiceScope,
+    pub(crate) name: String,
+    /// `None` until the dependencies have been followed.
+    pub(crate) units: Option<Result<Vec<String>, String>>,
+}
+
 pub struct AppModel {
     pub(crate) core: cosmic::Core,
     pub(crate) context_page: ContextPage,
     about: About,
     pub nav: nav_bar::Model,
     key_binds: HashMap<menu::KeyBind, MenuAction>,
+    /// Handler used to write `config` back, `None` if the config couldn't be opened.
+    pub(crate) config_handler: Option<cosmic_config::Config>,
+    pub(crate) config: Config,
     pub(crate) system_services: Vec<SystemdService>,
     pub(crate) user_services: Vec<SystemdService>,
+    pub(crate) timers: Vec<SystemdService>,
+    pub(crate) sockets: Vec<SystemdService>,
+    pub(crate) targets: Vec<SystemdService>,
     pub(crate) selected_service: Option<SystemdService>,
+    /// Services checked in the list for a batch action.
+    pub selected_names: HashSet<String>,
+    /// Per-service outcome of the last batch action, until dismissed, holding
+    /// the symlinks each unit's enable or disable changed.
+    pub batch_results: Option<(ServiceAction, Vec<(String, Result<Vec<String>, String>)>)>,
+    /// Whether the symlinks the last batch enable or disable changed are shown.
+    pub(crate) unit_file_changes_open: bool,
+    pub(crate) dependencies: Option<UnitDependencies>,
+    /// The selected unit's dependency tree or why it couldn't be read, `None` while loading.
+    pub(crate) dependency_tree: Option<Result<DependencyNode, String>>,
+    pub(crate) dependency_kind: DependencyKind,
+    /// The expanded nodes of the dependency tree, by their path of unit names joined with `/`.
+    pub(crate) expanded_dependencies: HashSet<String>,
+    /// Switches between the logs and the unit file in the detail view.
+    pub(crate) detail_tabs: segmented_button::SingleSelectModel,
+    /// The selected unit's file and drop-ins, `None` while loading.
+    pub(crate) unit_file: Option<String>,
+    /// The open unit's note, saved once editing pauses.
+    pub(crate) note: text_editor::Content,
+    /// Debounces saving each unit's note on its own, by scope and unit name.
+    pub(crate) note_debounce: KeyedDebounce<(ServiceScope, String)>,
+    /// Debounces saving the window size, which changes with every step of a resize.
+    pub(crate) window_size_debounce: Debounce,
     pub(crate) current_scope: ServiceScope,
+    /// The scope the Timers and Sockets pages list units of, switched above their lists.
+    pub(crate) unit_scope: ServiceScope,
     pub current_page: Page,
     pub service_logs: String,
-    pub is_loading: bool,
-    pub search_filter: String,
+    /// `service_logs` with relative timestamps, kept while they're shown so the
+    /// view can borrow it rather than rebuild it every frame.
+    pub(crate) relative_logs: String,
+    /// The cursor of the oldest log entry loaded, older ones are loaded before it.
+    pub(crate) log_cursor: Option<String>,
+    /// How many older log entries were loaded on top of `log_lines`, kept on refresh.
+    pub(crate) older_log_lines: u32,
+    /// Whether the loaded logs reach back to the unit's first entry.
+    pub(crate) logs_at_start: bool,
+    pub(crate) loading_older_logs: bool,
+    /// Whether the logs include those of the units activating the selected one or
+    /// activated by it, see `SystemdService::log_units`.
+    pub(crate) include_related_logs: bool,
+    /// The error of the last failed action on the selected service.
+    pub action_error: Option<String>,
+    pub(crate) toasts: widget::Toasts<Message>,
+    /// An action waiting for the user to confirm it in a dialog, with the scope of its unit.
+    pub(crate) pending_action: Option<(ServiceScope, ServiceAction, String)>,
+    /// The action undoing the last one and its unit, until it's too late to.
+    pub(crate) undo: Option<(Instant, ServiceScope, ServiceAction, String)>,
+    /// An action on all the units shown in the list waiting to be confirmed, with their names.
+    pub(crate) pending_shown_action: Option<(ServiceAction, Vec<String>)>,
+    /// A stop or disable of the checked units, some of them critical, waiting to be confirmed.
+    pub(crate) pending_batch_action: Option<(ServiceAction, Vec<String>)>,
+    pub(crate) start_preview: Option<StartPreview>,
+    /// Whether the detail view's menu of kill signals is open.
+    pub(crate) kill_menu_open: bool,
+    /// Whether the processes in the selected service's cgroup are listed.
+    pub(crate) show_processes: bool,
+    /// The command palette's query while it's open.
+    pub(crate) command_palette: Option<String>,
+    /// The override drop-in being edited, if any.
+    pub(crate) drop_in_editor: Option<DropInEditor>,
+    pub(crate) transient_service: Option<TransientService>,
+    pub log_lines: u32,
+    pub follow_logs: bool,
+    /// Only log entries of this priority and above are shown.
+    pub log_priority: LogPriority,
+    pub log_timestamp_mode: LogTimestampMode,
+    /// The since/until inputs as typed, and the range last applied from them.
+    pub log_since: String,
+    pub log_until: String,
+    pub log_range: LogRange,
+    pub log_range_error: Option<String>,
+    /// The boots the journal has entries of, latest first, `None` until listed.
+    pub(crate) boots: Option<Vec<Boot>>,
+    /// The boot selector's labels, every boot's entries followed by each of `boots`.
+    pub(crate) boot_labels: Vec<String>,
+    /// Whether `log_lines` still applies while a range is set.
+    pub log_range_limited: bool,
+    /// Text searched for in the logs, and which of its matches is current.
+    pub log_search: String,
+    pub log_match: usize,
+    /// Last CPU usage reading of the selected service, used to derive `cpu_percent`.
+    pub(crate) cpu_sample: Option<(u64, Instant)>,
+    pub cpu_percent: Option<f64>,
+    /// The selected service's restart count climbed while it was shown, or is high.
+    pub(crate) restart_loop: bool,
+    /// The unit lists being loaded, each page showing a loader for its own.
+    pub(crate) loading: Loading,
+    /// Jobs queued in systemd, by id, with the unit each is for.
+    pub(crate) pending_jobs: HashMap<u32, String>,
+    /// When the list shown was last loaded.
+    pub last_refreshed: Option<Instant>,
+    /// Set when systemd's change signals can't be received and the app has to poll instead.
+    pub(crate) signals_unavailable: bool,
+    /// The scopes whose connection to systemd is lost and being re-established.
+    pub(crate) reconnecting: HashSet<ServiceScope>,
+    /// Why a scope's units can't be listed, e.g. there's no session bus, until they next are.
+    pub(crate) scope_errors: HashMap<ServiceScope, String>,
+    /// Each scope's manager once its bus was reached, shared by every operation after.
+    pub(crate) managers: HashMap<ServiceScope, SystemdManager>,
+    pub(crate) settings_labels: views::settings::SettingsLabels,
+    /// Columns left out of the list, leaving their width to the others.
+    pub(crate) hidden_columns: HashSet<ListColumn>,
+    /// Whether the list header's menu of columns is open.
+    pub(crate) column_menu_open: bool,
+    pub search: ListSearch,
+    pub state_filter: StateFilter,
+    pub file_state_filter: FileStateFilter,
+    /// The unit file state dropdown's labels, one for each of `FileStateFilter::ALL`.
+    pub(crate) file_state_labels: Vec<String>,
+    /// The manager's `SystemState` for the current scope, e.g. "degraded".
+    pub system_state: Option<String>,
+    /// Managing system units will ask for a password, which the system lists
+    /// say until dismissed.
+    pub(crate) system_needs_authentication: bool,
+    /// The page of the flat list being shown, see `Config::list_page_size`.
+    pub list_page: usize,
+    /// The row moved to with the arrow keys, by unit name.
+    pub(crate) focused_row: Option<String>,
+    /// Where the list was scrolled to, restored when coming back from the details.
+    pub(crate) list_scroll: scrollable::AbsoluteOffset,
+    pub sort_column: SortColumn,
+    pub sort_ascending: bool,
+    /// Whether the list buckets units by their name prefix, and which buckets are expanded.
+    pub group_services: bool,
+    pub hide_vendor_units: bool,
+    pub expanded_groups: HashSet<String>,
+    /// Units pinned above the list, by name.
+    pub favorites: HashSet<String>,
 }
 
 impl cosmic::Application for AppModel {
     type Executor = cosmic::executor::Default;
-    type Flags = ();
+    type Flags = Args;
     type Message = Message;
     const APP_ID: &'static str = "io.github.nikelaz.CtlDash";
 
@@ -46,8 +219,10 @@ impl cosmic::Application for AppModel {
 
     fn init(
         core: cosmic::Core,
-        _flags: Self::Flags,
+        flags: Self::Flags,
     ) -> (Self, Task<cosmic::Action<Self::Message>>) {
+        let (config_handler, config) = config::load();
+
         let mut nav = nav_bar::Model::default();
 
         nav.insert()
@@ -61,6 +236,42 @@ impl cosmic::Application for AppModel {
             .data::<Page>(Page::UserServices)
             .icon(icon::from_name("system-users-symbolic"));
 
+        nav.insert()
+            .text(fl!("timers"))
+            .data::<Page>(Page::Timers)
+            .icon(icon::from_name("alarm-symbolic"));
+
+        nav.insert()
+            .text(fl!("sockets"))
+            .data::<Page>(Page::Sockets)
+            .icon(icon::from_name("network-wired-symbolic"));
+
+        nav.insert()
+            .text(fl!("targets"))
+            .data::<Page>(Page::Targets)
+            .icon(icon::from_name("emblem-system-symbolic"));
+
+        // Open the scope given on the command line, else the chosen startup page, or
+        // reopen the one from the last session, if it still exists.
+        let startup_page = flags.scope.map(Page::services).or(config.startup_page).unwrap_or(config.page);
+        if let Some(id) = nav.iter().find(|id| nav.data::<Page>(*id) == Some(&startup_page)) {
+            nav.activate(id);
+        }
+
+        let mut detail_tabs = segmented_button::SingleSelectModel::default();
+
+        detail_tabs.insert()
+            .text(fl!("logs"))
+            .data::<DetailTab>(DetailTab::Logs)
+            .activate();
+
+        detail_tabs.insert()
+            .text(fl!("unit-file"))
+            .data::<DetailTab>(DetailTab::UnitFile);
+
+        let initial_page = *nav.active_data::<Page>().unwrap();
+        let initial_scope = initial_page.scope();
+
         // Create the about widget
         let about = About::default()
             .name(fl!("app-title"))
@@ -81,43 +292,214 @@ impl cosmic::Application for AppModel {
             about,
             nav,
             key_binds: HashMap::new(),
+            config_handler,
             system_services: Vec::new(),
             user_services: Vec::new(),
+            timers: Vec::new(),
+            sockets: Vec::new(),
+            targets: Vec::new(),
             selected_service: None,
-            current_scope: ServiceScope::System,
-            current_page: Page::SystemServices,
+            selected_names: HashSet::new(),
+            batch_results: None,
+            unit_file_changes_open: false,
+            dependencies: None,
+            dependency_tree: None,
+            dependency_kind: DependencyKind::default(),
+            expanded_dependencies: HashSet::new(),
+            detail_tabs,
+            unit_file: None,
+            note: text_editor::Content::new(),
+            note_debounce: KeyedDebounce::default(),
+            window_size_debounce: Debounce::default(),
+            current_scope: initial_scope,
+            unit_scope: ServiceScope::System,
+            current_page: initial_page,
             service_logs: "".to_string(),
-            is_loading: false,
-            search_filter: String::new(),
+            relative_logs: String::new(),
+            log_cursor: None,
+            older_log_lines: 0,
+            logs_at_start: true,
+            loading_older_logs: false,
+            include_related_logs: false,
+            action_error: None,
+            toasts: widget::Toasts::new(Message::CloseToast),
+            pending_action: None,
+            undo: None,
+            pending_shown_action: None,
+            pending_batch_action: None,
+            start_preview: None,
+            kill_menu_open: false,
+            show_processes: false,
+            command_palette: None,
+            drop_in_editor: None,
+            transient_service: None,
+            log_lines: config.log_lines,
+            follow_logs: false,
+            log_priority: LogPriority::default(),
+            log_timestamp_mode: LogTimestampMode::default(),
+            log_since: String::new(),
+            log_until: String::new(),
+            log_range: LogRange::default(),
+            log_range_error: None,
+            boots: None,
+            boot_labels: views::service_detail::boot_labels(&[]),
+            log_range_limited: false,
+            log_search: String::new(),
+            log_match: 0,
+            cpu_sample: None,
+            cpu_percent: None,
+            restart_loop: false,
+            loading: Loading::default(),
+            pending_jobs: HashMap::new(),
+            last_refreshed: None,
+            signals_unavailable: false,
+            reconnecting: HashSet::new(),
+            scope_errors: HashMap::new(),
+            managers: HashMap::new(),
+            settings_labels: views::settings::SettingsLabels::default(),
+            hidden_columns: config.hidden_columns.clone(),
+            column_menu_open: false,
+            search: ListSearch::default(),
+            // `--failed` filters this session only, leaving the saved filter alone.
+            state_filter: if flags.failed { StateFilter::Failed } else { config.state_filter },
+            file_state_filter: config.file_state_filter,
+            file_state_labels: views::service_list::file_state_labels(),
+            system_state: None,
+            system_needs_authentication: false,
+            sort_column: config.sort_column,
+            sort_ascending: config.sort_ascending,
+            group_services: config.group_services,
+            hide_vendor_units: config.hide_vendor_units,
+            expanded_groups: HashSet::new(),
+            favorites: config.favorites.clone(),
+            list_page: 0,
+            focused_row: None,
+            list_scroll: scrollable::AbsoluteOffset::default(),
+            config,
         };
 
         // Create a startup command that sets the window title and loads services.
         let title_command = app.update_title();
-        let load_command = Task::perform(async {}, |_| {
-            cosmic::Action::from(Message::LoadServices(Some(ServiceScope::System)))
+        let load_message = app.load_list_message();
+        let load_command = Task::perform(async {}, move |_| {
+            cosmic::Action::from(load_message)
         });
 
-        (app, Task::batch(vec![title_command, load_command]))
+        // Both scopes' connections are opened up front and shared by every operation
+        // after. Either may fail on its own, e.g. without a session bus.
+        let connect_commands = [ServiceScope::System, ServiceScope::User].map(|scope| {
+            Task::perform(
+                async move { SystemdManager::new(scope).await.map_err(|e| error_message(&e)) },
+                move |result| match result {
+                    Ok(manager) => cosmic::Action::from(Message::ScopeConnected(scope, manager)),
+                    Err(error) => cosmic::Action::from(Message::ScopeUnavailable(scope, error)),
+                },
+            )
+        });
+
+        let theme_command = cosmic::command::set_theme(app.config.app_theme.theme());
+
+        let mut commands = vec![title_command, load_command, theme_command];
+        commands.extend(connect_commands);
+
+        // A unit that doesn't exist leaves its scope's list open, with a toast saying so.
+        let scope = flags.service_scope();
+        if let Some(name) = flags.service {
+            commands.push(Task::perform(async {}, move |_| {
+                cosmic::Action::from(Message::ShowUnit(scope, name, None))
+            }));
+        }
+        (app, Task::batch(commands))
     }
 
     /// Elements to pack at the start of the header bar.
     fn header_start(&self) -> Vec<Element<'_, Self::Message>> {
-        let menu_bar = menu::bar(vec![menu::Tree::with_children(
-            menu::root(fl!("view")).apply(Element::from),
-            menu::items(
-                &self.key_binds,
-                vec![menu::Item::Button(fl!("about"), None, MenuAction::About)],
+        let menu_bar = menu::bar(vec![
+            menu::Tree::with_children(
+                menu::root(fl!("file")).apply(Element::from),
+                menu::items(
+                    &self.key_binds,
+                    vec![
+                        menu::Item::Button(fl!("new-transient-service"), None, MenuAction::NewTransientService),
+                        menu::Item::Divider,
+                        menu::Item::Button(fl!("export-csv"), None, MenuAction::ExportCsv),
+                        menu::Item::Button(fl!("export-json"), None, MenuAction::ExportJson),
+                    ],
+                ),
+            ),
+            menu::Tree::with_children(
+                menu::root(fl!("view")).apply(Element::from),
+                menu::items(
+                    &self.key_binds,
+                    vec![
+                        menu::Item::Button(fl!("command-palette"), None, MenuAction::CommandPalette),
+                        menu::Item::Button(fl!("reload-unit-files"), None, MenuAction::DaemonReload),
+                        menu::Item::Divider,
+                        menu::Item::Button(fl!("settings"), None, MenuAction::Settings),
+                        menu::Item::Button(fl!("about"), None, MenuAction::About),
+                    ],
+                ),
             ),
-        )]);
+        ]);
 
         vec![menu_bar.into()]
     }
 
+    /// Elements to pack at the end of the header bar.
+    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
+        if self.pending_jobs.is_empty() {
+            return Vec::new();
+        }
+
+        let badge = widget::row()
+            .push(icon::from_name("emblem-synchronizing-symbolic").size(16))
+            .push(widget::text(fl!("jobs-running", count = self.pending_jobs.len())))
+            .align_y(cosmic::iced::Alignment::Center)
+            .spacing(cosmic::theme::spacing().space_xxs);
+
+        vec![badge.into()]
+    }
+
     /// Enables the COSMIC application to create a nav bar with this model.
     fn nav_model(&self) -> Option<&nav_bar::Model> {
         Some(&self.nav)
     }
 
+    /// Display a confirmation dialog while an action is pending, otherwise the
+    /// drop-in editor or the command palette while either is open.
+    fn dialog(&self) -> Option<Element<'_, Self::Message>> {
+        if let Some((_, action, name)) = &self.pending_action {
+            return Some(views::view_confirm_dialog(*action, name));
+        }
+
+        if let Some((action, names)) = &self.pending_shown_action {
+            return Some(views::view_confirm_shown_dialog(*action, names));
+        }
+
+        if let Some((action, names)) = &self.pending_batch_action {
+            return Some(views::view_confirm_batch_dialog(*action, names));
+        }
+
+        if let Some(preview) = &self.start_preview {
+            return Some(views::view_start_preview_dialog(preview));
+        }
+
+        if let Some(editor) = &self.drop_in_editor {
+            return Some(views::view_drop_in_editor(editor));
+        }
+
+        if let Some(transient) = &self.transient_service {
+            return Some(views::view_transient_service(transient));
+        }
+
+        if let Some((action, results)) = self.batch_results.as_ref().filter(|_| self.unit_file_changes_open) {
+            return Some(views::view_unit_file_changes_dialog(*action, results));
+        }
+
+        let query = self.command_palette.as_ref()?;
+        Some(views::view_command_palette(self, query))
+    }
+
     /// Display a context drawer if the context page is requested.
     fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Self::Message>> {
         if !self.core.window.show_context {
@@ -130,6 +512,16 @@ impl cosmic::Application for AppModel {
                 |url| Message::LaunchUrl(url.to_string()),
                 Message::ToggleContextPage(ContextPage::About),
             ),
+            ContextPage::Dependencies => context_drawer::context_drawer(
+                views::view_dependencies(self),
+                Message::ToggleContextPage(ContextPage::Dependencies),
+            )
+            .title(fl!("dependencies")),
+            ContextPage::Settings => context_drawer::context_drawer(
+                views::view_settings(self),
+                Message::ToggleContextPage(ContextPage::Settings),
+            )
+            .title(fl!("settings")),
         })
     }
 
@@ -144,27 +536,127 @@ impl cosmic::Application for AppModel {
 
         match &self.current_page {
             Page::SystemServices => {
-                content = views::view_services_list(self, &self.system_services, fl!("system-services"));
+                content = views::view_services_list(self, &self.system_services, UnitList::Services(ServiceScope::System), fl!("system-services"));
             },
             Page::UserServices => {
-                content = views::view_services_list(self, &self.user_services, fl!("user-services"));
+                content = views::view_services_list(self, &self.user_services, UnitList::Services(ServiceScope::User), fl!("user-services"));
+            },
+            Page::Timers => {
+                content = views::view_services_list(self, &self.timers, UnitList::Timers, fl!("timers"));
+            },
+            Page::Sockets => {
+                content = views::view_services_list(self, &self.sockets, UnitList::Sockets, fl!("sockets"));
+            },
+            Page::Targets => {
+                content = views::view_services_list(self, &self.targets, UnitList::Targets, fl!("targets"));
             },
             Page::Details => {
                 content = views::view_service_detail(self, self.selected_service.as_ref());
             },
         }
 
-        widget::container(content)
+        let content: Element<_> = if !self.reconnecting.is_empty() {
+            widget::column()
+                .push(views::reconnecting_banner())
+                .push(content)
+                .spacing(spacing.space_s)
+                .into()
+        } else {
+            content
+        };
+
+        let content = widget::container(content)
             .width(Length::Fill)
             .height(Length::Fill)
-            .padding(cosmic::iced::Padding::from([0, spacing.space_m, spacing.space_m, spacing.space_m]))
-            .into()
+            .padding(cosmic::iced::Padding::from([0, spacing.space_m, spacing.space_s, spacing.space_m]));
+
+        let content = widget::column()
+            .push(content)
+            .push(views::view_status_bar(self));
+
+        widget::toaster(&self.toasts, content)
     }
 
     /// Register subscriptions for this application.
     fn subscription(&self) -> Subscription<Self::Message> {
-        cosmic::iced::time::every(std::time::Duration::from_secs(1))
-            .map(|_| Message::Tick)
+        // Polling is only a fallback for when systemd's signals can't be received,
+        // and can be turned off to only refresh by hand.
+        let refresh = if self.signals_unavailable {
+            match self.config.poll_interval {
+                0 => Subscription::none(),
+                seconds => cosmic::iced::time::every(Duration::from_secs(seconds.into()))
+                    .map(|_| Message::Tick),
+            }
+        } else {
+            // Each scope's signals are received on the connection kept from
+            // `ScopeConnected`, whichever unit is selected.
+            let mut refresh = Vec::new();
+            for scope in [ServiceScope::System, ServiceScope::User] {
+                let manager = self.managers.get(&scope).cloned();
+                if manager.is_some() || self.reconnecting.contains(&scope) {
+                    refresh.push(unit_changes(scope, manager));
+                }
+            }
+            if let Some(manager) = self.managers.get(&self.current_scope) {
+                refresh.push(job_changes(manager.clone()));
+            }
+            Subscription::batch(refresh)
+        };
+
+        let window_resized = cosmic::iced::window::resize_events()
+            .map(|(_id, size)| Message::WindowResized(size.width, size.height));
+
+        let command_palette_key = cosmic::iced::event::listen_with(|event, status, _id| match event {
+            cosmic::iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => match key.as_ref() {
+                keyboard::Key::Character("p") if modifiers.command() => Some(Message::ToggleCommandPalette),
+                keyboard::Key::Named(keyboard::key::Named::Escape) => Some(Message::CloseCommandPalette),
+                // Keys a focused widget, like a text input, already handled are left to it.
+                _ if status == cosmic::iced::event::Status::Captured => None,
+                keyboard::Key::Named(keyboard::key::Named::ArrowDown) => Some(Message::MoveRowFocus(true)),
+                keyboard::Key::Named(keyboard::key::Named::ArrowUp) => Some(Message::MoveRowFocus(false)),
+                keyboard::Key::Named(keyboard::key::Named::Enter) => Some(Message::OpenFocusedRow),
+                _ => None,
+            },
+            _ => None,
+        });
+
+        let mut subscriptions = vec![refresh, window_resized, command_palette_key, remote_control()];
+
+        // Keeps "Updated … ago" in the list and "Running since" in the details current.
+        if self.selected_service.is_some() || self.last_refreshed.is_some() {
+            subscriptions.push(
+                cosmic::iced::time::every(Duration::from_secs(1))
+                    .map(|_| Message::ClockTick)
+            );
+        }
+
+        for scope in [ServiceScope::System, ServiceScope::User] {
+            let Some(manager) = self.managers.get(&scope) else {
+                continue;
+            };
+
+            let notify_failures = match scope {
+                ServiceScope::System => self.config.notify_system_failures,
+                ServiceScope::User => self.config.notify_user_failures,
+            };
+            if notify_failures {
+                subscriptions.push(failure_monitor(manager.clone()));
+            }
+
+            let mut units: Vec<String> = self.config.watched_units(scope).iter().cloned().collect();
+            if !units.is_empty() {
+                units.sort();
+                subscriptions.push(watched_units(manager.clone(), units));
+            }
+        }
+
+        if self.follow_logs {
+            if let Some(service) = &self.selected_service {
+                subscriptions.push(follow_logs(service.log_units(self.include_related_logs), self.log_priority));
+            }
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     /// Handles messages emitted by the application and its widgets.
@@ -175,24 +667,299 @@ impl cosmic::Application for AppModel {
     fn on_nav_select(&mut self, id: nav_bar::Id) -> Task<cosmic::Action<Self::Message>> {
         self.nav.activate(id);
         self.selected_service = None;
-        self.search_filter.clear();
-
-        let mut scope = ServiceScope::System;
+        self.selected_names.clear();
+        self.batch_results = None;
+        self.unit_file_changes_open = false;
+        self.search.page_changed(self.config.keep_search_on_page_change);
+        self.list_page = 0;
+        let scroll_command = self.reset_list_scroll();
 
         let active_nav_page = self.nav.active_data::<Page>().unwrap();
 
+        // Jobs are only tracked for the scope being watched.
+        if self.page_scope(*active_nav_page) != self.current_scope {
+            self.pending_jobs.clear();
+        }
+
         self.current_page = *active_nav_page;
 
-        if *active_nav_page == Page::UserServices {
-            scope = ServiceScope::User;
+        if let Some(handler) = &self.config_handler {
+            if let Err(why) = self.config.set_page(handler, *active_nav_page) {
+                eprintln!("failed to save page: {why}");
+            }
         }
 
         let title_command = self.update_title();
+        let load_message = self.load_list_message();
         let load_command = Task::perform(async {}, move |_| {
-            cosmic::Action::from(Message::LoadServices(Some(scope)))
+            cosmic::Action::from(load_message)
         });
 
-        Task::batch(vec![title_command, load_command])
+        Task::batch(vec![title_command, load_command, scroll_command])
     }
 }
 
+/// Listens for systemd property changes on `scope`'s bus and reports the units
+/// they were for, leaving it to `UnitsChanged` to tell whether the list or the
+/// selected unit needs a refresh. The stream starts on the app's `manager` and
+/// only opens a connection of its own to replace one that was lost.
+fn unit_changes(scope: ServiceScope, manager: Option<SystemdManager>) -> Subscription<Message> {
+    struct UnitChanges;
+
+    Subscription::run_with_id(
+        (std::any::TypeId::of::<UnitChanges>(), scope),
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            // Counts the failed attempts to reconnect since the connection was lost.
+            let mut reconnect_attempt: Option<u32> = manager.is_none().then_some(0);
+            let mut manager = manager;
+
+            loop {
+                let connected = match manager.take() {
+                    Some(manager) => Ok(manager),
+                    None => SystemdManager::new(scope).await,
+                };
+                let connected = match connected {
+                    Ok(manager) => manager.watch_units(None).await.map(|stream| (manager, stream)),
+                    Err(e) => Err(e),
+                };
+
+                let (manager, mut stream) = match (connected, reconnect_attempt) {
+                    (Ok(connected), _) => connected,
+                    (Err(e), None) => {
+                        eprintln!("Failed to subscribe to systemd signals: {}", e);
+                        let _ = output.send(Message::SignalsUnavailable).await;
+                        return futures_util::future::pending().await;
+                    }
+                    (Err(e), Some(attempt)) => {
+                        eprintln!("Failed to reconnect to systemd: {}", e);
+                        tokio::time::sleep(reconnect_delay(attempt)).await;
+                        reconnect_attempt = Some(attempt + 1);
+                        continue;
+                    }
+                };
+
+                // The app's manager is only replaced once its connection was lost.
+                if reconnect_attempt.take().is_some()
+                    && output.send(Message::ConnectionRestored(scope, manager)).await.is_err()
+                {
+                    break;
+                }
+
+                while let Some(signal) = stream.next().await {
+                    let mut paths = HashSet::new();
+                    paths.extend(signal_path(&signal));
+
+                    // A single state change updates several properties one after another,
+                    // so wait for the burst to settle and report it once.
+                    while let Ok(Some(signal)) = tokio::time::timeout(Duration::from_millis(250), stream.next()).await {
+                        paths.extend(signal_path(&signal));
+                    }
+
+                    if output.send(Message::UnitsChanged(scope, paths)).await.is_err() {
+                        return futures_util::future::pending().await;
+                    }
+                }
+
+                // The stream only ends when the connection is gone, e.g. the bus restarted.
+                reconnect_attempt = Some(0);
+                if output.send(Message::ConnectionLost(scope)).await.is_err() {
+                    break;
+                }
+            }
+
+            futures_util::future::pending().await
+        }),
+    )
+}
+
+/// The object path of the unit a `PropertiesChanged` signal is for.
+fn signal_path(signal: &zbus::Result<zbus::Message>) -> Option<String> {
+    signal.as_ref().ok()?.header().path().map(|path| path.to_string())
+}
+
+/// Reports jobs entering and leaving systemd's queue, e.g. a slow stop. A new
+/// connection, after the last one was lost, starts a new stream.
+fn job_changes(manager: SystemdManager) -> Subscription<Message> {
+    struct JobChanges;
+
+    Subscription::run_with_id(
+        (std::any::TypeId::of::<JobChanges>(), manager.scope(), manager.connection_name()),
+        cosmic::iced::stream::channel(16, move |mut output| async move {
+            let mut stream = match manager.watch_jobs().await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Failed to subscribe to systemd job signals: {}", e);
+                    return futures_util::future::pending().await;
+                }
+            };
+
+            while let Some(signal) = stream.next().await {
+                let message = match signal.as_ref().ok().and_then(parse_job_signal) {
+                    Some(JobEvent::New { id, unit }) => Message::JobStarted(id, unit),
+                    Some(JobEvent::Removed { id, unit }) => Message::JobFinished(id, unit),
+                    None => continue,
+                };
+
+                if output.send(message).await.is_err() {
+                    break;
+                }
+            }
+
+            // The unit changes subscription reports the lost connection.
+            futures_util::future::pending().await
+        }),
+    )
+}
+
+/// Polls the manager's scope for units entering the failed state, whichever page
+/// is shown. Units that were already failed when monitoring started aren't reported.
+fn failure_monitor(manager: SystemdManager) -> Subscription<Message> {
+    struct FailureMonitor;
+
+    let scope = manager.scope();
+    Subscription::run_with_id(
+        (std::any::TypeId::of::<FailureMonitor>(), scope, manager.connection_name()),
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            let mut failed: Option<HashSet<String>> = None;
+            let mut interval = tokio::time::interval(Duration::from_secs(15));
+
+            loop {
+                interval.tick().await;
+
+                // A lost connection is replaced by the unit changes subscription,
+                // which starts a new monitor on the new one.
+                let Ok(current) = manager.list_failed_units().await else {
+                    continue;
+                };
+
+                if let Some(previous) = &failed {
+                    let units = newly_failed(previous, &current);
+                    if !units.is_empty() && output.send(Message::ServicesFailed(scope, units)).await.is_err() {
+                        break;
+                    }
+                }
+
+                failed = Some(current.into_iter().collect());
+            }
+
+            futures_util::future::pending().await
+        }),
+    )
+}
+
+/// Reports the watched `units` of the manager's scope finishing or failing,
+/// whichever page is shown. Their change signals are followed rather than
+/// polled, so even a short run between two polls isn't missed.
+fn watched_units(manager: SystemdManager, units: Vec<String>) -> Subscription<Message> {
+    struct WatchedUnits;
+
+    let scope = manager.scope();
+    Subscription::run_with_id(
+        (std::any::TypeId::of::<WatchedUnits>(), scope, manager.connection_name(), units.clone()),
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            let paths: HashMap<String, String> = units
+                .into_iter()
+                .map(|name| (unit_object_path(&name), name))
+                .collect();
+            let mut states: HashMap<String, String> = HashMap::new();
+
+            let mut stream = match manager.watch_units(None).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Failed to watch units: {}", e);
+                    return futures_util::future::pending().await;
+                }
+            };
+
+            // The states the first changes are compared against.
+            for (path, name) in &paths {
+                if let Ok(state) = manager.get_active_state(path).await {
+                    states.insert(name.clone(), state);
+                }
+            }
+
+            while let Some(signal) = stream.next().await {
+                let Some((path, name)) = signal.ok().and_then(|signal| {
+                    let path = signal.header().path()?.to_string();
+                    paths.get_key_value(&path).map(|(path, name)| (path.clone(), name.clone()))
+                }) else {
+                    continue;
+                };
+
+                let Ok(state) = manager.get_active_state(&path).await else {
+                    continue;
+                };
+
+                let previous = states.insert(name.clone(), state.clone());
+                if previous.is_some_and(|previous| is_watched_transition(&previous, &state))
+                    && output.send(Message::WatchedUnitChanged(scope, name, state)).await.is_err()
+                {
+                    break;
+                }
+            }
+
+            // The unit changes subscription reports the lost connection.
+            futures_util::future::pending().await
+        }),
+    )
+}
+
+/// Serves the app's D-Bus interface for as long as the app runs, feeding what's
+/// asked of it into the update loop. Without a session bus, or with another
+/// instance already serving it, the app just can't be driven from outside.
+fn remote_control() -> Subscription<Message> {
+    struct RemoteControl;
+
+    Subscription::run_with_id(
+        std::any::TypeId::of::<RemoteControl>(),
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            let (sender, mut messages) = tokio::sync::mpsc::unbounded_channel();
+
+            match remote::serve(sender).await {
+                // The connection serves the interface for as long as it's held.
+                Ok(_connection) => {
+                    while let Some(message) = messages.recv().await {
+                        if output.send(message).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Err(e) => eprintln!("failed to serve {}: {e}", remote::BUS_NAME),
+            }
+
+            futures_util::future::pending().await
+        }),
+    )
+}
+
+/// Streams new journal entries of `units` while the subscription is active.
+/// Dropping the subscription drops the `journalctl -f` child, which kills it.
+fn follow_logs(units: Vec<String>, priority: LogPriority) -> Subscription<Message> {
+    struct FollowLogs;
+
+    Subscription::run_with_id(
+        (std::any::TypeId::of::<FollowLogs>(), units.clone(), priority),
+        cosmic::iced::stream::channel(16, move |mut output| async move {
+            let mut child = match SystemdManager::follow_service_logs(&units, priority) {
+                Ok(child) => child,
+                Err(e) => {
+                    eprintln!("Failed to follow logs of {}: {}", units.join(", "), e);
+                    return futures_util::future::pending().await;
+                }
+            };
+
+            if let Some(stdout) = child.stdout.take() {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if output.send(Message::LogLineReceived(line)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+
+            // `child` lives as long as this future, so it's only killed once
+            // the subscription is dropped.
+            futures_util::future::pending().await
+        }),
+    )
+}
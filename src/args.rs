@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The command line, e.g. `ctl-dash --user --service foo.service` to open
+//! straight to a unit's details.
+
+use crate::systemd::ServiceScope;
+
+pub const USAGE: &str = "\
+Usage: ctl-dash [OPTIONS]
+
+Options:
+      --system          Open the system services (default)
+      --user            Open the user services
+      --service <NAME>  Open the details of the unit NAME
+      --failed          Show only the failed units
+  -h, --help            Print this help";
+
+/// What to open at launch. Without a scope the startup page from the settings is opened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    pub scope: Option<ServiceScope>,
+    pub service: Option<String>,
+    /// Starts with the list filtered to failed units, for a quick health check.
+    pub failed: bool,
+    pub help: bool,
+}
+
+impl Args {
+    /// The scope `service` is looked up in, the system's unless another was given.
+    pub fn service_scope(&self) -> ServiceScope {
+        self.scope.unwrap_or(ServiceScope::System)
+    }
+}
+
+/// Parses the arguments after the program name. Like `systemctl`, the last of
+/// `--system` and `--user` wins.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--system" => parsed.scope = Some(ServiceScope::System),
+            "--user" => parsed.scope = Some(ServiceScope::User),
+            "--failed" => parsed.failed = true,
+            "-h" | "--help" => parsed.help = true,
+            "--service" => match args.next() {
+                Some(name) if !name.is_empty() => parsed.service = Some(name),
+                _ => return Err("--service needs a unit name".to_string()),
+            },
+            _ => match arg.strip_prefix("--service=") {
+                Some(name) if !name.is_empty() => parsed.service = Some(name.to_string()),
+                Some(_) => return Err("--service needs a unit name".to_string()),
+                None => return Err(format!("unexpected argument '{arg}'")),
+            },
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Args, String> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&[]), Ok(Args::default()));
+
+        let args = parse_args(&["--user", "--service", "foo.service"]).unwrap();
+        assert_eq!(args.scope, Some(ServiceScope::User));
+        assert_eq!(args.service.as_deref(), Some("foo.service"));
+        assert_eq!(args.service_scope(), ServiceScope::User);
+
+        let args = parse_args(&["--service=sshd.service", "--user", "--system"]).unwrap();
+        assert_eq!(args.scope, Some(ServiceScope::System));
+        assert_eq!(args.service.as_deref(), Some("sshd.service"));
+
+        assert_eq!(parse_args(&["--service", "sshd.service"]).unwrap().service_scope(), ServiceScope::System);
+        assert!(parse_args(&["-h"]).unwrap().help);
+
+        let args = parse_args(&["--failed", "--user"]).unwrap();
+        assert!(args.failed);
+        assert_eq!(args.scope, Some(ServiceScope::User));
+        assert!(!parse_args(&["--user"]).unwrap().failed);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(&["--service"]).is_err());
+        assert!(parse_args(&["--service="]).is_err());
+        assert!(parse_args(&["--verbose"]).is_err());
+        assert!(parse_args(&["foo.service"]).is_err());
+    }
+}
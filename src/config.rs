@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::AppModel;
+use crate::systemd::ServiceScope;
+use crate::types::{AppTheme, FileStateFilter, ListColumn, ListDensity, Page, SortColumn, StateFilter};
+use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use std::collections::{HashMap, HashSet};
+
+/// Units that always ask for confirmation before being stopped or disabled,
+/// whatever their scope.
+const DEFAULT_CRITICAL_UNITS: &[&str] = &[
+    "dbus.service",
+    "dbus-broker.service",
+    "NetworkManager.service",
+    "systemd-logind.service",
+    "systemd-journald.service",
+    "systemd-udevd.service",
+    "polkit.service",
+    "gdm.service",
+    "sddm.service",
+    "cosmic-greeter.service",
+    "pipewire.service",
+    "wireplumber.service",
+];
+
+/// Settings that are persisted between launches.
+#[derive(Debug, Clone, CosmicConfigEntry, PartialEq)]
+#[version = 1]
+pub struct Config {
+    /// The page shown last, reopened at startup unless `startup_page` is set.
+    pub page: Page,
+    pub startup_page: Option<Page>,
+    pub app_theme: AppTheme,
+    /// Seconds between reloads when systemd's change signals can't be received,
+    /// 0 to only reload by hand.
+    pub poll_interval: u32,
+    pub sort_column: SortColumn,
+    pub sort_ascending: bool,
+    pub group_services: bool,
+    /// Leaves units the user can't enable or disable out of the list.
+    pub hide_vendor_units: bool,
+    pub state_filter: StateFilter,
+    pub file_state_filter: FileStateFilter,
+    pub log_lines: u32,
+    /// How many rows the list shows per page, or groups when it is grouped.
+    pub list_page_size: u32,
+    pub list_density: ListDensity,
+    pub hidden_columns: HashSet<ListColumn>,
+    pub window_width: f32,
+    pub window_height: f32,
+    /// Extra units to confirm before stopping or disabling. Every system unit is
+    /// confirmed anyway, so this is mostly useful for user units.
+    pub critical_units: Vec<String>,
+    /// Units pinned above the list, by name.
+    pub favorites: HashSet<String>,
+    /// Scopes in which a service entering the failed state raises a desktop notification.
+    pub notify_system_failures: bool,
+    pub notify_user_failures: bool,
+    /// Units whose finishing or failing raises a desktop notification, by scope.
+    pub watched_system_units: HashSet<String>,
+    pub watched_user_units: HashSet<String>,
+    /// The user's own notes on units, by scope and unit name.
+    pub system_notes: HashMap<String, String>,
+    pub user_notes: HashMap<String, String>,
+    /// Keeps the app, and its failure notifications, running after the window is
+    /// closed. Read at startup.
+    pub run_in_background: bool,
+    /// Carries the list's search over to the next page instead of clearing it.
+    pub keep_search_on_page_change: bool,
+    /// Recent searches of the list, latest first, see `search::remember`.
+    pub search_history: Vec<String>,
+    /// Shows only the header of a unit's logs, leaving the room to its details.
+    pub logs_collapsed: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            page: Page::SystemServices,
+            startup_page: None,
+            app_theme: AppTheme::default(),
+            poll_interval: 1,
+            sort_column: SortColumn::default(),
+            sort_ascending: true,
+            group_services: false,
+            hide_vendor_units: false,
+            state_filter: StateFilter::default(),
+            file_state_filter: FileStateFilter::default(),
+            log_lines: 100,
+            list_page_size: 100,
+            list_density: ListDensity::default(),
+            hidden_columns: HashSet::new(),
+            window_width: 1024.0,
+            window_height: 768.0,
+            critical_units: DEFAULT_CRITICAL_UNITS.iter().map(|unit| unit.to_string()).collect(),
+            favorites: HashSet::new(),
+            notify_system_failures: false,
+            notify_user_failures: false,
+            watched_system_units: HashSet::new(),
+            watched_user_units: HashSet::new(),
+            system_notes: HashMap::new(),
+            user_notes: HashMap::new(),
+            run_in_background: false,
+            keep_search_on_page_change: false,
+            search_history: Vec::new(),
+            logs_collapsed: false,
+        }
+    }
+}
+
+impl Config {
+    /// The watched units of `scope`.
+    pub fn watched_units(&self, scope: ServiceScope) -> &HashSet<String> {
+        match scope {
+            ServiceScope::System => &self.watched_system_units,
+            ServiceScope::User => &self.watched_user_units,
+        }
+    }
+
+    /// The notes on the units of `scope`.
+    pub fn notes(&self, scope: ServiceScope) -> &HashMap<String, String> {
+        match scope {
+            ServiceScope::System => &self.system_notes,
+            ServiceScope::User => &self.user_notes,
+        }
+    }
+}
+
+/// Loads the persisted config along with the handler used to write it back.
+///
+/// A missing config falls back to the defaults, and a corrupt one keeps every
+/// field that could still be read.
+pub fn load() -> (Option<cosmic_config::Config>, Config) {
+    match cosmic_config::Config::new(<AppModel as cosmic::Application>::APP_ID, Config::VERSION) {
+        Ok(handler) => {
+            let config = match Config::get_entry(&handler) {
+                Ok(config) => config,
+                Err((_errors, config)) => config,
+            };
+            (Some(handler), config)
+        }
+        Err(why) => {
+            eprintln!("failed to open config: {why}");
+            (None, Config::default())
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Lets only the last of a burst of changes take effect.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Each change gets a token that's carried by a message delayed until the burst
+/// is expected to be over. When the message arrives, it's only acted on if no
+/// newer change came in meanwhile.
+#[derive(Debug, Default)]
+pub struct Debounce {
+    generation: u64,
+}
+
+impl Debounce {
+    /// Registers a change and returns its token.
+    pub fn change(&mut self) -> u64 {
+        self.generation += 1;
+        self.generation
+    }
+
+    /// Whether `token` belongs to the latest change.
+    pub fn is_latest(&self, token: u64) -> bool {
+        token == self.generation
+    }
+}
+
+/// A `Debounce` for each of several keys, so a burst of changes to one doesn't
+/// hold back the last change to another, e.g. notes on two units edited in turn.
+#[derive(Debug)]
+pub struct KeyedDebounce<K> {
+    last_token: u64,
+    latest: HashMap<K, u64>,
+}
+
+impl<K> Default for KeyedDebounce<K> {
+    fn default() -> Self {
+        Self { last_token: 0, latest: HashMap::new() }
+    }
+}
+
+impl<K: Eq + Hash> KeyedDebounce<K> {
+    /// Registers a change to `key` and returns its token.
+    pub fn change(&mut self, key: K) -> u64 {
+        self.last_token += 1;
+        self.latest.insert(key, self.last_token);
+        self.last_token
+    }
+
+    /// Whether `token` belongs to the latest change to `key`, which is then
+    /// forgotten, since no later message can carry it.
+    pub fn settle(&mut self, key: &K, token: u64) -> bool {
+        let is_latest = self.latest.get(key) == Some(&token);
+        if is_latest {
+            self.latest.remove(key);
+        }
+        is_latest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_last_change_applies() {
+        let mut debounce = Debounce::default();
+        let tokens: Vec<u64> = (0..3).map(|_| debounce.change()).collect();
+
+        let applied: Vec<u64> = tokens.into_iter().filter(|token| debounce.is_latest(*token)).collect();
+        assert_eq!(applied.len(), 1);
+    }
+
+    #[test]
+    fn test_settled_change_applies() {
+        let mut debounce = Debounce::default();
+        let first = debounce.change();
+        assert!(debounce.is_latest(first));
+
+        let second = debounce.change();
+        assert!(!debounce.is_latest(first));
+        assert!(debounce.is_latest(second));
+    }
+
+    #[test]
+    fn test_keys_settle_apart() {
+        let mut debounce = KeyedDebounce::default();
+        let a = debounce.change("a.service");
+        let b = debounce.change("b.service");
+
+        // Changing b doesn't make the change to a stale.
+        assert!(debounce.settle(&"a.service", a));
+        assert!(debounce.settle(&"b.service", b));
+
+        let first = debounce.change("a.service");
+        let second = debounce.change("a.service");
+        assert!(!debounce.settle(&"a.service", first));
+        assert!(debounce.settle(&"a.service", second));
+        // Once settled, a token is never the latest again.
+        assert!(!debounce.settle(&"a.service", second));
+    }
+}
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Serializes unit lists for the Export menu actions.
+
+use crate::systemd::SystemdService;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// The exported fields of a unit.
+#[derive(Serialize)]
+struct ExportRow<'a> {
+    name: &'a str,
+    description: &'a str,
+    load_state: &'a str,
+    active_state: &'a str,
+    sub_state: &'a str,
+    unit_file_state: &'a str,
+    unit_path: &'a str,
+}
+
+impl<'a> From<&'a SystemdService> for ExportRow<'a> {
+    fn from(service: &'a SystemdService) -> Self {
+        Self {
+            name: &service.name,
+            description: &service.description,
+            load_state: &service.load_state,
+            active_state: &service.active_state,
+            sub_state: &service.sub_state,
+            unit_file_state: &service.unit_file_state,
+            unit_path: &service.unit_path,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "name,description,load_state,active_state,sub_state,unit_file_state,unit_path";
+
+pub fn export(services: &[&SystemdService], format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(services)),
+        ExportFormat::Json => {
+            let rows: Vec<ExportRow> = services.iter().map(|s| ExportRow::from(*s)).collect();
+            serde_json::to_string_pretty(&rows).map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn to_csv(services: &[&SystemdService]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+
+    for service in services {
+        let row = ExportRow::from(*service);
+        let fields = [
+            row.name,
+            row.description,
+            row.load_state,
+            row.active_state,
+            row.sub_state,
+            row.unit_file_state,
+            row.unit_path,
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_csv_quotes_fields() {
+        let service = SystemdService {
+            name: "foo.service".to_string(),
+            description: "Foo, the \"bar\" daemon".to_string(),
+            load_state: "loaded".to_string(),
+            active_state: "active".to_string(),
+            sub_state: "running".to_string(),
+            unit_file_state: "enabled".to_string(),
+            unit_path: "/org/freedesktop/systemd1/unit/foo_2eservice".to_string(),
+            ..Default::default()
+        };
+
+        let csv = to_csv(&[&service]);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert_eq!(
+            lines.next(),
+            Some("foo.service,\"Foo, the \"\"bar\"\" daemon\",loaded,active,running,enabled,/org/freedesktop/systemd1/unit/foo_2eservice")
+        );
+        assert_eq!(lines.next(), None);
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Which unit lists are being loaded.
+
+use crate::systemd::{merge_services, ServiceScope, SystemdService};
+use std::collections::HashSet;
+
+/// A list of units that is loaded on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitList {
+    Services(ServiceScope),
+    Timers,
+    Sockets,
+    Targets,
+}
+
+/// The lists being loaded. Each is tracked apart, so a page only shows a loader
+/// for its own list, whichever of the loads started or finished last.
+#[derive(Debug, Default)]
+pub struct Loading {
+    lists: HashSet<UnitList>,
+}
+
+impl Loading {
+    pub fn start(&mut self, list: UnitList) {
+        self.lists.insert(list);
+    }
+
+    /// Called when `list` was loaded, or failed to.
+    pub fn finish(&mut self, list: UnitList) {
+        self.lists.remove(&list);
+    }
+
+    pub fn is_loading(&self, list: UnitList) -> bool {
+        self.lists.contains(&list)
+    }
+}
+
+/// Takes in the services `scope` listed: they're merged into that scope's units,
+/// and only its list stops loading, whichever scope was asked for first.
+pub fn services_loaded(
+    loading: &mut Loading,
+    system_services: &mut Vec<SystemdService>,
+    user_services: &mut Vec<SystemdService>,
+    scope: ServiceScope,
+    services: Vec<SystemdService>,
+) {
+    loading.finish(UnitList::Services(scope));
+
+    match scope {
+        ServiceScope::System => merge_services(system_services, services),
+        ServiceScope::User => merge_services(user_services, services),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn services(names: &[&str]) -> Vec<SystemdService> {
+        names
+            .iter()
+            .map(|name| SystemdService { name: name.to_string(), ..Default::default() })
+            .collect()
+    }
+
+    fn names(services: &[SystemdService]) -> Vec<&str> {
+        services.iter().map(|service| service.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_concurrent_scope_loads() {
+        let system = UnitList::Services(ServiceScope::System);
+        let user = UnitList::Services(ServiceScope::User);
+        let mut loading = Loading::default();
+
+        loading.start(system);
+        loading.start(user);
+        assert!(loading.is_loading(system));
+        assert!(loading.is_loading(user));
+        assert!(!loading.is_loading(UnitList::Timers));
+
+        // The user list arriving first leaves the system one loading.
+        loading.finish(user);
+        assert!(loading.is_loading(system));
+        assert!(!loading.is_loading(user));
+
+        loading.finish(system);
+        assert!(!loading.is_loading(system));
+
+        // A list that isn't loading is unaffected by another finishing.
+        loading.start(UnitList::Sockets);
+        loading.finish(UnitList::Timers);
+        assert!(loading.is_loading(UnitList::Sockets));
+    }
+
+    #[test]
+    fn test_services_loaded_for_both_scopes() {
+        let system = UnitList::Services(ServiceScope::System);
+        let user = UnitList::Services(ServiceScope::User);
+        let mut loading = Loading::default();
+        let mut system_services = Vec::new();
+        let mut user_services = Vec::new();
+
+        // Both lists are asked for at startup, and the user one answers first.
+        loading.start(system);
+        loading.start(user);
+        services_loaded(&mut loading, &mut system_services, &mut user_services, ServiceScope::User, services(&["pipewire.service"]));
+        assert!(loading.is_loading(system));
+        assert!(!loading.is_loading(user));
+        assert!(system_services.is_empty());
+        assert_eq!(names(&user_services), ["pipewire.service"]);
+
+        services_loaded(&mut loading, &mut system_services, &mut user_services, ServiceScope::System, services(&["sshd.service", "cron.service"]));
+        assert!(!loading.is_loading(system));
+        assert_eq!(names(&system_services), ["sshd.service", "cron.service"]);
+        assert_eq!(names(&user_services), ["pipewire.service"]);
+
+        // A refresh of one scope leaves the other's units alone.
+        loading.start(system);
+        loading.start(user);
+        services_loaded(&mut loading, &mut system_services, &mut user_services, ServiceScope::System, services(&["sshd.service"]));
+        assert!(loading.is_loading(user));
+        assert_eq!(names(&system_services), ["sshd.service"]);
+        assert_eq!(names(&user_services), ["pipewire.service"]);
+    }
+}
@@ -1,27 +1,58 @@
 // SPDX-License-Identifier: MPL-2.0
 
 mod app;
+mod args;
+mod config;
+mod debounce;
+mod export;
 mod i18n;
+mod loading;
 mod message;
+mod notes;
+mod notifications;
+mod paging;
+mod remote;
+mod search;
 mod systemd;
 mod types;
 mod update;
 mod views;
 
 fn main() -> cosmic::iced::Result {
+    let args = match args::parse(std::env::args().skip(1)) {
+        Ok(args) if args.help => {
+            println!("{}", args::USAGE);
+            return Ok(());
+        }
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{}", args::USAGE);
+            std::process::exit(2);
+        }
+    };
+
     // Get the system's preferred languages.
     let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
 
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
+    // Restore the window size from the last session.
+    let (_, config) = config::load();
+
+    // Failure notifications come from subscriptions, so they stop when the app exits.
+    let exit_on_close = !config.run_in_background;
+
     // Settings for configuring the application window and iced runtime.
-    let settings = cosmic::app::Settings::default().size_limits(
-        cosmic::iced::Limits::NONE
-            .min_width(360.0)
-            .min_height(180.0),
-    );
-
-    // Starts the application's event loop with `()` as the application's flags.
-    cosmic::app::run::<app::AppModel>(settings, ())
+    let settings = cosmic::app::Settings::default()
+        .size(cosmic::iced::Size::new(config.window_width, config.window_height))
+        .size_limits(
+            cosmic::iced::Limits::NONE
+                .min_width(360.0)
+                .min_height(180.0),
+        )
+        .exit_on_close(exit_on_close);
+
+    // Starts the application's event loop with the command line as the application's flags.
+    cosmic::app::run::<app::AppModel>(settings, args)
 }
@@ -1,7 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::systemd::{ServiceScope, SystemdService};
-use crate::types::ContextPage;
+use crate::export::ExportFormat;
+use crate::loading::UnitList;
+use crate::systemd::{Boot, DependencyKind, DependencyNode, KillSignal, LogPage, LogPriority, ServiceAction, ServiceScope, SystemdManager, SystemdService, UnitDependencies};
+use crate::types::{AppTheme, ContextPage, DetailTab, FileStateFilter, ListColumn, ListDensity, LogTimestampMode, Page, SortColumn, StateFilter};
+use cosmic::iced::widget::{scrollable, text_editor};
+use cosmic::widget::{segmented_button, ToastId};
+use std::collections::HashSet;
+use std::path::PathBuf;
 
 /// Messages emitted by the application and its widgets.
 #[derive(Debug, Clone)]
@@ -10,17 +16,179 @@ pub enum Message {
     ToggleContextPage(ContextPage),
     LoadServices(Option<ServiceScope>),
     ServicesLoaded(ServiceScope, Vec<SystemdService>),
+    /// The scope's bus was reached, with the manager every operation on it reuses.
+    ScopeConnected(ServiceScope, SystemdManager),
+    /// The scope's bus couldn't be reached or its units listed, with why.
+    ScopeUnavailable(ServiceScope, String),
+    /// Whether system units can be managed without authenticating.
+    SystemAuthorizationChecked(bool),
+    DismissAuthenticationNotice,
+    LoadTimers(ServiceScope),
+    TimersLoaded(ServiceScope, Vec<SystemdService>),
+    LoadSockets(ServiceScope),
+    SocketsLoaded(ServiceScope, Vec<SystemdService>),
+    /// Switches the Timers and Sockets pages between the system's and the user's units.
+    SetUnitScope(ServiceScope),
+    LoadTargets,
+    TargetsLoaded(Vec<SystemdService>),
     SelectService(SystemdService),
     BackToList,
     StartService(String),
+    PreviewStart(String),
+    StartPreviewLoaded(String, Result<Vec<String>, String>),
+    ConfirmStartPreview,
     StopService(String),
     RestartService(String),
+    ReloadService(String),
+    ReloadOrRestartService(String),
     EnableService(String),
     DisableService(String),
-    ServiceActionComplete,
-    LogsLoaded(String),
+    MaskService(String),
+    UnmaskService(String),
+    ResetFailedService(String),
+    IsolateTarget(String),
+    ToggleKillMenu,
+    KillService(String, KillSignal),
+    FreezeService(String, bool),
+    ToggleProcesses,
+    ServiceActionResult(ServiceScope, ServiceAction, String, Result<(), String>),
+    /// Takes back the last action, from its toast.
+    UndoAction(ToastId),
+    DismissActionError,
+    ConfirmPendingAction,
+    CancelPendingAction,
+    CloseToast(ToastId),
+    SetServicesSelected(Vec<String>, bool),
+    ClearSelection,
+    BatchAction(ServiceAction),
+    BatchActionComplete(ServiceAction, Vec<(String, Result<Vec<String>, String>)>),
+    DismissBatchResults,
+    ShowUnitFileChanges(bool),
+    /// Starts or stops every unit the list shows, once confirmed.
+    ActOnShown(ServiceAction),
+    ConfirmShownAction,
+    ConfirmBatchAction,
+    DaemonReload,
+    Export(ExportFormat),
+    /// The path written to, `None` if the file dialog was cancelled.
+    ExportComplete(Result<Option<PathBuf>, String>),
+    DaemonReloadComplete(UnitList, Result<(), String>),
+    LogsLoaded(LogPage),
+    LoadOlderLogs,
+    /// Entries before the cursor, which is the oldest loaded when they were asked for.
+    OlderLogsLoaded(String, Result<LogPage, String>),
+    DependenciesLoaded(String, UnitDependencies),
+    DependencyKindChanged(DependencyKind),
+    DependencyTreeLoaded(String, DependencyKind, Result<DependencyNode, String>),
+    ToggleDependencyNode(String),
+    DetailTabSelected(segmented_button::Entity),
+    UnitFileLoaded(String, String),
+    FollowLogs(bool),
+    IncludeRelatedLogs(bool),
+    CollapseLogs(bool),
+    LogPriorityChanged(LogPriority),
+    LogTimestampModeChanged(LogTimestampMode),
+    LogSinceChanged(String),
+    LogUntilChanged(String),
+    ApplyLogRange,
+    ClearLogRange,
+    LimitLogRange(bool),
+    RefreshBoots,
+    BootsLoaded(Result<Vec<Boot>, String>),
+    /// An index into the boot selector's options, 0 being every boot.
+    SelectBoot(usize),
+    LogSearchChanged(String),
+    NextLogMatch,
+    PreviousLogMatch,
+    CopyLogs,
+    /// Copies a unit's name or path.
+    CopyText(String),
+    SaveLogs,
+    OpenLogsInTerminal,
+    LogsOpenedInTerminal(Result<(), String>),
+    /// Opens the directory holding this unit file.
+    OpenUnitFileLocation(String),
+    UnitFileLocationOpened(Result<(), String>),
+    /// The path written to, `None` if the file dialog was cancelled.
+    LogsSaved(Result<Option<PathBuf>, String>),
+    LogLineReceived(String),
     RefreshCurrentService,
-    CurrentServiceRefreshed(Option<SystemdService>, String),
+    CurrentServiceRefreshed(Option<SystemdService>, LogPage),
     Tick,
+    /// The object paths of a scope's units whose properties just changed.
+    UnitsChanged(ServiceScope, HashSet<String>),
+    JobStarted(u32, String),
+    JobFinished(u32, String),
+    RefreshServices,
+    /// Redraws times shown relative to now.
+    ClockTick,
+    SignalsUnavailable,
+    ConnectionLost(ServiceScope),
+    /// The scope's bus was reached again, with the manager to use from now on.
+    ConnectionRestored(ServiceScope, SystemdManager),
     SearchFilterChanged(String),
+    SetStateFilter(StateFilter),
+    SetFileStateFilter(FileStateFilter),
+    SystemStateLoaded(ServiceScope, Option<String>),
+    /// Applies the search input if it hasn't changed since the token was issued.
+    ApplySearchFilter(u64),
+    SubmitSearch,
+    OpenSearchHistory,
+    CloseSearchHistory,
+    SearchFromHistory(String),
+    SortBy(SortColumn),
+    ListPage(usize),
+    /// Moves the list's keyboard focus to the next row, or the previous one.
+    MoveRowFocus(bool),
+    OpenFocusedRow,
+    ListScrolled(scrollable::AbsoluteOffset),
+    GroupServices(bool),
+    HideVendorUnits(bool),
+    ToggleGroup(String),
+    ToggleFavorite(String),
+    WindowResized(f32, f32),
+    /// Saves the window size, unless the window was resized again since.
+    SaveWindowSize(f32, f32, u64),
+    SetFailureNotifications(ServiceScope, bool),
+    SetRunInBackground(bool),
+    SetKeepSearch(bool),
+    SetAppTheme(AppTheme),
+    SetStartupPage(Option<Page>),
+    SetPollInterval(u32),
+    SetListDensity(ListDensity),
+    ToggleColumnMenu,
+    SetColumnVisible(ListColumn, bool),
+    SetLogLines(u32),
+    ServicesFailed(ServiceScope, Vec<String>),
+    ToggleWatch(String),
+    /// A watched unit finished or failed, with the active state it entered.
+    WatchedUnitChanged(ServiceScope, String, String),
+    EditDropIn(String),
+    /// The drop-in's path and contents, `None` if it doesn't exist yet.
+    DropInLoaded(ServiceScope, String, Result<(String, Option<String>), String>),
+    DropInEdited(text_editor::Action),
+    SaveDropIn,
+    DropInSaved(Result<(), String>),
+    CloseDropInEditor,
+    NoteEdited(text_editor::Action),
+    /// Saves the note on a unit, unless it was edited again since.
+    SaveNote(ServiceScope, String, String, u64),
+    OpenTransientService,
+    TransientCommandChanged(String),
+    TransientDescriptionChanged(String),
+    TransientScopeChanged(ServiceScope),
+    StartTransientService,
+    /// The new unit's name, once it was started.
+    TransientServiceStarted(ServiceScope, Result<String, String>),
+    CloseTransientService,
+    CopyToUserScope(String),
+    /// Where the copy was written and the directives left out of it.
+    CopiedToUserScope(Result<(String, Vec<String>), String>),
+    ToggleCommandPalette,
+    CloseCommandPalette,
+    CommandPaletteQueryChanged(String),
+    CommandPaletteAction(ServiceScope, ServiceAction, String),
+    /// Asked for over D-Bus, see `remote`.
+    ShowUnit(ServiceScope, String, Option<DetailTab>),
+    UnitNotFound(String),
 }
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The notes the user keeps on units.
+
+use std::collections::HashMap;
+
+/// Sets the note on `name` to `text`, removing it once it's left blank.
+/// Trailing whitespace isn't kept, the editor always ends in a newline.
+pub fn set_note(notes: &mut HashMap<String, String>, name: String, text: &str) {
+    match text.trim_end() {
+        "" => notes.remove(&name),
+        text => notes.insert(name, text.to_string()),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_note() {
+        let mut notes = HashMap::new();
+
+        set_note(&mut notes, "foo.service".to_string(), "Restarts at night\n");
+        assert_eq!(notes["foo.service"], "Restarts at night");
+
+        set_note(&mut notes, "bar.service".to_string(), "Ask before stopping");
+        set_note(&mut notes, "foo.service".to_string(), "Moved to a timer\n\n");
+        assert_eq!(notes["foo.service"], "Moved to a timer");
+        assert_eq!(notes["bar.service"], "Ask before stopping");
+
+        // Clearing the text removes the note rather than keeping it empty.
+        set_note(&mut notes, "foo.service".to_string(), " \n");
+        assert!(!notes.contains_key("foo.service"));
+        assert_eq!(notes.len(), 1);
+    }
+}
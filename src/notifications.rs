@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MPL-2.0
+//
+// Reference for the notifications dbus interface:
+// https://specifications.freedesktop.org/notification-spec/latest/
+
+use std::collections::HashMap;
+use zbus::zvariant::Value;
+
+/// Shows a desktop notification through the notification server on the session bus.
+pub async fn notify(app_name: &str, summary: &str, body: &str) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        "org.freedesktop.Notifications",
+        "/org/freedesktop/Notifications",
+        "org.freedesktop.Notifications",
+    )
+    .await?;
+
+    let actions: Vec<&str> = Vec::new();
+    let hints: HashMap<&str, Value> = HashMap::new();
+
+    // A replaces_id of 0 always opens a new notification, and an expire_timeout
+    // of -1 leaves the timeout to the server.
+    let _id: u32 = proxy
+        .call(
+            "Notify",
+            &(app_name, 0u32, "dialog-error-symbolic", summary, body, actions, hints, -1i32),
+        )
+        .await?;
+    Ok(())
+}
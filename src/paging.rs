@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Splitting the unit list into groups and pages, so only one page of rows is built.
+
+use crate::systemd::{parse_unit_name, SystemdService};
+
+/// The page to show out of `count` items, clamped to the last one, and the number of pages.
+pub fn page_of(count: usize, page: usize, page_size: usize) -> (usize, usize) {
+    let page_count = count.div_ceil(page_size.max(1));
+    (page.min(page_count.saturating_sub(1)), page_count)
+}
+
+/// The items on `page`, see `page_of`.
+pub fn page_items<T>(items: Vec<T>, page: usize, page_size: usize) -> Vec<T> {
+    let page_size = page_size.max(1);
+    let (page, _) = page_of(items.len(), page, page_size);
+    items.into_iter().skip(page * page_size).take(page_size).collect()
+}
+
+/// Buckets services by `group_name`, keeping the order in which each bucket first appears.
+pub fn group_services<'a>(services: &[&'a SystemdService]) -> Vec<(&'a str, Vec<&'a SystemdService>)> {
+    let mut groups: Vec<(&str, Vec<&SystemdService>)> = Vec::new();
+
+    for &service in services {
+        let group = group_name(&service.name);
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, members)) => members.push(service),
+            None => groups.push((group, vec![service])),
+        }
+    }
+
+    groups
+}
+
+/// The prefix a unit is grouped under: its template for instances, e.g. "getty@",
+/// otherwise the text before the first `-`, e.g. "systemd".
+fn group_name(name: &str) -> &str {
+    let unit_name = parse_unit_name(name);
+    if unit_name.instance.is_some() {
+        return &name[..=unit_name.prefix.len()];
+    }
+
+    match name.find('-') {
+        Some(index) => &name[..index],
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn services(count: usize) -> Vec<SystemdService> {
+        (0..count)
+            .map(|index| SystemdService {
+                name: format!("unit{}-{index}.service", index % 300),
+                description: format!("Synthetic unit {index}"),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_page_of() {
+        assert_eq!(page_of(0, 0, 100), (0, 0));
+        assert_eq!(page_of(250, 1, 100), (1, 3));
+        // A page left behind by a shorter list falls back to the last one.
+        assert_eq!(page_of(250, 7, 100), (2, 3));
+    }
+
+    #[test]
+    fn test_group_services() {
+        let units = ["getty@tty1.service", "systemd-logind.service", "getty@tty2.service", "cron.service"]
+            .map(|name| SystemdService { name: name.to_string(), ..Default::default() });
+        let refs: Vec<&SystemdService> = units.iter().collect();
+
+        let groups: Vec<(&str, usize)> = group_services(&refs)
+            .into_iter()
+            .map(|(group, members)| (group, members.len()))
+            .collect();
+        assert_eq!(groups, [("getty@", 2), ("systemd", 1), ("cron.service", 1)]);
+    }
+
+    #[test]
+    fn test_pages_of_1000_units() {
+        let units = services(1000);
+        let refs: Vec<&SystemdService> = units.iter().collect();
+
+        // Only a page of the 1000 units gets a row.
+        let rows = page_items(refs.clone(), 3, 100);
+        assert_eq!(rows.len(), 100);
+        assert_eq!(rows[0].name, "unit0-300.service");
+        assert_eq!(page_of(refs.len(), 3, 100), (3, 10));
+
+        // The grouped view pages through the groups, so a page has at most
+        // 100 headers rather than the 300 groups of the whole list.
+        let groups = group_services(&refs);
+        assert_eq!(groups.len(), 300);
+        let shown = page_items(groups, 2, 100);
+        assert_eq!(shown.len(), 100);
+        assert_eq!(shown[0].0, "unit200");
+    }
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A D-Bus interface on the session bus through which other tools can drive
+//! the running app, e.g. to open a service's logs from a terminal:
+//!
+//! ```sh
+//! busctl --user call io.github.nikelaz.CtlDash /io/github/nikelaz/CtlDash \
+//!     io.github.nikelaz.CtlDash ShowLogs ss nginx.service system
+//! ```
+
+use crate::message::Message;
+use crate::systemd::ServiceScope;
+use crate::types::DetailTab;
+use tokio::sync::mpsc::UnboundedSender;
+use zbus::fdo;
+
+pub const BUS_NAME: &str = "io.github.nikelaz.CtlDash";
+pub const OBJECT_PATH: &str = "/io/github/nikelaz/CtlDash";
+
+/// Forwards each call to the update loop as a message.
+struct Remote {
+    messages: UnboundedSender<Message>,
+}
+
+impl Remote {
+    fn show(&self, name: String, scope: &str, tab: Option<DetailTab>) -> fdo::Result<()> {
+        let scope = match scope {
+            "system" => ServiceScope::System,
+            "user" => ServiceScope::User,
+            _ => return Err(fdo::Error::InvalidArgs(format!("unknown scope \"{scope}\", expected \"system\" or \"user\""))),
+        };
+        if name.is_empty() {
+            return Err(fdo::Error::InvalidArgs("no unit name given".to_string()));
+        }
+
+        self.messages
+            .send(Message::ShowUnit(scope, name, tab))
+            .map_err(|_| fdo::Error::Failed("the app is shutting down".to_string()))
+    }
+}
+
+#[zbus::interface(name = "io.github.nikelaz.CtlDash")]
+impl Remote {
+    /// Opens the details of the unit `name` of `scope`, "system" or "user".
+    async fn show_service(&self, name: String, scope: String) -> fdo::Result<()> {
+        self.show(name, &scope, None)
+    }
+
+    /// Like `ShowService`, with the logs tab selected.
+    async fn show_logs(&self, name: String, scope: String) -> fdo::Result<()> {
+        self.show(name, &scope, Some(DetailTab::Logs))
+    }
+}
+
+/// Takes `BUS_NAME` and serves the interface until the returned connection is
+/// dropped, sending what's asked of it to `messages`. Fails if there's no
+/// session bus or another instance already holds the name.
+pub async fn serve(messages: UnboundedSender<Message>) -> zbus::Result<zbus::Connection> {
+    zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, Remote { messages })?
+        .build()
+        .await
+}
@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The search over the unit list.
+
+use crate::debounce::Debounce;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+/// How much more a match in the name counts than one in the description.
+const NAME_MATCH_WEIGHT: i64 = 2;
+
+/// How many recent searches are kept.
+pub const HISTORY_LEN: usize = 10;
+
+/// The search box's text, and the filter applied from it once typing pauses.
+#[derive(Debug, Default)]
+pub struct ListSearch {
+    pub input: String,
+    pub filter: String,
+    pub debounce: Debounce,
+    /// Whether the recent searches are shown under the search box.
+    pub history_open: bool,
+}
+
+impl ListSearch {
+    /// Called when another nav page is selected. The search is cleared, unless
+    /// `keep` carries it over, in which case text still being typed applies now.
+    pub fn page_changed(&mut self, keep: bool) {
+        if keep {
+            self.filter = self.input.clone();
+        } else {
+            self.input.clear();
+            self.filter.clear();
+        }
+    }
+}
+
+/// Puts `term` first among the recent searches in `history`, moving it there if
+/// it was searched for before and dropping the oldest beyond `HISTORY_LEN`.
+pub fn remember(history: &mut Vec<String>, term: &str) {
+    let term = term.trim();
+    if term.is_empty() {
+        return;
+    }
+
+    history.retain(|recent| recent != term);
+    history.insert(0, term.to_string());
+    history.truncate(HISTORY_LEN);
+}
+
+/// A search split into its space separated terms. A unit matches if any of the
+/// terms does and none of the excluded ones, written with a leading `!`. Terms
+/// with a `*` are globs, the others are fuzzy matched.
+#[derive(Debug, Default, PartialEq)]
+pub struct Query<'a> {
+    pub terms: Vec<&'a str>,
+    pub excluded: Vec<&'a str>,
+}
+
+impl<'a> Query<'a> {
+    pub fn parse(text: &'a str) -> Self {
+        let mut query = Query::default();
+        for term in text.split_whitespace() {
+            match term.strip_prefix('!') {
+                Some("") => {}
+                Some(excluded) => query.excluded.push(excluded),
+                None => query.terms.push(term),
+            }
+        }
+        query
+    }
+
+    /// How well a unit with `name` and `description` matches, `None` if it
+    /// doesn't. Only excluding terms match everything else equally.
+    pub fn score(&self, matcher: &SkimMatcherV2, name: &str, description: &str) -> Option<i64> {
+        if self.excluded.iter().any(|term| contains(term, name) || contains(term, description)) {
+            return None;
+        }
+
+        if self.terms.is_empty() {
+            return Some(0);
+        }
+
+        self.terms
+            .iter()
+            .filter_map(|term| term_score(matcher, term, name, description))
+            .max()
+    }
+
+    /// The characters of `name` to highlight, those of the first fuzzy term matching it.
+    pub fn name_indices(&self, matcher: &SkimMatcherV2, name: &str) -> Option<Vec<usize>> {
+        self.terms
+            .iter()
+            .filter(|term| !term.contains('*'))
+            .find_map(|term| matcher.fuzzy_indices(name, term))
+            .map(|(_, indices)| indices)
+    }
+}
+
+fn term_score(matcher: &SkimMatcherV2, term: &str, name: &str, description: &str) -> Option<i64> {
+    if term.contains('*') {
+        return (glob_matches(term, name) || glob_matches(term, description)).then_some(0);
+    }
+
+    let name_score = matcher.fuzzy_match(name, term).map(|score| score * NAME_MATCH_WEIGHT);
+    let description_score = matcher.fuzzy_match(description, term);
+    name_score.max(description_score)
+}
+
+/// Whether `text` contains the excluded `term`, or matches it if it's a glob.
+/// Unlike the terms looked for, these aren't fuzzy, so a few stray letters
+/// don't hide a unit.
+fn contains(term: &str, text: &str) -> bool {
+    if term.contains('*') {
+        glob_matches(term, text)
+    } else {
+        text.to_lowercase().contains(&term.to_lowercase())
+    }
+}
+
+/// Whether all of `text` matches `pattern`, where `*` stands for any run of
+/// characters, ignoring case.
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    // Where the last `*` was and the text position it was tried at, to
+    // backtrack to when the rest stops matching.
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(text: &str) -> ListSearch {
+        ListSearch {
+            input: text.to_string(),
+            filter: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_page_change_keeps_search() {
+        let mut search = search("network");
+        search.page_changed(true);
+
+        assert_eq!(search.input, "network");
+        assert_eq!(search.filter, "network");
+    }
+
+    #[test]
+    fn test_page_change_clears_search() {
+        let mut search = search("network");
+        search.page_changed(false);
+
+        assert!(search.input.is_empty());
+        assert!(search.filter.is_empty());
+    }
+
+    #[test]
+    fn test_remember_search() {
+        let mut history = vec!["ssh".to_string(), "network".to_string()];
+
+        remember(&mut history, " network ");
+        assert_eq!(history, ["network", "ssh"]);
+
+        remember(&mut history, "  ");
+        assert_eq!(history, ["network", "ssh"]);
+
+        for i in 0..HISTORY_LEN {
+            remember(&mut history, &format!("term{i}"));
+        }
+        assert_eq!(history.len(), HISTORY_LEN);
+        assert_eq!(history[0], format!("term{}", HISTORY_LEN - 1));
+        assert!(!history.contains(&"network".to_string()));
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(Query::parse("nginx"), Query { terms: vec!["nginx"], excluded: vec![] });
+        assert_eq!(
+            Query::parse(" nginx  redis !docker ! "),
+            Query { terms: vec!["nginx", "redis"], excluded: vec!["docker"] }
+        );
+        assert_eq!(Query::parse(""), Query::default());
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("ssh*", "sshd.service"));
+        assert!(glob_matches("*@*.service", "getty@tty1.service"));
+        assert!(glob_matches("SSH*", "sshd.service"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("ssh*", "openssh.service"));
+        assert!(!glob_matches("*.timer", "fstrim.service"));
+        assert!(glob_matches("*a*b", "aXbXb"));
+    }
+
+    #[test]
+    fn test_query_score() {
+        let matcher = SkimMatcherV2::default();
+        let score = |query: &str, name: &str| Query::parse(query).score(&matcher, name, "");
+
+        // A single term matches as before.
+        assert!(score("nginx", "nginx.service").is_some());
+        assert!(score("nginx", "redis.service").is_none());
+
+        // Any of several terms matches.
+        assert!(score("nginx redis", "nginx.service").is_some());
+        assert!(score("nginx redis", "redis.service").is_some());
+        assert!(score("nginx redis", "sshd.service").is_none());
+
+        // Excluded terms win, and on their own leave everything else.
+        assert!(score("!docker", "docker.service").is_none());
+        assert_eq!(score("!docker", "containerd.service"), Some(0));
+        assert!(score("*.service !docker", "docker.service").is_none());
+        assert!(score("!docker*", "dockerd.socket").is_none());
+
+        // Globs match the whole name, or the description.
+        assert!(score("ssh*", "sshd.service").is_some());
+        assert!(score("ssh*", "openssh.service").is_none());
+        assert!(Query::parse("*web*").score(&matcher, "nginx.service", "A web server").is_some());
+    }
+}
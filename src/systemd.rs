@@ -3,9 +3,10 @@
 // Reference for systemd dbus interface: 
 // https://www.freedesktop.org/wiki/Software/systemd/dbus 
 
+use crate::fl;
 use zbus::{Connection, Result};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SystemdService {
     pub name: String,
     pub description: String,
@@ -14,25 +15,521 @@ pub struct SystemdService {
     pub sub_state: String,
     pub unit_path: String,
     pub unit_file_state: String,
+    // Details below are only fetched for the selected service, see `load_details`.
+    pub can_reload: bool,
+    pub main_pid: Option<u32>,
+    pub memory_current: Option<u64>,
+    pub cpu_usage_nsec: Option<u64>,
+    pub exec_start: Option<String>,
+    /// The unit's cgroup and the processes in it, empty once the cgroup is cleaned up.
+    pub control_group: Option<String>,
+    pub processes: Vec<UnitProcess>,
+    /// When the unit last entered the active or inactive state, in microseconds since the epoch.
+    pub active_since: Option<u64>,
+    pub inactive_since: Option<u64>,
+    /// "running", "frozen", "freezing" or "thawing"; `None` where systemd has no freezer support.
+    pub freezer_state: Option<String>,
+    // Only set for timer units.
+    pub next_elapse: Option<u64>,
+    pub last_trigger: Option<u64>,
+    /// Only set for socket units, and for services, from the sockets activating them.
+    pub listen: Vec<String>,
+    /// The units it activates, e.g. a socket's or a timer's service.
+    pub triggers: Vec<String>,
+    /// The units activating it, e.g. a service's socket or timer.
+    pub triggered_by: Vec<String>,
+    /// The units pulling it in through `Wants=`, e.g. the targets a service is
+    /// enabled for, or the target a target is part of.
+    pub wanted_by: Vec<String>,
+    /// Whether it's allowed to be isolated, which only some targets are.
+    pub can_isolate: bool,
+    /// Why the unit file couldn't be loaded, only fetched when `load_state` isn't "loaded".
+    pub load_error: Option<String>,
+    /// The unit's `Documentation=` URLs and man pages, e.g. "man:sshd(8)".
+    pub documentation: Vec<String>,
+    /// Where the unit file was loaded from, empty for units without one such
+    /// as transient units.
+    pub fragment_path: String,
+    /// The unit file or its drop-ins changed on disk since systemd last loaded
+    /// them, so what's running doesn't follow them until a daemon reload.
+    pub need_daemon_reload: bool,
+    /// The outcome of the unit's `Condition…=` and `Assert…=` checks on its last
+    /// start, `None` if it was never started since they were added.
+    pub condition_result: Option<CheckResult>,
+    pub assert_result: Option<CheckResult>,
+    /// Only set for services: how their last run ended, e.g. "success",
+    /// "exit-code" or "timeout", and how their main process did.
+    pub result: Option<String>,
+    pub main_exit: Option<MainExit>,
+    /// Only set for services with `WatchdogSec=`, which have to keep pinging
+    /// systemd to be considered alive.
+    pub watchdog: Option<Watchdog>,
+    /// Only set for services: how often systemd restarted them through `Restart=`
+    /// since they were last started by hand.
+    pub n_restarts: Option<u32>,
+}
+
+/// A service's watchdog, from its `WatchdogUSec` and `WatchdogTimestamp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watchdog {
+    /// How often the service has to ping, in microseconds.
+    pub interval_usec: u64,
+    /// When it last pinged, or was started, in microseconds since the epoch.
+    pub last_ping: Option<u64>,
+}
+
+impl Watchdog {
+    /// Whether the last ping came within the interval before `now`, in
+    /// microseconds since the epoch.
+    pub fn is_healthy(&self, now: u64) -> bool {
+        self.last_ping.is_some_and(|ping| now.saturating_sub(ping) <= self.interval_usec)
+    }
+}
+
+/// How a service's main process ended, from its `ExecMainCode` and `ExecMainStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MainExit {
+    /// Exited on its own, with this status.
+    Exited(i32),
+    /// Killed by this signal.
+    Killed(i32),
+    /// Killed by this signal, dumping core.
+    Dumped(i32),
+}
+
+impl MainExit {
+    /// Reads the `si_code` of the process's `SIGCHLD`, `None` while it hasn't ended.
+    pub fn from_code(code: i32, status: i32) -> Option<Self> {
+        match code {
+            1 => Some(MainExit::Exited(status)),
+            2 => Some(MainExit::Killed(status)),
+            3 => Some(MainExit::Dumped(status)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a unit's conditions or asserts held when it was last started. A failed
+/// condition skips the start without an error, a failed assert fails it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckResult {
+    pub passed: bool,
+    /// When they were checked, in microseconds since the epoch.
+    pub checked_at: u64,
+    /// The checks that didn't hold, as written in the unit file.
+    pub failed: Vec<String>,
+}
+
+impl SystemdService {
+    /// Whether systemd couldn't load the unit, e.g. because of a typo in its file.
+    /// Masked units aren't loaded either, but on purpose, and stubs just haven't
+    /// been needed yet.
+    pub fn failed_to_load(&self) -> bool {
+        !matches!(self.load_state.as_str(), "loaded" | "masked" | "stub")
+    }
+
+    /// Whether the unit is only ever started by the system itself, because it has
+    /// no [Install] section, is pulled in through another unit or was generated.
+    pub fn is_vendor_managed(&self) -> bool {
+        matches!(self.unit_file_state.as_str(), "static" | "indirect" | "generated")
+    }
+
+    /// The units whose logs are shown for this one: itself, and with `related`
+    /// the units activating it or activated by it, like its socket or timer.
+    pub fn log_units(&self, related: bool) -> Vec<String> {
+        let mut units = vec![self.name.clone()];
+        if related {
+            for unit in self.triggered_by.iter().chain(&self.triggers) {
+                if !units.contains(unit) {
+                    units.push(unit.clone());
+                }
+            }
+        }
+        units
+    }
+}
+
+/// A process in a unit's cgroup, as listed by `GetUnitProcesses`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnitProcess {
+    pub pid: u32,
+    pub command: String,
+    /// The cgroup the process is in, which may be a child of the unit's own.
+    pub cgroup: String,
+}
+
+/// The ordering and requirement dependencies of a unit.
+#[derive(Debug, Clone, Default)]
+pub struct UnitDependencies {
+    pub requires: Vec<String>,
+    pub wants: Vec<String>,
+    pub after: Vec<String>,
+    pub before: Vec<String>,
+}
+
+/// The dependencies a dependency tree follows, like `systemctl list-dependencies`
+/// follows requirements by default and ordering with `--after` or `--before`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DependencyKind {
+    #[default]
+    Requirements,
+    After,
+    Before,
+}
+
+impl DependencyKind {
+    pub const ALL: [DependencyKind; 3] = [
+        DependencyKind::Requirements,
+        DependencyKind::After,
+        DependencyKind::Before,
+    ];
+
+    /// The units of this kind among a unit's dependencies.
+    pub fn units(&self, dependencies: &UnitDependencies) -> Vec<String> {
+        match self {
+            DependencyKind::Requirements => dependencies
+                .requires
+                .iter()
+                .chain(&dependencies.wants)
+                .cloned()
+                .collect(),
+            DependencyKind::After => dependencies.after.clone(),
+            DependencyKind::Before => dependencies.before.clone(),
+        }
+    }
+}
+
+/// A unit in a dependency tree, with its own dependencies below it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyNode {
+    pub name: String,
+    pub children: Vec<DependencyNode>,
+    /// The unit is already on the path from the root, so it isn't expanded again.
+    pub cycle: bool,
+}
+
+/// Journal priorities, from most to least severe, as accepted by `journalctl -p`.
+/// Filtering by one shows entries of that priority and above.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LogPriority {
+    Emerg,
+    Alert,
+    Crit,
+    Err,
+    Warning,
+    Notice,
+    Info,
+    /// Every entry, `journalctl`'s default.
+    #[default]
+    Debug,
+}
+
+impl LogPriority {
+    pub const ALL: [LogPriority; 8] = [
+        LogPriority::Emerg,
+        LogPriority::Alert,
+        LogPriority::Crit,
+        LogPriority::Err,
+        LogPriority::Warning,
+        LogPriority::Notice,
+        LogPriority::Info,
+        LogPriority::Debug,
+    ];
+
+    /// `journalctl`'s names for `ALL`.
+    pub const NAMES: [&'static str; 8] = ["emerg", "alert", "crit", "err", "warning", "notice", "info", "debug"];
+
+    pub fn as_str(&self) -> &'static str {
+        Self::NAMES[*self as usize]
+    }
+}
+
+/// A time window of journal entries, passed as `journalctl --since`/`--until`,
+/// optionally within a single boot's entries, passed as `--boot`.
+/// Both ends are expected to have passed `is_valid_journal_time`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LogRange {
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// The boot's ID, entries of every boot are shown without one.
+    pub boot: Option<String>,
+}
+
+impl LogRange {
+    pub fn is_empty(&self) -> bool {
+        self.since.is_none() && self.until.is_none() && self.boot.is_none()
+    }
+}
+
+/// A boot the journal has entries of, as listed by `journalctl --list-boots`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Boot {
+    /// Counted back from the current boot, which is 0, the one before it being -1.
+    #[serde(rename = "index")]
+    pub offset: i32,
+    #[serde(rename = "boot_id")]
+    pub id: String,
+    /// When the boot's first entry was written, in microseconds since the epoch.
+    #[serde(rename = "first_entry")]
+    pub started: u64,
+}
+
+/// Consecutive journal entries of a unit, oldest first, as journalctl prints them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogPage {
+    pub text: String,
+    /// The cursor of the oldest entry, the one entries before it are loaded from.
+    /// `None` when there are no entries.
+    pub cursor: Option<String>,
+    /// Whether there's nothing older to load.
+    pub at_start: bool,
+}
+
+/// Reads the output of `journalctl --reverse --show-cursor`, which lists the
+/// latest entries first and ends with the cursor of the oldest. The entries are
+/// put back in order, each with the indented lines continuing it. Fewer entries
+/// than the `lines` asked for means the journal ran out of them.
+pub fn parse_log_page(output: &str, lines: Option<u32>) -> LogPage {
+    let mut cursor = None;
+    let mut entries: Vec<Vec<&str>> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(value) = line.strip_prefix("-- cursor: ") {
+            cursor = Some(value.to_string());
+        } else if line.starts_with(char::is_whitespace) && !entries.is_empty() {
+            entries.last_mut().unwrap().push(line);
+        } else {
+            entries.push(vec![line]);
+        }
+    }
+
+    let at_start = match lines {
+        Some(lines) => cursor.is_none() || entries.len() < lines as usize,
+        None => true,
+    };
+
+    let mut text = String::with_capacity(output.len());
+    for entry in entries.iter().rev() {
+        for line in entry {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    LogPage { text, cursor, at_start }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Reads the JSON output of `journalctl --list-boots`, latest boot first.
+pub fn parse_boots(output: &str) -> std::result::Result<Vec<Boot>, serde_json::Error> {
+    let mut boots: Vec<Boot> = serde_json::from_str(output)?;
+    boots.sort_by_key(|boot| std::cmp::Reverse(boot.offset));
+    Ok(boots)
+}
+
+/// Whether `input` is a time `journalctl --since`/`--until` accepts: an absolute
+/// "YYYY-MM-DD[ HH:MM[:SS]]" or "HH:MM[:SS]" for today, optionally after a weekday
+/// like "Fri", an offset from now such as "-1h", "-2d 3h" or "1h ago", seconds
+/// since the epoch like "@1700000000", or one of "now", "today", "yesterday"
+/// and "tomorrow".
+pub fn is_valid_journal_time(input: &str) -> bool {
+    const WEEKDAYS: &[&str] = &[
+        "mon", "monday", "tue", "tuesday", "wed", "wednesday", "thu", "thursday",
+        "fri", "friday", "sat", "saturday", "sun", "sunday",
+    ];
+
+    let input = input.trim();
+
+    if matches!(input, "now" | "today" | "yesterday" | "tomorrow") {
+        return true;
+    }
+
+    if let Some(offset) = input.strip_prefix(['-', '+']) {
+        return is_valid_time_span(offset);
+    }
+
+    if let Some(offset) = input.strip_suffix(" ago").or_else(|| input.strip_suffix(" left")) {
+        return is_valid_time_span(offset);
+    }
+
+    if let Some(seconds) = input.strip_prefix('@') {
+        return !seconds.is_empty() && seconds.chars().all(|c| c.is_ascii_digit());
+    }
+
+    // Whether the weekday matches the date is left to journalctl.
+    let input = match input.split_once(' ') {
+        Some((weekday, rest)) if WEEKDAYS.contains(&weekday.to_lowercase().as_str()) => rest.trim_start(),
+        _ => input,
+    };
+
+    chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S").is_ok()
+        || chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M").is_ok()
+        || chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d").is_ok()
+        || chrono::NaiveTime::parse_from_str(input, "%H:%M:%S").is_ok()
+        || chrono::NaiveTime::parse_from_str(input, "%H:%M").is_ok()
+}
+
+/// Whether `span` is a systemd time span like "90", "1h 30min" or "2d3h".
+fn is_valid_time_span(span: &str) -> bool {
+    // A bare number is in seconds.
+    const UNITS: &[&str] = &[
+        "", "us", "ms", "s", "sec", "second", "seconds", "m", "min", "minute", "minutes",
+        "h", "hr", "hour", "hours", "d", "day", "days", "w", "week", "weeks",
+        "M", "month", "months", "y", "year", "years",
+    ];
+
+    let mut rest = span.trim_start();
+    if rest.is_empty() {
+        return false;
+    }
+
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            return false;
+        }
+        rest = &rest[digits..];
+
+        let unit = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        if !UNITS.contains(&&rest[..unit]) {
+            return false;
+        }
+        rest = rest[unit..].trim_start();
+    }
+
+    true
+}
+
+/// An operation that can be performed on a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+    Reload,
+    /// Reloads the unit if it supports it, otherwise restarts it.
+    ReloadOrRestart,
+    Enable,
+    Disable,
+    Mask,
+    Unmask,
+    ResetFailed,
+    Kill(KillSignal),
+    Freeze,
+    Thaw,
+    /// Starts a target and stops every unit it doesn't pull in, like switching
+    /// between `multi-user.target` and `graphical.target`.
+    Isolate,
+}
+
+impl ServiceAction {
+    /// The action that takes this one back, `None` if nothing can, like for a restart.
+    pub fn inverse(self) -> Option<ServiceAction> {
+        match self {
+            ServiceAction::Start => Some(ServiceAction::Stop),
+            ServiceAction::Stop => Some(ServiceAction::Start),
+            ServiceAction::Enable => Some(ServiceAction::Disable),
+            ServiceAction::Disable => Some(ServiceAction::Enable),
+            ServiceAction::Mask => Some(ServiceAction::Unmask),
+            ServiceAction::Unmask => Some(ServiceAction::Mask),
+            ServiceAction::Freeze => Some(ServiceAction::Thaw),
+            ServiceAction::Thaw => Some(ServiceAction::Freeze),
+            ServiceAction::Restart
+            | ServiceAction::Reload
+            | ServiceAction::ReloadOrRestart
+            | ServiceAction::ResetFailed
+            | ServiceAction::Kill(_)
+            | ServiceAction::Isolate => None,
+        }
+    }
+
+    /// Whether doing the action twice leaves the unit as doing it once does.
+    pub fn is_idempotent(self) -> bool {
+        matches!(self, ServiceAction::Start | ServiceAction::Stop | ServiceAction::ResetFailed)
+    }
+
+    /// The name of the action shown on buttons and in messages.
+    pub fn label(self) -> String {
+        match self {
+            ServiceAction::Start => fl!("start"),
+            ServiceAction::Stop => fl!("stop"),
+            ServiceAction::Restart => fl!("restart"),
+            ServiceAction::Reload => fl!("reload"),
+            ServiceAction::ReloadOrRestart => fl!("reload-or-restart"),
+            ServiceAction::Enable => fl!("enable"),
+            ServiceAction::Disable => fl!("disable"),
+            ServiceAction::Mask => fl!("mask"),
+            ServiceAction::Unmask => fl!("unmask"),
+            ServiceAction::ResetFailed => fl!("reset-failed"),
+            ServiceAction::Kill(signal) => fl!("kill-with", signal = signal.name()),
+            ServiceAction::Freeze => fl!("freeze"),
+            ServiceAction::Thaw => fl!("thaw"),
+            ServiceAction::Isolate => fl!("isolate"),
+        }
+    }
+}
+
+/// Signals offered for killing a unit's processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillSignal {
+    Term,
+    Hup,
+    Kill,
+    Usr1,
+}
+
+impl KillSignal {
+    pub const ALL: [KillSignal; 4] = [KillSignal::Term, KillSignal::Hup, KillSignal::Kill, KillSignal::Usr1];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KillSignal::Term => "SIGTERM",
+            KillSignal::Hup => "SIGHUP",
+            KillSignal::Kill => "SIGKILL",
+            KillSignal::Usr1 => "SIGUSR1",
+        }
+    }
+
+    pub fn number(&self) -> i32 {
+        match self {
+            KillSignal::Term => 15,
+            KillSignal::Hup => 1,
+            KillSignal::Kill => 9,
+            KillSignal::Usr1 => 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ServiceScope {
     System,
     User,
 }
 
+#[derive(Debug, Clone)]
 pub struct SystemdManager {
     connection: Connection,
+    scope: ServiceScope,
 }
 
 impl SystemdManager {
+    /// A manager on a new connection to the scope's bus. Clones share it, so the
+    /// app opens one per scope and replaces it only once it's lost.
     pub async fn new(scope: ServiceScope) -> Result<Self> {
         let connection = match scope {
             ServiceScope::System => Connection::system().await?,
             ServiceScope::User => Connection::session().await?,
         };
-        Ok(Self { connection })
+        Ok(Self { connection, scope })
+    }
+
+    pub fn scope(&self) -> ServiceScope {
+        self.scope
+    }
+
+    /// The connection's unique name on the bus, which only changes once the
+    /// connection is replaced.
+    pub fn connection_name(&self) -> String {
+        self.connection.unique_name().map(|name| name.to_string()).unwrap_or_default()
     }
 
     fn is_flatpak() -> bool {
@@ -41,6 +538,42 @@ impl SystemdManager {
     }
 
     pub async fn list_services(&self) -> Result<Vec<SystemdService>> {
+        self.list_units("*", &["service"]).await
+    }
+
+    pub async fn list_timers(&self) -> Result<Vec<SystemdService>> {
+        let mut timers = self.list_units("*", &["timer"]).await?;
+
+        for timer in &mut timers {
+            let _ = self.load_timer_details(timer).await;
+        }
+
+        Ok(timers)
+    }
+
+    pub async fn list_targets(&self) -> Result<Vec<SystemdService>> {
+        self.list_units("*", &["target"]).await
+    }
+
+    pub async fn list_sockets(&self) -> Result<Vec<SystemdService>> {
+        let mut sockets = self.list_units("*", &["socket"]).await?;
+
+        for socket in &mut sockets {
+            let _ = self.load_socket_details(socket).await;
+        }
+
+        Ok(sockets)
+    }
+
+    /// Units whose name matches the glob `pattern` followed by one of `types`,
+    /// such as `"ssh*"` and `["service", "socket"]`, or `pattern` alone when
+    /// `types` is empty. The matching is done by systemd, loaded units and unit
+    /// files alike.
+    pub async fn list_units(&self, pattern: &str, types: &[&str]) -> Result<Vec<SystemdService>> {
+        let patterns = unit_patterns(pattern, types);
+        // No state filter, every matching unit is listed.
+        let args = (Vec::<&str>::new(), patterns);
+
         let proxy = zbus::Proxy::new(
             &self.connection,
             "org.freedesktop.systemd1",
@@ -60,18 +593,24 @@ impl SystemdManager {
             u32, // If there is a job queued for the job unit the numeric job id, 0 otherwise
             String, // The job type as string 
             zbus::zvariant::OwnedObjectPath // The job object path
-        )> = proxy.call("ListUnits", &()).await?;
+        )> = with_retry(|| proxy.call("ListUnitsByPatterns", &args)).await?;
+
+        // Every matching unit file with its enablement state, in one call rather
+        // than a property read per unit. It also lists units that aren't loaded.
+        let unit_files: Vec<(String, String)> =
+            with_retry(|| proxy.call("ListUnitFilesByPatterns", &args)).await?;
+        let mut file_states = unit_file_states(unit_files);
 
         let mut services: Vec<SystemdService> = Vec::new();
-        
+
         for (name, description, load_state, active_state, sub_state, _following, unit_object_path, _job_id, _job_type, _job_object_path) in units {
-            if !name.ends_with(".service") {
-                continue;
-            }
+            // Transient units have no file, so they have no entry, and instances
+            // share their template's file, so they're enabled like it.
+            let unit_file_state = file_states
+                .remove(&name)
+                .or_else(|| parse_unit_name(&name).template().and_then(|template| file_states.get(&template).cloned()))
+                .unwrap_or_else(|| "unknown".to_string());
 
-            let unit_file_state = self.get_unit_file_state(&unit_object_path).await
-                .unwrap_or_else(|_| "unknown".to_string());
-            
             services.push(SystemdService {
                 name,
                 description,
@@ -80,162 +619,1677 @@ impl SystemdManager {
                 sub_state,
                 unit_path: unit_object_path.to_string(),
                 unit_file_state,
+                ..Default::default()
+            });
+        }
+
+        // What's left are units systemd hasn't loaded, typically disabled ones.
+        // Templates can't be started without an instance, and aliases are listed
+        // under the unit they name, so they're left out.
+        for (name, unit_file_state) in file_states {
+            if parse_unit_name(&name).instance == Some("") || unit_file_state == "alias" {
+                continue;
+            }
+
+            services.push(SystemdService {
+                unit_path: unit_object_path(&name),
+                name,
+                load_state: "stub".to_string(),
+                active_state: "inactive".to_string(),
+                sub_state: "dead".to_string(),
+                unit_file_state,
+                ..Default::default()
             });
         }
 
         Ok(services)
     }
 
-    async fn get_unit_file_state(&self, unit_object_path: &zbus::zvariant::OwnedObjectPath) -> Result<String> {
-        let unit_proxy = zbus::Proxy::new(
-            &self.connection,
-            "org.freedesktop.systemd1",
-            unit_object_path.as_str(),
-            "org.freedesktop.systemd1.Unit",
-        )
-        .await?;
+    /// Names of the loaded units currently in the failed state, of every type.
+    pub async fn list_failed_units(&self) -> Result<Vec<String>> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        // Same tuple as `ListUnits`, only the name is needed here.
+        let units: Vec<(
+            String, String, String, String, String, String,
+            zbus::zvariant::OwnedObjectPath, u32, String, zbus::zvariant::OwnedObjectPath,
+        )> = {
+            let args = (vec!["failed"],);
+            with_retry(|| proxy.call("ListUnitsFiltered", &args)).await?
+        };
+
+        Ok(units.into_iter().map(|unit| unit.0).collect())
+    }
+
+    /// Returns a stream of the `PropertiesChanged` signals systemd emits for
+    /// `unit_path`, or for every unit when `unit_path` is `None`.
+    pub async fn watch_units(&self, unit_path: Option<&str>) -> Result<zbus::MessageStream> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        // systemd only emits unit signals while at least one client is subscribed.
+        let _: () = proxy.call("Subscribe", &()).await?;
+
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.freedesktop.systemd1")?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?;
+
+        let rule = match unit_path {
+            Some(path) => rule.path(path)?,
+            None => rule.path_namespace("/org/freedesktop/systemd1/unit")?,
+        };
+
+        zbus::MessageStream::for_match_rule(rule.build(), &self.connection, None).await
+    }
+
+    /// A single unit as the list shows it, read without listing every unit. `None`
+    /// if systemd has neither loaded the unit nor a file for it, e.g. a transient
+    /// unit that stopped. Its sub state is read by `load_details`, with the rest of
+    /// what the detail view shows.
+    pub async fn get_unit(&self, service_name: &str) -> Result<Option<SystemdService>> {
+        let (active_state, unit_file_state) = match self.get_service_status(service_name).await {
+            Ok(status) => status,
+            Err(zbus::Error::MethodError(name, _, _)) if name.as_str() == "org.freedesktop.systemd1.NoSuchUnit" => {
+                let proxy = zbus::Proxy::new(
+                    &self.connection,
+                    "org.freedesktop.systemd1",
+                    "/org/freedesktop/systemd1",
+                    "org.freedesktop.systemd1.Manager",
+                )
+                .await?;
+
+                // Like in the list, a unit that isn't loaded is shown from its file alone.
+                let Ok(unit_file_state) = proxy.call::<_, _, String>("GetUnitFileState", &(service_name,)).await else {
+                    return Ok(None);
+                };
+                return Ok(Some(SystemdService {
+                    name: service_name.to_string(),
+                    load_state: "stub".to_string(),
+                    active_state: "inactive".to_string(),
+                    sub_state: "dead".to_string(),
+                    unit_path: unit_object_path(service_name),
+                    unit_file_state,
+                    ..Default::default()
+                }));
+            }
+            Err(e) => return Err(e),
+        };
+
+        // The unit is loaded, so its path needn't be asked for again.
+        let unit_path = unit_object_path(service_name);
+        let unit_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            unit_path.as_str(),
+            "org.freedesktop.systemd1.Unit",
+        )
+        .await?;
+
+        Ok(Some(SystemdService {
+            name: service_name.to_string(),
+            description: read_property(&unit_proxy, "Description").await?,
+            load_state: read_property(&unit_proxy, "LoadState").await?,
+            active_state,
+            unit_path,
+            // Units without a file have no state for it, the list shows "unknown".
+            unit_file_state: if unit_file_state.is_empty() { "unknown".to_string() } else { unit_file_state },
+            ..Default::default()
+        }))
+    }
+
+    /// The unit's `ActiveState` and `UnitFileState`, like `systemctl is-active` and
+    /// `is-enabled`, read without listing every unit. Fails for units systemd
+    /// hasn't loaded, which `GetUnit` doesn't load.
+    pub async fn get_service_status(&self, service_name: &str) -> Result<(String, String)> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let args = (service_name,);
+        let unit_path: zbus::zvariant::OwnedObjectPath = with_retry(|| proxy.call("GetUnit", &args)).await?;
+        let unit_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            unit_path,
+            "org.freedesktop.systemd1.Unit",
+        )
+        .await?;
+
+        let active_state: String = read_property(&unit_proxy, "ActiveState").await?;
+        let unit_file_state: String = read_property(&unit_proxy, "UnitFileState").await?;
+        Ok((active_state, unit_file_state))
+    }
+
+    /// The unit's current `ActiveState`, e.g. "active" or "failed".
+    pub async fn get_active_state(&self, unit_path: &str) -> Result<String> {
+        let unit_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            unit_path,
+            "org.freedesktop.systemd1.Unit",
+        )
+        .await?;
+
+        read_property(&unit_proxy, "ActiveState").await
+    }
+
+    /// The manager's overall state as reported by `systemctl is-system-running`,
+    /// e.g. "running" or "degraded".
+    pub async fn get_system_state(&self) -> Result<String> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        read_property(&proxy, "SystemState").await
+    }
+
+    /// Whether polkit lets this process start, stop and otherwise manage units
+    /// without asking for a password. User units always can be.
+    pub async fn can_manage_units(&self) -> Result<bool> {
+        if self.scope == ServiceScope::User {
+            return Ok(true);
+        }
+
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.PolicyKit1",
+            "/org/freedesktop/PolicyKit1/Authority",
+            "org.freedesktop.PolicyKit1.Authority",
+        )
+        .await?;
+
+        // Asked about this connection, the one the actions are sent on, and
+        // without interaction, so the check itself never prompts.
+        let name = self
+            .connection
+            .unique_name()
+            .ok_or_else(|| zbus::Error::Failure("Not connected to the system bus".to_string()))?;
+        let subject = (
+            "system-bus-name",
+            std::collections::HashMap::from([("name", zbus::zvariant::Value::from(name.as_str()))]),
+        );
+        let details: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
+
+        let args = (subject, "org.freedesktop.systemd1.manage-units", details, 0u32, "");
+        let (authorized, _challenge, _details): (bool, bool, std::collections::HashMap<String, String>) =
+            with_retry(|| proxy.call("CheckAuthorization", &args)).await?;
+        Ok(authorized)
+    }
+
+    /// Streams the Manager's `JobNew` and `JobRemoved` signals, see `parse_job_signal`.
+    pub async fn watch_jobs(&self) -> Result<zbus::MessageStream> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: () = proxy.call("Subscribe", &()).await?;
+
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.freedesktop.systemd1")?
+            .path("/org/freedesktop/systemd1")?
+            .interface("org.freedesktop.systemd1.Manager")?
+            .build();
+
+        zbus::MessageStream::for_match_rule(rule, &self.connection, None).await
+    }
+
+    async fn load_timer_details(&self, timer: &mut SystemdService) -> Result<()> {
+        let timer_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            timer.unit_path.as_str(),
+            "org.freedesktop.systemd1.Timer",
+        )
+        .await?;
+
+        // Timestamps are in microseconds since the epoch, 0 meaning "never".
+        timer.next_elapse = read_property::<u64>(&timer_proxy, "NextElapseUSecRealtime")
+            .await
+            .ok()
+            .filter(|usec| *usec != 0);
+        timer.last_trigger = read_property::<u64>(&timer_proxy, "LastTriggerUSec")
+            .await
+            .ok()
+            .filter(|usec| *usec != 0);
+
+        Ok(())
+    }
+
+    async fn load_socket_details(&self, socket: &mut SystemdService) -> Result<()> {
+        let socket_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            socket.unit_path.as_str(),
+            "org.freedesktop.systemd1.Socket",
+        )
+        .await?;
+
+        socket.listen = Self::listen_addresses(&socket_proxy).await?;
+
+        Ok(())
+    }
+
+    /// The socket's addresses, each followed by its type, e.g. "/run/dbus/system_bus_socket (Stream)".
+    async fn listen_addresses(socket_proxy: &zbus::Proxy<'_>) -> Result<Vec<String>> {
+        // Pairs of (type, address), e.g. ("Stream", "/run/dbus/system_bus_socket").
+        let listen: Vec<(String, String)> = read_property(socket_proxy, "Listen").await?;
+        Ok(listen
+            .into_iter()
+            .map(|(kind, address)| format!("{} ({})", address, kind))
+            .collect())
+    }
+
+    /// Reads the `ConditionResult`/`AssertResult` family of properties, `kind` being
+    /// "Condition" or "Assert". systemd reports a timestamp of 0 until they're checked.
+    async fn load_check_result(unit_proxy: &zbus::Proxy<'_>, kind: &str) -> Option<CheckResult> {
+        let checked_at = read_property::<u64>(unit_proxy, &format!("{kind}Timestamp"))
+            .await
+            .ok()
+            .filter(|usec| *usec != 0)?;
+        let passed = read_property::<bool>(unit_proxy, &format!("{kind}Result")).await.ok()?;
+        let checks = read_property::<Vec<(String, bool, bool, String, i32)>>(unit_proxy, &format!("{kind}s"))
+            .await
+            .unwrap_or_default();
+
+        Some(CheckResult { passed, checked_at, failed: failed_checks(checks) })
+    }
+
+    /// Fills in the properties that are too expensive to read for every unit in
+    /// `list_services` and are only needed for the detail view.
+    pub async fn load_details(&self, service: &mut SystemdService) -> Result<()> {
+        let unit_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            service.unit_path.as_str(),
+            "org.freedesktop.systemd1.Unit",
+        )
+        .await?;
+
+        // A unit listed from its file alone is loaded by accessing it here,
+        // so its real state and description are only known now.
+        if service.load_state == "stub" {
+            service.description = read_property(&unit_proxy, "Description").await.unwrap_or_default();
+            if let Ok(load_state) = read_property(&unit_proxy, "LoadState").await {
+                service.load_state = load_state;
+            }
+            if let Ok(active_state) = read_property(&unit_proxy, "ActiveState").await {
+                service.active_state = active_state;
+            }
+        }
+
+        // The sub state isn't part of `get_service_status`, so a refresh reads it here.
+        if let Ok(sub_state) = read_property(&unit_proxy, "SubState").await {
+            service.sub_state = sub_state;
+        }
+
+        service.can_reload = read_property(&unit_proxy, "CanReload").await.unwrap_or(false);
+
+        // systemd reports 0 for units that never entered the state.
+        service.active_since = read_property::<u64>(&unit_proxy, "ActiveEnterTimestamp")
+            .await
+            .ok()
+            .filter(|usec| *usec != 0);
+        service.inactive_since = read_property::<u64>(&unit_proxy, "InactiveEnterTimestamp")
+            .await
+            .ok()
+            .filter(|usec| *usec != 0);
+        service.freezer_state = read_property(&unit_proxy, "FreezerState").await.ok();
+
+        // The error's D-Bus name and message, both empty if the unit loaded fine.
+        service.load_error = if service.failed_to_load() {
+            read_property::<(String, String)>(&unit_proxy, "LoadError")
+                .await
+                .ok()
+                .map(|(name, message)| if message.is_empty() { name } else { message })
+                .filter(|error| !error.is_empty())
+        } else {
+            None
+        };
+
+        service.documentation = read_property(&unit_proxy, "Documentation").await.unwrap_or_default();
+        service.fragment_path = read_property(&unit_proxy, "FragmentPath").await.unwrap_or_default();
+        service.triggers = read_property(&unit_proxy, "Triggers").await.unwrap_or_default();
+        service.triggered_by = read_property(&unit_proxy, "TriggeredBy").await.unwrap_or_default();
+        service.wanted_by = read_property(&unit_proxy, "WantedBy").await.unwrap_or_default();
+        service.can_isolate = read_property(&unit_proxy, "AllowIsolate").await.unwrap_or(false);
+        service.need_daemon_reload = read_property(&unit_proxy, "NeedDaemonReload").await.unwrap_or(false);
+        service.condition_result = Self::load_check_result(&unit_proxy, "Condition").await;
+        service.assert_result = Self::load_check_result(&unit_proxy, "Assert").await;
+
+        if service.name.ends_with(".timer") {
+            return self.load_timer_details(service).await;
+        }
+
+        if service.name.ends_with(".socket") {
+            return self.load_socket_details(service).await;
+        }
+
+        // Targets and the other unit types have no Service interface to read.
+        if !service.name.ends_with(".service") {
+            return Ok(());
+        }
+
+        let service_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            service.unit_path.as_str(),
+            "org.freedesktop.systemd1.Service",
+        )
+        .await?;
+
+        // systemd reports 0 for "no main process" and u64::MAX for "not set".
+        service.main_pid = read_property::<u32>(&service_proxy, "MainPID")
+            .await
+            .ok()
+            .filter(|pid| *pid != 0);
+        service.memory_current = read_property::<u64>(&service_proxy, "MemoryCurrent")
+            .await
+            .ok()
+            .filter(|bytes| *bytes != u64::MAX);
+        service.cpu_usage_nsec = read_property::<u64>(&service_proxy, "CPUUsageNSec")
+            .await
+            .ok()
+            .filter(|nsec| *nsec != u64::MAX);
+
+        let exec_start: Vec<(
+            String, // The binary path
+            Vec<String>, // The arguments, including argv[0]
+            bool, // Whether a failure exit status is ignored
+            u64, u64, u64, u64, // Start and exit timestamps (realtime, monotonic)
+            u32, // The PID
+            i32, // The exit code
+            i32, // The exit status
+        )> = read_property(&service_proxy, "ExecStart").await.unwrap_or_default();
+        service.exec_start = format_exec_start(
+            exec_start.into_iter().map(|(path, argv, ..)| (path, argv)),
+        );
+
+        // A socket activated service listens on its sockets' addresses, like
+        // `systemctl status` shows them under "TriggeredBy".
+        let mut listen = Vec::new();
+        for socket in service.triggered_by.iter().filter(|unit| unit.ends_with(".socket")) {
+            let socket_proxy = zbus::Proxy::new(
+                &self.connection,
+                "org.freedesktop.systemd1",
+                unit_object_path(socket),
+                "org.freedesktop.systemd1.Socket",
+            )
+            .await?;
+            listen.extend(Self::listen_addresses(&socket_proxy).await.unwrap_or_default());
+        }
+        service.listen = listen;
+
+        // An interval of 0 means the service has no watchdog.
+        service.watchdog = read_property::<u64>(&service_proxy, "WatchdogUSec")
+            .await
+            .ok()
+            .filter(|usec| *usec != 0)
+            .map(|interval_usec| Watchdog { interval_usec, last_ping: None });
+        if let Some(watchdog) = &mut service.watchdog {
+            watchdog.last_ping = read_property::<u64>(&service_proxy, "WatchdogTimestamp")
+                .await
+                .ok()
+                .filter(|usec| *usec != 0);
+        }
+
+        service.result = read_property(&service_proxy, "Result").await.ok();
+        service.n_restarts = read_property(&service_proxy, "NRestarts").await.ok();
+        service.main_exit = match (
+            read_property::<i32>(&service_proxy, "ExecMainCode").await,
+            read_property::<i32>(&service_proxy, "ExecMainStatus").await,
+        ) {
+            (Ok(code), Ok(status)) => MainExit::from_code(code, status),
+            _ => None,
+        };
+
+        // systemd reports an empty path once a stopped unit's cgroup is removed.
+        service.control_group = read_property::<String>(&service_proxy, "ControlGroup")
+            .await
+            .ok()
+            .filter(|path| !path.is_empty());
+        service.processes = match service.control_group {
+            Some(_) => self.get_unit_processes(&service.name).await.unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        Ok(())
+    }
+
+    async fn get_unit_processes(&self, service_name: &str) -> Result<Vec<UnitProcess>> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let args = (service_name,);
+        let processes: Vec<(String, u32, String)> = with_retry(|| proxy.call("GetUnitProcesses", &args)).await?;
+
+        Ok(processes
+            .into_iter()
+            .map(|(cgroup, pid, command)| UnitProcess { pid, command, cgroup })
+            .collect())
+    }
+
+    pub async fn get_dependencies(&self, unit_path: &str) -> Result<UnitDependencies> {
+        let unit_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            unit_path,
+            "org.freedesktop.systemd1.Unit",
+        )
+        .await?;
+
+        Ok(UnitDependencies {
+            requires: read_property(&unit_proxy, "Requires").await?,
+            wants: read_property(&unit_proxy, "Wants").await?,
+            after: read_property(&unit_proxy, "After").await?,
+            before: read_property(&unit_proxy, "Before").await?,
+        })
+    }
+
+    /// The dependencies of `name` of one kind, resolved `depth` levels deep. Units
+    /// whose dependencies can't be read are leaves, only the root's errors are returned.
+    pub async fn get_dependency_tree(&self, name: &str, kind: DependencyKind, depth: usize) -> Result<DependencyNode> {
+        let mut edges = std::collections::HashMap::new();
+        let mut level = vec![name.to_string()];
+
+        // Units reached by several paths are only asked for once.
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for unit in level {
+                if edges.contains_key(&unit) {
+                    continue;
+                }
+
+                let dependencies = match self.get_dependencies(&unit_object_path(&unit)).await {
+                    Ok(dependencies) => dependencies,
+                    Err(e) if unit == name => return Err(e),
+                    Err(_) => UnitDependencies::default(),
+                };
+                let units = kind.units(&dependencies);
+                next.extend(units.iter().cloned());
+                edges.insert(unit, units);
+            }
+            level = next;
+        }
+
+        Ok(dependency_tree(name, depth, &edges))
+    }
+
+    /// Runs `command` as a new transient service, like `systemd-run` does, and
+    /// returns the name it was given. On the system bus this goes through polkit,
+    /// which may prompt the user.
+    pub async fn start_transient_service(&self, command: Vec<String>, description: &str) -> Result<String> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let micros = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros();
+        let name = format!("run-ctldash-{micros}.service");
+
+        let description = if description.trim().is_empty() {
+            command.join(" ")
+        } else {
+            description.trim().to_string()
+        };
+        let (path, argv) = transient_exec_start(command);
+
+        let properties: Vec<(&str, zbus::zvariant::Value)> = vec![
+            ("Description", description.into()),
+            ("ExecStart", vec![(path, argv, false)].into()),
+        ];
+        let aux: Vec<(&str, Vec<(&str, zbus::zvariant::Value)>)> = Vec::new();
+
+        // The name is fixed, so trying again after a call that went through without
+        // an answer finds the unit already there rather than running the command twice.
+        let args = (name.as_str(), "fail", properties, aux);
+        let mut attempts = 0;
+        with_retry(|| {
+            attempts += 1;
+            let retried = attempts > 1;
+            let call = proxy.call_with_flags(
+                "StartTransientUnit",
+                zbus::proxy::MethodFlags::AllowInteractiveAuth.into(),
+                &args,
+            );
+            async move {
+                let started: Result<Option<zbus::zvariant::OwnedObjectPath>> = call.await;
+                match started {
+                    Err(zbus::Error::MethodError(error, _, _))
+                        if retried && error.as_str() == "org.freedesktop.systemd1.UnitExists" => Ok(()),
+                    started => started.map(|_| ()),
+                }
+            }
+        })
+        .await?;
+        Ok(name)
+    }
+
+    /// The units starting `name` would pull in through `Requires=` and `Wants=`,
+    /// followed transitively, less those already active. It's only a prediction,
+    /// conditions can still leave some of them inactive.
+    pub async fn start_preview(&self, name: &str) -> Result<Vec<String>> {
+        let mut edges = std::collections::HashMap::new();
+        let mut pending = vec![name.to_string()];
+
+        while let Some(unit) = pending.pop() {
+            if edges.contains_key(&unit) {
+                continue;
+            }
+
+            let dependencies = match self.get_dependencies(&unit_object_path(&unit)).await {
+                Ok(dependencies) => dependencies,
+                Err(e) if unit == name => return Err(e),
+                Err(_) => UnitDependencies::default(),
+            };
+            let units = DependencyKind::Requirements.units(&dependencies);
+            pending.extend(units.iter().cloned());
+            edges.insert(unit, units);
+        }
+
+        let mut inactive = Vec::new();
+        for unit in pulled_in_units(name, &edges) {
+            let state = self.get_active_state(&unit_object_path(&unit)).await.unwrap_or_default();
+            if state != "active" {
+                inactive.push(unit);
+            }
+        }
+
+        Ok(inactive)
+    }
+
+    /// The unit file followed by its drop-ins, each preceded by a comment with its
+    /// path like `systemctl cat` does. `None` for transient units, which have neither.
+    pub async fn get_unit_file_contents(&self, unit_path: &str, service_name: &str) -> Result<Option<String>> {
+        // The unit's files are on the host, out of the sandbox's reach.
+        if Self::is_flatpak() {
+            let mut command = tokio::process::Command::new("flatpak-spawn");
+            command.arg("--host").arg("systemctl");
+            if self.scope == ServiceScope::User {
+                command.arg("--user");
+            }
+
+            let output = command
+                .arg("cat")
+                .arg(service_name)
+                .output()
+                .await
+                .map_err(|e| zbus::Error::Failure(format!("Failed to execute flatpak-spawn: {}", e)))?;
+
+            let contents = String::from_utf8_lossy(&output.stdout).to_string();
+            return Ok((!contents.is_empty()).then_some(contents));
+        }
+
+        let unit_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            unit_path,
+            "org.freedesktop.systemd1.Unit",
+        )
+        .await?;
+
+        let fragment_path: String = read_property(&unit_proxy, "FragmentPath").await?;
+        let drop_in_paths: Vec<String> = read_property(&unit_proxy, "DropInPaths").await?;
+
+        let mut contents = Vec::new();
+        for path in std::iter::once(fragment_path).chain(drop_in_paths).filter(|path| !path.is_empty()) {
+            let file = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| zbus::Error::Failure(format!("Failed to read {}: {}", path, e)))?;
+            contents.push(format!("# {}\n{}", path, file));
+        }
+
+        Ok((!contents.is_empty()).then(|| contents.join("\n")))
+    }
+
+    /// Performs `action`, trying again if systemd didn't answer in time and the
+    /// action is harmless to repeat. A restart or kill that went unanswered may
+    /// still have been carried out, so those fail right away instead.
+    pub async fn perform_action(&self, action: ServiceAction, service_name: &str) -> Result<()> {
+        if action.is_idempotent() {
+            with_retry(|| self.run_action(action, service_name)).await
+        } else {
+            self.run_action(action, service_name).await
+        }
+    }
+
+    async fn run_action(&self, action: ServiceAction, service_name: &str) -> Result<()> {
+        match action {
+            ServiceAction::Start => self.start_service(service_name).await,
+            ServiceAction::Stop => self.stop_service(service_name).await,
+            ServiceAction::Restart => self.restart_service(service_name).await,
+            ServiceAction::Reload => self.reload_service(service_name).await,
+            ServiceAction::ReloadOrRestart => self.reload_or_restart_service(service_name).await,
+            ServiceAction::Enable => self.enable_service(service_name).await.map(|_| ()),
+            ServiceAction::Disable => self.disable_service(service_name).await.map(|_| ()),
+            ServiceAction::Mask => self.mask_service(service_name).await,
+            ServiceAction::Unmask => self.unmask_service(service_name).await,
+            ServiceAction::ResetFailed => self.reset_failed(service_name).await,
+            ServiceAction::Kill(signal) => self.kill_service(service_name, signal).await,
+            ServiceAction::Freeze => self.freeze_service(service_name).await,
+            ServiceAction::Thaw => self.thaw_service(service_name).await,
+            ServiceAction::Isolate => self.isolate_unit(service_name).await,
+        }
+    }
+
+    pub async fn start_service(&self, service_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: zbus::zvariant::OwnedObjectPath =
+            proxy.call("StartUnit", &(service_name, "replace")).await?;
+        Ok(())
+    }
+
+    pub async fn stop_service(&self, service_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: zbus::zvariant::OwnedObjectPath =
+            proxy.call("StopUnit", &(service_name, "replace")).await?;
+        Ok(())
+    }
+
+    pub async fn restart_service(&self, service_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: zbus::zvariant::OwnedObjectPath =
+            proxy.call("RestartUnit", &(service_name, "replace")).await?;
+        Ok(())
+    }
+
+    pub async fn reload_service(&self, service_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: zbus::zvariant::OwnedObjectPath =
+            proxy.call("ReloadUnit", &(service_name, "replace")).await?;
+        Ok(())
+    }
+
+    /// Like `systemctl reload-or-restart`, which also starts the unit if it isn't running.
+    pub async fn reload_or_restart_service(&self, service_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: zbus::zvariant::OwnedObjectPath =
+            proxy.call("ReloadOrRestartUnit", &(service_name, "replace")).await?;
+        Ok(())
+    }
+
+    /// Starts `unit_name` in the "isolate" mode, which stops everything it
+    /// doesn't depend on. systemd refuses it unless the unit has `AllowIsolate=`.
+    pub async fn isolate_unit(&self, unit_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: zbus::zvariant::OwnedObjectPath =
+            proxy.call("StartUnit", &(unit_name, "isolate")).await?;
+        Ok(())
+    }
+
+    pub async fn reset_failed(&self, service_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: () = proxy.call("ResetFailedUnit", &(service_name,)).await?;
+        Ok(())
+    }
+
+    /// Sends `signal` to all of the unit's processes, without running its `ExecStop`.
+    /// On the system bus this goes through polkit, which may prompt the user.
+    pub async fn kill_service(&self, service_name: &str, signal: KillSignal) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: Option<()> = proxy
+            .call_with_flags(
+                "KillUnit",
+                zbus::proxy::MethodFlags::AllowInteractiveAuth.into(),
+                &(service_name, "all", signal.number()),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Suspends all of the unit's processes through the cgroup freezer. Fails for inactive units.
+    pub async fn freeze_service(&self, service_name: &str) -> Result<()> {
+        self.call_freezer("FreezeUnit", service_name).await
+    }
+
+    pub async fn thaw_service(&self, service_name: &str) -> Result<()> {
+        self.call_freezer("ThawUnit", service_name).await
+    }
+
+    async fn call_freezer(&self, method: &str, service_name: &str) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: Option<()> = proxy
+            .call_with_flags(
+                method,
+                zbus::proxy::MethodFlags::AllowInteractiveAuth.into(),
+                &(service_name,),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Makes systemd re-read all unit files, like `systemctl daemon-reload`.
+    /// On the system bus this goes through polkit, which may prompt the user.
+    pub async fn daemon_reload(&self) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .await?;
+
+        let _: Option<()> = proxy
+            .call_with_flags("Reload", zbus::proxy::MethodFlags::AllowInteractiveAuth.into(), &())
+            .await?;
+        Ok(())
+    }
+
+    /// The directory of the scope's administrator-made units, where `systemctl edit`
+    /// puts its files.
+    fn unit_dir(&self) -> std::path::PathBuf {
+        match self.scope {
+            ServiceScope::System => std::path::PathBuf::from("/etc/systemd/system"),
+            ServiceScope::User => {
+                let home = std::path::PathBuf::from(std::env::var_os("HOME").unwrap_or_default());
+                // Inside the sandbox XDG_CONFIG_HOME points at the app's own directory.
+                let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+                    .filter(|_| !Self::is_flatpak())
+                    .map(std::path::PathBuf::from)
+                    .unwrap_or_else(|| home.join(".config"));
+                config_dir.join("systemd").join("user")
+            }
+        }
+    }
+
+    /// Where `systemctl edit` keeps the unit's override drop-in for this scope.
+    pub fn drop_in_path(&self, service_name: &str) -> std::path::PathBuf {
+        self.unit_dir().join(format!("{service_name}.d")).join("override.conf")
+    }
+
+    /// The unit's override drop-in, `None` if it has none yet.
+    pub async fn read_drop_in(&self, service_name: &str) -> Result<Option<String>> {
+        Self::read_host_file(&self.drop_in_path(service_name)).await
+    }
+
+    /// Reads a file on the host, `None` if it doesn't exist.
+    async fn read_host_file(path: &std::path::Path) -> Result<Option<String>> {
+        if Self::is_flatpak() {
+            let output = tokio::process::Command::new("flatpak-spawn")
+                .arg("--host")
+                .arg("cat")
+                .arg(path)
+                .output()
+                .await
+                .map_err(|e| zbus::Error::Failure(format!("Failed to execute flatpak-spawn: {}", e)))?;
+
+            // cat fails for a missing file, which is the common case.
+            return Ok(output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string()));
+        }
+
+        match tokio::fs::read_to_string(path).await {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(zbus::Error::Failure(format!("Failed to read {}: {}", path.display(), e))),
+        }
+    }
+
+    /// Writes the unit's override drop-in and reloads systemd so it takes effect.
+    /// System drop-ins are written through pkexec.
+    pub async fn write_drop_in(&self, service_name: &str, contents: &str) -> Result<()> {
+        self.write_unit_file(&self.drop_in_path(service_name), contents).await?;
+        self.daemon_reload().await
+    }
+
+    /// Copies the system unit at `unit_path` into the user's own units, adapted with
+    /// `adapt_for_user_scope`, and reloads the user manager. Returns where it was
+    /// written and the directives that were left out. An existing unit of the same
+    /// name is never overwritten.
+    pub async fn copy_to_user_scope(
+        &self,
+        user: &SystemdManager,
+        unit_path: &str,
+        service_name: &str,
+    ) -> Result<(std::path::PathBuf, Vec<String>)> {
+        let unit_proxy = zbus::Proxy::new(
+            &self.connection,
+            "org.freedesktop.systemd1",
+            unit_path,
+            "org.freedesktop.systemd1.Unit",
+        )
+        .await?;
+
+        // Transient and generated units have no file of their own to copy.
+        let fragment: String = read_property(&unit_proxy, "FragmentPath").await?;
+        if fragment.is_empty() {
+            return Err(zbus::Error::Failure(format!("{service_name} has no unit file")));
+        }
+
+        let contents = Self::read_host_file(std::path::Path::new(&fragment))
+            .await?
+            .ok_or_else(|| zbus::Error::Failure(format!("{fragment} doesn't exist")))?;
+
+        let path = user.unit_dir().join(service_name);
+        if Self::read_host_file(&path).await?.is_some() {
+            return Err(zbus::Error::Failure(format!("{} already exists", path.display())));
+        }
+
+        let (adapted, removed) = adapt_for_user_scope(&contents);
+        user.write_unit_file(&path, &format!("# Copied from {fragment}\n{adapted}")).await?;
+        user.daemon_reload().await?;
+
+        Ok((path, removed))
+    }
+
+    /// Writes a file in the scope's unit directories, creating its directory.
+    /// System files are written through pkexec.
+    async fn write_unit_file(&self, path: &std::path::Path, contents: &str) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let write_error = |e: &dyn std::fmt::Display| zbus::Error::Failure(format!("Failed to write {}: {}", path.display(), e));
+
+        if self.scope == ServiceScope::User && !Self::is_flatpak() {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await.map_err(|e| write_error(&e))?;
+            }
+            tokio::fs::write(path, contents).await.map_err(|e| write_error(&e))?;
+            return Ok(());
+        }
+
+        // Creates the file's directory and writes stdin to the file named by $0.
+        const WRITE_SCRIPT: &str = r#"mkdir -p -- "$(dirname -- "$0")" && cat > "$0""#;
+
+        let mut program = Vec::new();
+        if Self::is_flatpak() {
+            program.extend(["flatpak-spawn", "--host"]);
+        }
+        if self.scope == ServiceScope::System {
+            program.push("pkexec");
+        }
+        program.extend(["sh", "-c", WRITE_SCRIPT]);
+
+        let mut child = tokio::process::Command::new(program[0])
+            .args(&program[1..])
+            .arg(path)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| zbus::Error::Failure(format!("Failed to execute {}: {}", program[0], e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(contents.as_bytes()).await.map_err(|e| write_error(&e))?;
+        }
+
+        let output = child.wait_with_output().await.map_err(|e| write_error(&e))?;
+        if !output.status.success() {
+            return Err(write_error(&String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        Ok(())
+    }
+
+    /// Enables the unit, returning the symlinks systemctl created.
+    pub async fn enable_service(&self, service_name: &str) -> Result<Vec<String>> {
+        let output = self.run_systemctl("enable", service_name).await?;
+        Ok(unit_file_changes(&output))
+    }
+
+    /// Disables the unit, returning the symlinks systemctl removed.
+    pub async fn disable_service(&self, service_name: &str) -> Result<Vec<String>> {
+        let output = self.run_systemctl("disable", service_name).await?;
+        Ok(unit_file_changes(&output))
+    }
+
+    pub async fn mask_service(&self, service_name: &str) -> Result<()> {
+        self.run_systemctl("mask", service_name).await.map(|_| ())
+    }
+
+    pub async fn unmask_service(&self, service_name: &str) -> Result<()> {
+        self.run_systemctl("unmask", service_name).await.map(|_| ())
+    }
+
+    /// Runs `systemctl <action> <service>`, through pkexec for system units and
+    /// with `--user` for the user's own, going through flatpak-spawn when
+    /// sandboxed. Returns what systemctl printed, on either stream, since it
+    /// reports the symlinks it changed on stderr.
+    async fn run_systemctl(&self, action: &str, service_name: &str) -> Result<String> {
+        let mut program = Vec::new();
+        if Self::is_flatpak() {
+            program.extend(["flatpak-spawn", "--host"]);
+        }
+        match self.scope {
+            ServiceScope::System => program.extend(["pkexec", "systemctl"]),
+            ServiceScope::User => program.extend(["systemctl", "--user"]),
+        }
+
+        let output = tokio::process::Command::new(program[0])
+            .args(&program[1..])
+            .arg(action)
+            .arg(service_name)
+            .output()
+            .await
+            .map_err(|e| zbus::Error::Failure(format!("Failed to execute {}: {}", program[0], e)))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(zbus::Error::Failure(format!("Failed to {} service: {}", action, error)));
+        }
+
+        Ok(format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+
+    /// Spawns `journalctl -f` for `units`, printing only new entries.
+    /// The process is killed when the returned `Child` is dropped.
+    pub fn follow_service_logs(units: &[String], priority: LogPriority) -> Result<tokio::process::Child> {
+        Self::journalctl(units, priority)
+            .arg("-f")
+            .arg("-n")
+            .arg("0")
+            .stdout(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| zbus::Error::Failure(format!("Failed to execute journalctl: {}", e)))
+    }
+
+    /// Opens the directory holding the unit file at `fragment_path` in the file
+    /// manager. A sandbox can't see the host's unit directories, so there it's
+    /// opened on the host instead.
+    pub async fn open_unit_file_location(fragment_path: &str) -> Result<()> {
+        let directory = std::path::Path::new(fragment_path)
+            .parent()
+            .ok_or_else(|| zbus::Error::Failure(format!("{fragment_path} has no directory")))?;
+
+        if !Self::is_flatpak() {
+            return open::that_detached(directory)
+                .map_err(|e| zbus::Error::Failure(format!("Failed to open {}: {}", directory.display(), e)));
+        }
+
+        let status = tokio::process::Command::new("flatpak-spawn")
+            .arg("--host")
+            .arg("xdg-open")
+            .arg(directory)
+            .status()
+            .await
+            .map_err(|e| zbus::Error::Failure(format!("Failed to execute flatpak-spawn: {}", e)))?;
+
+        if !status.success() {
+            return Err(zbus::Error::Failure(format!("Failed to open {}: xdg-open exited with {}", directory.display(), status)));
+        }
+
+        Ok(())
+    }
+
+    /// Opens `journalctl -f` for the unit in the user's terminal emulator, found through
+    /// `xdg-terminal-exec` or Debian's `x-terminal-emulator`, on the host when sandboxed.
+    pub async fn open_logs_in_terminal(&self, unit_name: &str) -> Result<()> {
+        const SCRIPT: &str = "if command -v xdg-terminal-exec >/dev/null; then exec xdg-terminal-exec \"$@\"; fi; \
+            if command -v x-terminal-emulator >/dev/null; then exec x-terminal-emulator -e \"$@\"; fi; \
+            exit 127";
+
+        let mut command = if Self::is_flatpak() {
+            let mut command = tokio::process::Command::new("flatpak-spawn");
+            command.arg("--host").arg("sh");
+            command
+        } else {
+            tokio::process::Command::new("sh")
+        };
+
+        command.arg("-c").arg(SCRIPT).arg("sh").arg("journalctl");
+        if self.scope == ServiceScope::User {
+            command.arg("--user");
+        }
+        command.arg("-u").arg(unit_name).arg("-f");
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| zbus::Error::Failure(format!("Failed to open a terminal: {}", e)))?;
+
+        // The terminal keeps running, only a quick exit tells that none could be opened.
+        match tokio::time::timeout(std::time::Duration::from_secs(1), child.wait()).await {
+            Ok(Ok(status)) if status.code() == Some(127) => Err(zbus::Error::Failure(
+                "No terminal emulator found, install xdg-terminal-exec".to_string(),
+            )),
+            Ok(Ok(status)) if !status.success() => Err(zbus::Error::Failure(format!(
+                "The terminal exited with {}",
+                status
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// The most recent journal entries of `units` within `range`, at most `lines`
+    /// of them when a limit is given. Several units' entries are interleaved by time.
+    pub async fn get_service_logs(
+        &self,
+        units: &[String],
+        lines: Option<u32>,
+        priority: LogPriority,
+        range: &LogRange,
+    ) -> Result<LogPage> {
+        self.read_logs(units, lines, priority, range, None).await
+    }
+
+    /// Up to `lines` of the journal entries of `units` within `range` written
+    /// before the entry at `cursor`.
+    pub async fn get_older_logs(
+        &self,
+        units: &[String],
+        lines: u32,
+        priority: LogPriority,
+        range: &LogRange,
+        cursor: &str,
+    ) -> Result<LogPage> {
+        self.read_logs(units, Some(lines), priority, range, Some(cursor)).await
+    }
+
+    async fn read_logs(
+        &self,
+        units: &[String],
+        lines: Option<u32>,
+        priority: LogPriority,
+        range: &LogRange,
+        before_cursor: Option<&str>,
+    ) -> Result<LogPage> {
+        let mut command = Self::journalctl(units, priority);
+
+        // Read backwards from the latest entry, or from the one before the cursor,
+        // so the cursor printed last is the oldest entry's.
+        command.arg("--reverse").arg("--show-cursor");
+        if let Some(cursor) = before_cursor {
+            command.arg(format!("--after-cursor={cursor}"));
+        }
+
+        if let Some(lines) = lines {
+            command.arg("-n").arg(lines.to_string());
+        }
+        if let Some(since) = &range.since {
+            command.arg("--since").arg(since);
+        }
+        if let Some(until) = &range.until {
+            command.arg("--until").arg(until);
+        }
+        if let Some(boot) = &range.boot {
+            command.arg("--boot").arg(boot);
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| zbus::Error::Failure(format!("Failed to execute journalctl: {}", e)))?;
+
+        Ok(parse_log_page(&String::from_utf8_lossy(&output.stdout), lines))
+    }
+
+    /// The boots the journal has entries of, latest first.
+    pub async fn list_boots() -> Result<Vec<Boot>> {
+        let mut command = if Self::is_flatpak() {
+            let mut command = tokio::process::Command::new("flatpak-spawn");
+            command.arg("--host").arg("journalctl");
+            command
+        } else {
+            tokio::process::Command::new("journalctl")
+        };
+
+        let output = command
+            .arg("--list-boots")
+            .arg("--output=json")
+            .arg("--no-pager")
+            .output()
+            .await
+            .map_err(|e| zbus::Error::Failure(format!("Failed to execute journalctl: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(zbus::Error::Failure(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+
+        parse_boots(&String::from_utf8_lossy(&output.stdout))
+            .map_err(|e| zbus::Error::Failure(format!("Failed to read the list of boots: {}", e)))
+    }
+
+    /// A `journalctl` command for the entries of `units` of `priority` and above,
+    /// run on the host when sandboxed.
+    fn journalctl(units: &[String], priority: LogPriority) -> tokio::process::Command {
+        let mut command = if Self::is_flatpak() {
+            let mut command = tokio::process::Command::new("flatpak-spawn");
+            command.arg("--host").arg("journalctl");
+            command
+        } else {
+            tokio::process::Command::new("journalctl")
+        };
+
+        for unit in units {
+            command.arg("-u").arg(unit);
+        }
+        command.arg("--no-pager");
+
+        if priority != LogPriority::default() {
+            command.arg("-p").arg(priority.as_str());
+        }
+
+        command
+    }
+}
+
+/// A job being queued for a unit or leaving the queue, whether it succeeded or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobEvent {
+    New { id: u32, unit: String },
+    Removed { id: u32, unit: String },
+}
+
+/// Reads a `JobNew` or `JobRemoved` signal, `None` for any other message.
+pub fn parse_job_signal(message: &zbus::Message) -> Option<JobEvent> {
+    let header = message.header();
+    match header.member()?.as_str() {
+        "JobNew" => {
+            let (id, _job, unit): (u32, zbus::zvariant::OwnedObjectPath, String) =
+                message.body().deserialize().ok()?;
+            Some(JobEvent::New { id, unit })
+        }
+        "JobRemoved" => {
+            let (id, _job, unit, _result): (u32, zbus::zvariant::OwnedObjectPath, String, String) =
+                message.body().deserialize().ok()?;
+            Some(JobEvent::Removed { id, unit })
+        }
+        _ => None,
+    }
+}
+
+/// The parts of a unit name like `getty@tty1.service`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitName<'a> {
+    /// The name before the `@` or type suffix, e.g. "getty".
+    pub prefix: &'a str,
+    /// The instance of a template unit, e.g. "tty1", and `Some("")` for the template itself.
+    pub instance: Option<&'a str>,
+    /// The unit type suffix including its dot, e.g. ".service".
+    pub suffix: &'a str,
+}
+
+impl UnitName<'_> {
+    /// The template an instance was created from, e.g. "getty@.service".
+    pub fn template(&self) -> Option<String> {
+        self.instance.map(|_| format!("{}@{}", self.prefix, self.suffix))
+    }
+}
+
+/// Splits a unit name into its prefix, instance and type suffix.
+pub fn parse_unit_name(name: &str) -> UnitName<'_> {
+    let (stem, suffix) = match name.rfind('.') {
+        Some(index) => name.split_at(index),
+        None => (name, ""),
+    };
+
+    match stem.split_once('@') {
+        Some((prefix, instance)) => UnitName { prefix, instance: Some(instance), suffix },
+        None => UnitName { prefix: stem, instance: None, suffix },
+    }
+}
+
+/// A user-facing description of `error`. For errors returned by systemd this is
+/// its own message, e.g. "Unit foo.service is masked.", without the D-Bus error name.
+pub fn error_message(error: &zbus::Error) -> String {
+    match error {
+        zbus::Error::MethodError(_, Some(description), _) => description.clone(),
+        zbus::Error::Failure(description) => description.clone(),
+        _ => error.to_string(),
+    }
+}
+
+/// What became of the selected unit once the list it was opened from was reloaded.
+#[derive(Debug)]
+pub enum Reloaded<'a> {
+    /// Its fresh copy.
+    Listed(&'a SystemdService),
+    /// It's no longer listed, e.g. a transient unit that stopped.
+    Gone,
+    /// The list came back empty, as a failed listing does, which says nothing
+    /// about the unit.
+    Unknown,
+}
+
+/// Looks the selected unit `name` up in its reloaded list.
+pub fn find_reloaded<'a>(units: &'a [SystemdService], name: &str) -> Reloaded<'a> {
+    match units.iter().find(|unit| unit.name == name) {
+        Some(unit) => Reloaded::Listed(unit),
+        None if units.is_empty() => Reloaded::Unknown,
+        None => Reloaded::Gone,
+    }
+}
+
+/// Updates `units` to a fresh listing. Entries whose state didn't change are kept
+/// as they are, new units are appended and units that are no longer listed are
+/// removed.
+pub fn merge_services(units: &mut Vec<SystemdService>, services: Vec<SystemdService>) {
+    // First load, replace everything
+    if units.is_empty() {
+        *units = services;
+        return;
+    }
+
+    for new_service in &services {
+        if let Some(index) = units.iter().position(|s| s.name == new_service.name) {
+            // Only update if the service data has changed
+            let existing_service = &units[index];
+            if existing_service.active_state != new_service.active_state
+                || existing_service.sub_state != new_service.sub_state
+                || existing_service.load_state != new_service.load_state
+                || existing_service.unit_file_state != new_service.unit_file_state
+                || existing_service.description != new_service.description {
+                units[index] = new_service.clone();
+            }
+        } else {
+            // New service appeared, add it
+            units.push(new_service.clone());
+        }
+    }
+
+    // Remove services that no longer exist
+    units.retain(|s| services.iter().any(|new_s| new_s.name == s.name));
+}
+
+/// Maps the unit files `ListUnitFiles` returns, given by path, to their states by
+/// unit name.
+pub fn unit_file_states(unit_files: Vec<(String, String)>) -> std::collections::HashMap<String, String> {
+    let mut states = std::collections::HashMap::new();
+
+    for (path, state) in unit_files {
+        let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+        states.entry(name).or_insert(state);
+    }
+
+    states
+}
+
+/// The object path systemd serves `unit_name` at, which loads the unit on first access.
+/// Bytes other than ASCII letters and digits are escaped as `_xx`, like `sd_bus_path_encode`.
+pub fn unit_object_path(unit_name: &str) -> String {
+    let mut path = String::from("/org/freedesktop/systemd1/unit/");
+
+    for (index, byte) in unit_name.bytes().enumerate() {
+        // A leading digit is escaped too, path elements can't start with one.
+        if byte.is_ascii_alphabetic() || (byte.is_ascii_digit() && index > 0) {
+            path.push(byte as char);
+        } else {
+            path.push_str(&format!("_{byte:02x}"));
+        }
+    }
+
+    path
+}
+
+/// Whether a watched unit going from `previous` to `current` active state is worth
+/// a notification: it failed, or it finished, whether it was running or, like a
+/// oneshot service, still starting.
+pub fn is_watched_transition(previous: &str, current: &str) -> bool {
+    match current {
+        "failed" => previous != "failed",
+        "inactive" => matches!(previous, "active" | "activating" | "deactivating" | "reloading"),
+        _ => false,
+    }
+}
+
+/// How long to wait before the `attempt`th try to reconnect to the bus, counting
+/// from 0: doubling from a second up to half a minute.
+pub fn reconnect_delay(attempt: u32) -> std::time::Duration {
+    std::time::Duration::from_secs(2u64.saturating_pow(attempt).min(30))
+}
+
+/// The patterns for `ListUnitsByPatterns` matching `pattern` as a unit of any
+/// of `types`.
+pub fn unit_patterns(pattern: &str, types: &[&str]) -> Vec<String> {
+    if types.is_empty() {
+        return vec![pattern.to_string()];
+    }
+    types.iter().map(|unit_type| format!("{pattern}.{unit_type}")).collect()
+}
 
-        let unit_file_state: String = unit_proxy
-            .get_property("UnitFileState")
-            .await
-            .unwrap_or_else(|_| "unknown".to_string());
+/// How many times a call is made before its error is given up on.
+const CALL_ATTEMPTS: u32 = 3;
 
-        Ok(unit_file_state)
+/// Whether `error` is likely to go away on its own, such as systemd being too
+/// busy to answer in time. Errors like a denied permission or a missing unit
+/// would only fail the same way again.
+pub fn is_retryable(error: &zbus::Error) -> bool {
+    match error {
+        zbus::Error::MethodError(name, _, _) => matches!(
+            name.as_str(),
+            "org.freedesktop.DBus.Error.NoReply"
+                | "org.freedesktop.DBus.Error.Timeout"
+                | "org.freedesktop.DBus.Error.TimedOut"
+                | "org.freedesktop.DBus.Error.LimitsExceeded"
+        ),
+        zbus::Error::FDO(error) => matches!(
+            **error,
+            zbus::fdo::Error::NoReply(_)
+                | zbus::fdo::Error::Timeout(_)
+                | zbus::fdo::Error::TimedOut(_)
+                | zbus::fdo::Error::LimitsExceeded(_)
+        ),
+        zbus::Error::InputOutput(_) => true,
+        _ => false,
     }
+}
 
-    pub async fn start_service(&self, service_name: &str) -> Result<()> {
-        let proxy = zbus::Proxy::new(
-            &self.connection,
-            "org.freedesktop.systemd1",
-            "/org/freedesktop/systemd1",
-            "org.freedesktop.systemd1.Manager",
-        )
-        .await?;
+/// How long to wait before the `attempt`th retry of a call, counting from 0:
+/// short, since the call is being waited on.
+pub fn retry_delay(attempt: u32) -> std::time::Duration {
+    std::time::Duration::from_millis(100 << attempt.min(4))
+}
 
-        let _: zbus::zvariant::OwnedObjectPath =
-            proxy.call("StartUnit", &(service_name, "replace")).await?;
-        Ok(())
+/// Makes the call `call` returns up to [`CALL_ATTEMPTS`] times, backing off in
+/// between, for as long as it fails with an error that [`is_retryable`].
+pub async fn with_retry<T, F, Fut>(mut call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match call().await {
+            Err(error) if attempt + 1 < CALL_ATTEMPTS && is_retryable(&error) => {
+                tokio::time::sleep(retry_delay(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
     }
+}
 
-    pub async fn stop_service(&self, service_name: &str) -> Result<()> {
-        let proxy = zbus::Proxy::new(
-            &self.connection,
-            "org.freedesktop.systemd1",
-            "/org/freedesktop/systemd1",
-            "org.freedesktop.systemd1.Manager",
-        )
-        .await?;
+/// Reads the property `name` off `proxy`, retrying like [`with_retry`].
+async fn read_property<T>(proxy: &zbus::Proxy<'_>, name: &str) -> Result<T>
+where
+    T: TryFrom<zbus::zvariant::OwnedValue>,
+    T::Error: Into<zbus::Error>,
+{
+    with_retry(|| proxy.get_property(name)).await
+}
 
-        let _: zbus::zvariant::OwnedObjectPath =
-            proxy.call("StopUnit", &(service_name, "replace")).await?;
-        Ok(())
+/// Splits a command line into its arguments at whitespace, keeping what's
+/// quoted together and taking a backslash to escape the next character, like
+/// a shell would. `None` if there's nothing to run or a quote isn't closed.
+pub fn split_command(line: &str) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                current.get_or_insert_with(String::new).push(chars.next()?);
+            }
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
     }
 
-    pub async fn restart_service(&self, service_name: &str) -> Result<()> {
-        let proxy = zbus::Proxy::new(
-            &self.connection,
-            "org.freedesktop.systemd1",
-            "/org/freedesktop/systemd1",
-            "org.freedesktop.systemd1.Manager",
-        )
-        .await?;
+    if quote.is_some() {
+        return None;
+    }
+    args.extend(current);
+    (!args.is_empty()).then_some(args)
+}
 
-        let _: zbus::zvariant::OwnedObjectPath =
-            proxy.call("RestartUnit", &(service_name, "replace")).await?;
-        Ok(())
+/// The path and arguments of an `ExecStart=` running `command`. systemd wants
+/// an absolute path, so a bare program name is looked up by `env` instead, in
+/// the manager's own `PATH`.
+pub fn transient_exec_start(command: Vec<String>) -> (String, Vec<String>) {
+    if command[0].starts_with('/') {
+        return (command[0].clone(), command);
     }
 
-    pub async fn enable_service(&self, service_name: &str) -> Result<()> {
-        let output = if Self::is_flatpak() {
-            tokio::process::Command::new("flatpak-spawn")
-                .arg("--host")
-                .arg("pkexec")
-                .arg("systemctl")
-                .arg("enable")
-                .arg(service_name)
-                .output()
-                .await
-                .map_err(|e| zbus::Error::Failure(format!("Failed to execute flatpak-spawn: {}", e)))?
-        } else {
-            tokio::process::Command::new("pkexec")
-                .arg("systemctl")
-                .arg("enable")
-                .arg(service_name)
-                .output()
-                .await
-                .map_err(|e| zbus::Error::Failure(format!("Failed to execute pkexec: {}", e)))?
-        };
+    let env = "/usr/bin/env".to_string();
+    let argv = std::iter::once(env.clone()).chain(command).collect();
+    (env, argv)
+}
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(zbus::Error::Failure(format!("Failed to enable service: {}", error)));
+/// The units in `current` that weren't already failed in `previous`, sorted by name.
+pub fn newly_failed(previous: &std::collections::HashSet<String>, current: &[String]) -> Vec<String> {
+    let mut units: Vec<String> = current
+        .iter()
+        .filter(|name| !previous.contains(*name))
+        .cloned()
+        .collect();
+    units.sort();
+    units.dedup();
+    units
+}
+
+/// The tree below `name`, `depth` levels deep, from each unit's dependencies in
+/// `edges`, sorted by name. A unit already on the path from the root is marked as
+/// a cycle rather than expanded again.
+pub fn dependency_tree(
+    name: &str,
+    depth: usize,
+    edges: &std::collections::HashMap<String, Vec<String>>,
+) -> DependencyNode {
+    fn node(
+        name: &str,
+        depth: usize,
+        edges: &std::collections::HashMap<String, Vec<String>>,
+        path: &mut Vec<String>,
+    ) -> DependencyNode {
+        let cycle = path.iter().any(|unit| unit == name);
+        let mut children = Vec::new();
+
+        if let Some(units) = edges.get(name).filter(|_| !cycle && depth > 0) {
+            let mut units = units.clone();
+            units.sort();
+            units.dedup();
+
+            path.push(name.to_string());
+            children = units.iter().map(|unit| node(unit, depth - 1, edges, path)).collect();
+            path.pop();
         }
 
-        Ok(())
+        DependencyNode { name: name.to_string(), children, cycle }
     }
 
-    pub async fn disable_service(&self, service_name: &str) -> Result<()> {
-        let output = if Self::is_flatpak() {
-            tokio::process::Command::new("flatpak-spawn")
-                .arg("--host")
-                .arg("pkexec")
-                .arg("systemctl")
-                .arg("disable")
-                .arg(service_name)
-                .output()
-                .await
-                .map_err(|e| zbus::Error::Failure(format!("Failed to execute flatpak-spawn: {}", e)))?
-        } else {
-            tokio::process::Command::new("pkexec")
-                .arg("systemctl")
-                .arg("disable")
-                .arg(service_name)
-                .output()
-                .await
-                .map_err(|e| zbus::Error::Failure(format!("Failed to execute pkexec: {}", e)))?
-        };
+    node(name, depth, edges, &mut Vec::new())
+}
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(zbus::Error::Failure(format!("Failed to disable service: {}", error)));
+/// Every unit reachable from `name` through `edges`, sorted, without `name` itself.
+pub fn pulled_in_units(name: &str, edges: &std::collections::HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut reached = std::collections::BTreeSet::new();
+    let mut pending = vec![name];
+
+    while let Some(unit) = pending.pop() {
+        for dependency in edges.get(unit).into_iter().flatten() {
+            if dependency != name && reached.insert(dependency.clone()) {
+                pending.push(dependency);
+            }
         }
+    }
 
-        Ok(())
+    reached.into_iter().collect()
+}
+
+/// Directives only the system manager supports, since running as another user
+/// takes privileges the user manager doesn't have.
+const SYSTEM_ONLY_DIRECTIVES: &[&str] = &["User", "Group", "DynamicUser", "SupplementaryGroups", "PAMName"];
+
+/// A system unit file made fit for the user manager. Directives only the system
+/// manager supports are commented out and returned, and installing into the
+/// system's multi-user or graphical target installs into the user's default
+/// target instead, since the user manager has neither.
+pub fn adapt_for_user_scope(contents: &str) -> (String, Vec<String>) {
+    let mut removed = Vec::new();
+    let mut adapted = String::new();
+
+    for line in contents.lines() {
+        let directive = line.trim_start();
+        match directive.split_once('=').map(|(key, _)| key.trim_end()) {
+            Some(key) if SYSTEM_ONLY_DIRECTIVES.contains(&key) => {
+                removed.push(directive.to_string());
+                adapted.push_str("# ");
+                adapted.push_str(directive);
+            }
+            Some("WantedBy") => adapted.push_str(
+                &line.replace("multi-user.target", "default.target").replace("graphical.target", "default.target"),
+            ),
+            _ => adapted.push_str(line),
+        }
+        adapted.push('\n');
     }
 
-    pub async fn get_service_logs(&self, service_name: &str, lines: u32) -> Result<String> {
-        let name = if service_name.ends_with(".service") {
-            service_name.to_string()
-        } else {
-            format!("{}.service", service_name)
-        };
+    (adapted, removed)
+}
 
-        let output = if Self::is_flatpak() {
-            tokio::process::Command::new("flatpak-spawn")
-                .arg("--host")
-                .arg("journalctl")
-                .arg("-u")
-                .arg(&name)
-                .arg("-n")
-                .arg(lines.to_string())
-                .arg("--no-pager")
-                .output()
-                .await
-                .map_err(|e| zbus::Error::Failure(format!("Failed to execute flatpak-spawn: {}", e)))?
-        } else {
-            tokio::process::Command::new("journalctl")
-                .arg("-u")
-                .arg(&name)
-                .arg("-n")
-                .arg(lines.to_string())
-                .arg("--no-pager")
-                .output()
-                .await
-                .map_err(|e| zbus::Error::Failure(format!("Failed to execute journalctl: {}", e)))?
-        };
+/// From how many automatic restarts on a service counts as crash looping.
+pub const CRASH_LOOP_RESTARTS: u32 = 5;
+
+/// Whether a service restarted by systemd `restarts` times looks like it's crash
+/// looping: it was restarted many times, or again since `previous` was read.
+pub fn is_restart_loop(previous: Option<u32>, restarts: u32) -> bool {
+    restarts >= CRASH_LOOP_RESTARTS || previous.is_some_and(|previous| restarts > previous)
+}
+
+/// The lines in which `systemctl enable` or `disable` reports a symlink it created
+/// or removed, leaving out its warnings and hints.
+pub fn unit_file_changes(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("Created symlink") || line.starts_with("Removed"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The checks of a `Conditions` or `Asserts` property that didn't hold, written like
+/// in the unit file, e.g. `ConditionPathExists=|!/etc/foo`. Each entry holds the
+/// check's type, whether it's triggering (`|`), whether it's negated (`!`), its
+/// parameter and its state: 0 if it wasn't checked, negative if it failed.
+pub fn failed_checks(checks: Vec<(String, bool, bool, String, i32)>) -> Vec<String> {
+    checks
+        .into_iter()
+        .filter(|(_, _, _, _, state)| *state < 0)
+        .map(|(kind, trigger, negate, parameter, _)| {
+            format!(
+                "{kind}={}{}{parameter}",
+                if trigger { "|" } else { "" },
+                if negate { "!" } else { "" },
+            )
+        })
+        .collect()
+}
+
+/// Turns `ExecStart` entries into readable command lines, one per line.
+/// argv[0] is replaced by the binary path since that's what actually runs.
+fn format_exec_start(commands: impl Iterator<Item = (String, Vec<String>)>) -> Option<String> {
+    let lines: Vec<String> = commands
+        .map(|(path, argv)| {
+            std::iter::once(path)
+                .chain(argv.into_iter().skip(1))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
 
-        let logs = String::from_utf8_lossy(&output.stdout).to_string();
-        Ok(logs)
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
     }
 }
 
@@ -253,6 +2307,7 @@ mod tests {
             sub_state: "running".to_string(),
             unit_path: "/lib/systemd/system/test.service".to_string(),
             unit_file_state: "enabled".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(service.name, "test.service");
@@ -262,6 +2317,10 @@ mod tests {
         assert_eq!(service.sub_state, "running");
         assert_eq!(service.unit_path, "/lib/systemd/system/test.service");
         assert_eq!(service.unit_file_state, "enabled");
+        assert!(!service.can_reload);
+        assert_eq!(service.main_pid, None);
+        assert_eq!(service.memory_current, None);
+        assert_eq!(service.cpu_usage_nsec, None);
     }
 
     #[test]
@@ -274,6 +2333,7 @@ mod tests {
             sub_state: "running".to_string(),
             unit_path: "/lib/systemd/system/test.service".to_string(),
             unit_file_state: "enabled".to_string(),
+            ..Default::default()
         };
 
         let cloned = service.clone();
@@ -362,4 +2422,463 @@ mod tests {
         
         assert_eq!(name, "myservice.service");
     }
+
+    #[test]
+    fn test_format_exec_start() {
+        let commands = vec![(
+            "/usr/sbin/nginx".to_string(),
+            vec!["nginx".to_string(), "-g".to_string(), "daemon off;".to_string()],
+        )];
+
+        assert_eq!(
+            format_exec_start(commands.into_iter()),
+            Some("/usr/sbin/nginx -g daemon off;".to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_exec_start_empty() {
+        assert_eq!(format_exec_start(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_error_message_failure() {
+        let error = zbus::Error::Failure("Failed to enable service: denied".to_string());
+        assert_eq!(error_message(&error), "Failed to enable service: denied");
+    }
+
+    #[test]
+    fn test_log_priority_as_str() {
+        assert_eq!(LogPriority::Err.as_str(), "err");
+        assert_eq!(LogPriority::default().as_str(), "debug");
+    }
+
+    #[test]
+    fn test_parse_unit_name_instance() {
+        let unit = parse_unit_name("getty@tty1.service");
+        assert_eq!(unit.prefix, "getty");
+        assert_eq!(unit.instance, Some("tty1"));
+        assert_eq!(unit.suffix, ".service");
+        assert_eq!(unit.template(), Some("getty@.service".to_string()));
+    }
+
+    #[test]
+    fn test_parse_unit_name_plain() {
+        let unit = parse_unit_name("systemd-journald.service");
+        assert_eq!(unit.prefix, "systemd-journald");
+        assert_eq!(unit.instance, None);
+        assert_eq!(unit.template(), None);
+    }
+
+    #[test]
+    fn test_parse_job_signal() {
+        let job = zbus::zvariant::ObjectPath::try_from("/org/freedesktop/systemd1/job/42").unwrap();
+        let message = zbus::Message::signal("/org/freedesktop/systemd1", "org.freedesktop.systemd1.Manager", "JobRemoved")
+            .unwrap()
+            .build(&(42u32, job, "foo.service", "done"))
+            .unwrap();
+
+        assert_eq!(
+            parse_job_signal(&message),
+            Some(JobEvent::Removed { id: 42, unit: "foo.service".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_newly_failed() {
+        let previous: std::collections::HashSet<String> = ["a.service".to_string()].into();
+        let current = vec!["c.service".to_string(), "a.service".to_string(), "b.service".to_string()];
+
+        assert_eq!(newly_failed(&previous, &current), vec!["b.service", "c.service"]);
+        assert!(newly_failed(&previous, &["a.service".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_services_drops_vanished_units() {
+        let unit = |name: &str, sub_state: &str| SystemdService {
+            name: name.to_string(),
+            sub_state: sub_state.to_string(),
+            ..Default::default()
+        };
+        let mut units = vec![unit("a.service", "running"), unit("run-u42.service", "running")];
+
+        merge_services(&mut units, vec![unit("a.service", "dead"), unit("b.service", "running")]);
+
+        let names: Vec<&str> = units.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["a.service", "b.service"]);
+        assert_eq!(units[0].sub_state, "dead");
+        // The selection is looked up by name after a refresh, and a miss sends the
+        // detail view back to the list.
+        assert!(!units.iter().any(|s| s.name == "run-u42.service"));
+    }
+
+    #[test]
+    fn test_find_reloaded() {
+        let unit = |name: &str| SystemdService { name: name.to_string(), ..Default::default() };
+        let units = vec![unit("a.service"), unit("b.service")];
+
+        assert!(matches!(find_reloaded(&units, "b.service"), Reloaded::Listed(unit) if unit.name == "b.service"));
+        // A transient unit that stopped sends the detail view back to the list.
+        assert!(matches!(find_reloaded(&units, "run-u42.service"), Reloaded::Gone));
+        assert!(matches!(find_reloaded(&[], "a.service"), Reloaded::Unknown));
+    }
+
+    #[test]
+    fn test_is_valid_journal_time() {
+        for valid in [
+            "2024-01-01", "2024-01-01 10:00", "2024-01-01 10:00:30", "-1h", "-2d 3h", "+30min", "-90", "10:00",
+            "yesterday", " now ", "1h ago", "2d 3h ago", "5min left", "Fri 2024-01-05", "friday 10:00", "@1700000000",
+        ] {
+            assert!(is_valid_journal_time(valid), "{valid}");
+        }
+
+        for invalid in [
+            "", "-", "-h", "-1 fortnight", "2024-13-01", "25:00", "1h", "last tuesday; rm -rf", "ago", " ago",
+            "Fri", "Funday 10:00", "@", "@12a",
+        ] {
+            assert!(!is_valid_journal_time(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_unit_file_states() {
+        let states = unit_file_states(vec![
+            ("/etc/systemd/system/foo.service".to_string(), "enabled".to_string()),
+            ("/usr/lib/systemd/system/foo.service".to_string(), "disabled".to_string()),
+            ("/usr/lib/systemd/system/getty@.service".to_string(), "enabled".to_string()),
+        ]);
+
+        assert_eq!(states.len(), 2);
+        assert_eq!(states["foo.service"], "enabled");
+        assert_eq!(states["getty@.service"], "enabled");
+    }
+
+    #[test]
+    fn test_unit_object_path() {
+        assert_eq!(unit_object_path("dbus.service"), "/org/freedesktop/systemd1/unit/dbus_2eservice");
+        assert_eq!(
+            unit_object_path("getty@tty1.service"),
+            "/org/freedesktop/systemd1/unit/getty_40tty1_2eservice"
+        );
+        assert_eq!(unit_object_path("1password.service"), "/org/freedesktop/systemd1/unit/_31password_2eservice");
+    }
+
+    #[test]
+    fn test_dependency_tree() {
+        let edges: std::collections::HashMap<String, Vec<String>> = [
+            ("a.target".to_string(), vec!["c.service".to_string(), "b.service".to_string(), "c.service".to_string()]),
+            ("b.service".to_string(), vec!["a.target".to_string(), "d.socket".to_string()]),
+            ("c.service".to_string(), vec![]),
+            ("d.socket".to_string(), vec!["e.service".to_string()]),
+        ]
+        .into();
+
+        let tree = dependency_tree("a.target", 2, &edges);
+        let names: Vec<&str> = tree.children.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["b.service", "c.service"]);
+
+        // a.target loops back on itself through b.service and isn't expanded again.
+        let b = &tree.children[0];
+        assert!(b.children[0].cycle);
+        assert!(b.children[0].children.is_empty());
+
+        // The depth stops the tree before d.socket's dependencies.
+        assert_eq!(b.children[1].name, "d.socket");
+        assert!(!b.children[1].cycle);
+        assert!(b.children[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_pulled_in_units() {
+        let edges: std::collections::HashMap<String, Vec<String>> = [
+            ("a.target".to_string(), vec!["c.service".to_string(), "b.service".to_string()]),
+            ("b.service".to_string(), vec!["a.target".to_string(), "d.socket".to_string()]),
+            ("c.service".to_string(), vec!["d.socket".to_string()]),
+            ("d.socket".to_string(), vec!["e.service".to_string()]),
+        ]
+        .into();
+
+        // Followed to the end, each unit once, and the cycle back to a.target left out.
+        assert_eq!(pulled_in_units("a.target", &edges), ["b.service", "c.service", "d.socket", "e.service"]);
+        assert_eq!(pulled_in_units("d.socket", &edges), ["e.service"]);
+        assert!(pulled_in_units("e.service", &edges).is_empty());
+    }
+
+    #[test]
+    fn test_reconnect_delay() {
+        let delays: Vec<u64> = (0..7).map(|attempt| reconnect_delay(attempt).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(reconnect_delay(u32::MAX).as_secs(), 30);
+    }
+
+    #[test]
+    fn test_unit_patterns() {
+        assert_eq!(unit_patterns("*", &["service"]), ["*.service"]);
+        assert_eq!(unit_patterns("ssh*", &["service", "socket"]), ["ssh*.service", "ssh*.socket"]);
+        assert_eq!(unit_patterns("*.mount", &[]), ["*.mount"]);
+    }
+
+    fn method_error(name: &str) -> zbus::Error {
+        let name = zbus::names::OwnedErrorName::try_from(name.to_string()).unwrap();
+        let message = zbus::Message::method_call("/", "Ping").unwrap().build(&()).unwrap();
+        zbus::Error::MethodError(name, None, message)
+    }
+
+    /// Runs `with_retry` on a call that fails with each of `errors` in turn before
+    /// succeeding, returning the result and how many times the call was made.
+    fn run_with_retry(errors: Vec<zbus::Error>) -> (Result<u32>, u32) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let mut errors = errors.into_iter();
+        let mut calls = 0;
+        let result = runtime.block_on(with_retry(|| {
+            calls += 1;
+            let result = errors.next().map_or(Ok(calls), Err);
+            async move { result }
+        }));
+        (result, calls)
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&method_error("org.freedesktop.DBus.Error.NoReply")));
+        assert!(is_retryable(&method_error("org.freedesktop.DBus.Error.Timeout")));
+        assert!(is_retryable(&zbus::Error::FDO(Box::new(zbus::fdo::Error::NoReply(String::new())))));
+        assert!(!is_retryable(&method_error("org.freedesktop.DBus.Error.AccessDenied")));
+        assert!(!is_retryable(&method_error("org.freedesktop.DBus.Error.InteractiveAuthorizationRequired")));
+        assert!(!is_retryable(&method_error("org.freedesktop.systemd1.NoSuchUnit")));
+        assert!(!is_retryable(&zbus::Error::Failure("failed".to_string())));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let delays: Vec<u128> = (0..6).map(|attempt| retry_delay(attempt).as_millis()).collect();
+        assert_eq!(delays, [100, 200, 400, 800, 1600, 1600]);
+    }
+
+    #[test]
+    fn test_with_retry() {
+        // Succeeds at once.
+        let (result, calls) = run_with_retry(vec![]);
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(calls, 1);
+
+        // A timeout is retried until the call goes through.
+        let (result, calls) = run_with_retry(vec![
+            method_error("org.freedesktop.DBus.Error.NoReply"),
+            method_error("org.freedesktop.DBus.Error.Timeout"),
+        ]);
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls, 3);
+
+        // Denied permission fails straight away.
+        let (result, calls) = run_with_retry(vec![
+            method_error("org.freedesktop.DBus.Error.AccessDenied"),
+        ]);
+        assert!(matches!(result, Err(zbus::Error::MethodError(..))));
+        assert_eq!(calls, 1);
+
+        // Gives up after the last attempt, with its error.
+        let (result, calls) = run_with_retry(vec![
+            method_error("org.freedesktop.DBus.Error.NoReply"),
+            method_error("org.freedesktop.DBus.Error.NoReply"),
+            method_error("org.freedesktop.DBus.Error.Timeout"),
+        ]);
+        match result {
+            Err(zbus::Error::MethodError(name, _, _)) => {
+                assert_eq!(name.as_str(), "org.freedesktop.DBus.Error.Timeout")
+            }
+            other => panic!("expected a method error, got {other:?}"),
+        }
+        assert_eq!(calls, CALL_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_failed_checks() {
+        let checks = vec![
+            ("ConditionPathExists".to_string(), false, false, "/etc/foo".to_string(), -1),
+            ("ConditionVirtualization".to_string(), true, true, "container".to_string(), 1),
+            ("ConditionFileNotEmpty".to_string(), true, true, "/etc/bar".to_string(), -1),
+            ("ConditionUser".to_string(), false, false, "root".to_string(), 0),
+        ];
+
+        assert_eq!(
+            failed_checks(checks),
+            ["ConditionPathExists=/etc/foo", "ConditionFileNotEmpty=|!/etc/bar"]
+        );
+    }
+
+    #[test]
+    fn test_adapt_for_user_scope() {
+        let unit = "[Service]\nUser=nobody\n  Group = nogroup\nExecStart=/usr/bin/foo --user=bar\n# User=commented\n\n[Install]\nWantedBy=multi-user.target\n";
+        let (adapted, removed) = adapt_for_user_scope(unit);
+
+        assert_eq!(removed, ["User=nobody", "Group = nogroup"]);
+        assert_eq!(
+            adapted,
+            "[Service]\n# User=nobody\n# Group = nogroup\nExecStart=/usr/bin/foo --user=bar\n# User=commented\n\n[Install]\nWantedBy=default.target\n"
+        );
+    }
+
+    #[test]
+    fn test_is_watched_transition() {
+        assert!(is_watched_transition("active", "failed"));
+        assert!(is_watched_transition("activating", "failed"));
+        assert!(is_watched_transition("deactivating", "inactive"));
+        assert!(is_watched_transition("activating", "inactive"));
+        assert!(!is_watched_transition("failed", "failed"));
+        assert!(!is_watched_transition("failed", "inactive"));
+        assert!(!is_watched_transition("inactive", "activating"));
+        assert!(!is_watched_transition("activating", "active"));
+    }
+
+    #[test]
+    fn test_log_units() {
+        let service = SystemdService {
+            name: "cups.service".to_string(),
+            triggered_by: vec!["cups.socket".to_string(), "cups.path".to_string()],
+            triggers: vec!["cups.socket".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(service.log_units(false), ["cups.service"]);
+        assert_eq!(service.log_units(true), ["cups.service", "cups.socket", "cups.path"]);
+    }
+
+    #[test]
+    fn test_parse_log_page() {
+        let output = "\
+Oct 16 10:00:03 host sshd[3]: third
+Oct 16 10:00:02 host sshd[2]: second, spanning
+                              two lines
+Oct 16 10:00:01 host sshd[1]: first
+-- cursor: s=abc;i=1
+";
+
+        let page = parse_log_page(output, Some(3));
+        assert_eq!(
+            page.text,
+            "Oct 16 10:00:01 host sshd[1]: first\n\
+Oct 16 10:00:02 host sshd[2]: second, spanning\n                              two lines\n\
+Oct 16 10:00:03 host sshd[3]: third\n"
+        );
+        assert_eq!(page.cursor.as_deref(), Some("s=abc;i=1"));
+        assert!(!page.at_start);
+
+        // Fewer entries than asked for, or no limit at all, reach the first entry.
+        assert!(parse_log_page(output, Some(4)).at_start);
+        assert!(parse_log_page(output, None).at_start);
+
+        let empty = parse_log_page("-- No entries --\n", Some(100));
+        assert_eq!(empty.text, "-- No entries --\n");
+        assert_eq!(empty.cursor, None);
+        assert!(empty.at_start);
+    }
+
+    #[test]
+    fn test_parse_boots() {
+        let output = r#"[{"index":-1,"boot_id":"2f0c9a8e5b2d4b2c9e6a4f1d3c7b8a90","first_entry":1736935200000000,"last_entry":1736942400000000},{"index":0,"boot_id":"7d1e4b6a9c3f4e2d8b5a1c0f9e8d7c6b","first_entry":1736946000000000,"last_entry":1736949600000000}]"#;
+        let boots = parse_boots(output).unwrap();
+
+        assert_eq!(boots.len(), 2);
+        assert_eq!(boots[0].offset, 0);
+        assert_eq!(boots[0].id, "7d1e4b6a9c3f4e2d8b5a1c0f9e8d7c6b");
+        assert_eq!(boots[1].offset, -1);
+        assert_eq!(boots[1].started, 1736935200000000);
+
+        assert!(parse_boots("No journal boot entry found.").is_err());
+    }
+
+    #[test]
+    fn test_action_inverse() {
+        assert_eq!(ServiceAction::Stop.inverse(), Some(ServiceAction::Start));
+        assert_eq!(ServiceAction::Disable.inverse(), Some(ServiceAction::Enable));
+        for action in [ServiceAction::Start, ServiceAction::Enable, ServiceAction::Mask, ServiceAction::Freeze] {
+            assert_eq!(action.inverse().and_then(ServiceAction::inverse), Some(action));
+        }
+        assert_eq!(ServiceAction::Restart.inverse(), None);
+        assert_eq!(ServiceAction::Kill(KillSignal::Term).inverse(), None);
+    }
+
+    #[test]
+    fn test_action_is_idempotent() {
+        assert!(ServiceAction::Start.is_idempotent());
+        assert!(ServiceAction::ResetFailed.is_idempotent());
+        // Repeating these would restart or signal the unit a second time.
+        for action in [ServiceAction::Restart, ServiceAction::ReloadOrRestart, ServiceAction::Kill(KillSignal::Kill), ServiceAction::Isolate] {
+            assert!(!action.is_idempotent());
+        }
+    }
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(split_command("sleep 60").unwrap(), ["sleep", "60"]);
+        assert_eq!(
+            split_command(r#"  sh -c "echo 'hi there'"  "#).unwrap(),
+            ["sh", "-c", "echo 'hi there'"]
+        );
+        assert_eq!(split_command(r#"touch a\ b '' "x\"y""#).unwrap(), ["touch", "a b", "", "x\"y"]);
+        assert_eq!(split_command("echo 'unclosed"), None);
+        assert_eq!(split_command("   "), None);
+        assert_eq!(split_command(r"trailing\"), None);
+    }
+
+    #[test]
+    fn test_transient_exec_start() {
+        let command = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        let (path, argv) = transient_exec_start(command(&["/usr/bin/sleep", "60"]));
+        assert_eq!(path, "/usr/bin/sleep");
+        assert_eq!(argv, ["/usr/bin/sleep", "60"]);
+
+        let (path, argv) = transient_exec_start(command(&["sleep", "60"]));
+        assert_eq!(path, "/usr/bin/env");
+        assert_eq!(argv, ["/usr/bin/env", "sleep", "60"]);
+    }
+
+    #[test]
+    fn test_is_restart_loop() {
+        assert!(!is_restart_loop(None, 0));
+        assert!(!is_restart_loop(None, 2));
+        assert!(!is_restart_loop(Some(2), 2));
+        assert!(is_restart_loop(Some(2), 3));
+        assert!(is_restart_loop(None, CRASH_LOOP_RESTARTS));
+
+        // A manual restart resets the count, which isn't climbing.
+        assert!(!is_restart_loop(Some(3), 0));
+    }
+
+    #[test]
+    fn test_unit_file_changes() {
+        let output = "Created symlink '/etc/systemd/system/multi-user.target.wants/foo.service' → '/usr/lib/systemd/system/foo.service'.\n\
+            The unit files have no installation config (WantedBy=, RequiredBy=, UpheldBy=,\n\
+            Also=, or Alias= settings in the [Install] section, and DefaultInstance= for\n\
+            template units).\n\
+            Removed \"/etc/systemd/system/sockets.target.wants/foo.socket\".\n";
+
+        assert_eq!(
+            unit_file_changes(output),
+            [
+                "Created symlink '/etc/systemd/system/multi-user.target.wants/foo.service' → '/usr/lib/systemd/system/foo.service'.",
+                "Removed \"/etc/systemd/system/sockets.target.wants/foo.socket\".",
+            ]
+        );
+        assert!(unit_file_changes("").is_empty());
+    }
+
+    #[test]
+    fn test_watchdog_health() {
+        let watchdog = Watchdog { interval_usec: 30_000_000, last_ping: Some(100_000_000) };
+        assert!(watchdog.is_healthy(100_000_000));
+        assert!(watchdog.is_healthy(130_000_000));
+        assert!(!watchdog.is_healthy(130_000_001));
+
+        // A clock set back since the ping doesn't count against it.
+        assert!(watchdog.is_healthy(90_000_000));
+
+        let never_pinged = Watchdog { interval_usec: 30_000_000, last_ping: None };
+        assert!(!never_pinged.is_healthy(100_000_000));
+    }
 }
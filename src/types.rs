@@ -1,27 +1,217 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use crate::export::ExportFormat;
+use crate::loading::UnitList;
 use crate::message::Message;
+use crate::systemd::{ServiceScope, SystemdService};
 use cosmic::widget::menu;
+use serde::{Deserialize, Serialize};
 
 /// The page to display in the application.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Page {
     SystemServices,
     UserServices,
+    Timers,
+    Sockets,
+    Targets,
     Details,
 }
 
+impl Page {
+    /// The page listing the services of `scope`.
+    pub fn services(scope: ServiceScope) -> Page {
+        match scope {
+            ServiceScope::System => Page::SystemServices,
+            ServiceScope::User => Page::UserServices,
+        }
+    }
+
+    /// The bus the units of this page are managed on.
+    pub fn scope(&self) -> ServiceScope {
+        match self {
+            Page::UserServices => ServiceScope::User,
+            _ => ServiceScope::System,
+        }
+    }
+
+    /// The list of units this page shows, `None` for the details page.
+    pub fn unit_list(&self) -> Option<UnitList> {
+        match self {
+            Page::SystemServices => Some(UnitList::Services(ServiceScope::System)),
+            Page::UserServices => Some(UnitList::Services(ServiceScope::User)),
+            Page::Timers => Some(UnitList::Timers),
+            Page::Sockets => Some(UnitList::Sockets),
+            Page::Targets => Some(UnitList::Targets),
+            Page::Details => None,
+        }
+    }
+}
+
+/// How the leading timestamp of each log line is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogTimestampMode {
+    /// journalctl's own output, untouched.
+    #[default]
+    Absolute,
+    /// Each timestamp replaced by how long ago it was.
+    Relative,
+}
+
+/// Limits the list to units in a given active state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StateFilter {
+    #[default]
+    All,
+    Active,
+    Inactive,
+    Failed,
+}
+
+impl StateFilter {
+    pub const ALL: [StateFilter; 4] = [
+        StateFilter::All,
+        StateFilter::Active,
+        StateFilter::Inactive,
+        StateFilter::Failed,
+    ];
+
+    pub fn matches(&self, service: &SystemdService) -> bool {
+        match self {
+            StateFilter::All => true,
+            StateFilter::Active => service.active_state == "active",
+            StateFilter::Inactive => service.active_state == "inactive",
+            StateFilter::Failed => service.active_state == "failed",
+        }
+    }
+}
+
+/// Limits the list to units in a given unit file state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileStateFilter {
+    #[default]
+    All,
+    Enabled,
+    Disabled,
+    Masked,
+    Static,
+}
+
+impl FileStateFilter {
+    pub const ALL: [FileStateFilter; 5] = [
+        FileStateFilter::All,
+        FileStateFilter::Enabled,
+        FileStateFilter::Disabled,
+        FileStateFilter::Masked,
+        FileStateFilter::Static,
+    ];
+
+    /// The runtime variants of enabled and masked, which go away on reboot,
+    /// count as the states they stand in for.
+    pub fn matches(&self, service: &SystemdService) -> bool {
+        match self {
+            FileStateFilter::All => true,
+            FileStateFilter::Enabled => matches!(service.unit_file_state.as_str(), "enabled" | "enabled-runtime"),
+            FileStateFilter::Disabled => service.unit_file_state == "disabled",
+            FileStateFilter::Masked => matches!(service.unit_file_state.as_str(), "masked" | "masked-runtime"),
+            FileStateFilter::Static => service.unit_file_state == "static",
+        }
+    }
+}
+
+/// The theme the app is drawn in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AppTheme {
+    /// Follows the desktop's light or dark mode.
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl AppTheme {
+    pub const ALL: [AppTheme; 3] = [AppTheme::System, AppTheme::Dark, AppTheme::Light];
+
+    pub fn theme(&self) -> cosmic::theme::Theme {
+        match self {
+            AppTheme::System => cosmic::theme::system_preference(),
+            AppTheme::Dark => {
+                let mut theme = cosmic::theme::system_dark();
+                theme.theme_type.prefer_dark(Some(true));
+                theme
+            }
+            AppTheme::Light => {
+                let mut theme = cosmic::theme::system_light();
+                theme.theme_type.prefer_dark(Some(false));
+                theme
+            }
+        }
+    }
+}
+
+/// The list's columns that can be hidden, the name always being shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ListColumn {
+    Description,
+    ActiveState,
+    /// Only shown for services, timers and sockets show other details in its place.
+    SubState,
+}
+
+impl ListColumn {
+    pub const ALL: [ListColumn; 3] = [
+        ListColumn::Description,
+        ListColumn::ActiveState,
+        ListColumn::SubState,
+    ];
+}
+
+/// How tightly the rows of the list are packed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListDensity {
+    #[default]
+    Comfortable,
+    /// Less padding around and between rows, to fit more of them on screen.
+    Compact,
+}
+
+/// The column the service list is sorted by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortColumn {
+    #[default]
+    Name,
+    Description,
+    ActiveState,
+    SubState,
+    NextElapse,
+}
+
 /// The context page to display in the context drawer.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub enum ContextPage {
     #[default]
     About,
+    Dependencies,
+    Settings,
+}
+
+/// The tabs below the details of a unit.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DetailTab {
+    Logs,
+    UnitFile,
 }
 
 /// Menu actions for the application's menu bar.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MenuAction {
     About,
+    DaemonReload,
+    ExportCsv,
+    ExportJson,
+    Settings,
+    CommandPalette,
+    NewTransientService,
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -30,6 +220,12 @@ impl menu::action::MenuAction for MenuAction {
     fn message(&self) -> Self::Message {
         match self {
             MenuAction::About => Message::ToggleContextPage(ContextPage::About),
+            MenuAction::DaemonReload => Message::DaemonReload,
+            MenuAction::ExportCsv => Message::Export(ExportFormat::Csv),
+            MenuAction::ExportJson => Message::Export(ExportFormat::Json),
+            MenuAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            MenuAction::CommandPalette => Message::ToggleCommandPalette,
+            MenuAction::NewTransientService => Message::OpenTransientService,
         }
     }
 }
@@ -1,19 +1,47 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::app::AppModel;
+use crate::app::{AppModel, DropInEditor, StartPreview, TransientService};
+use crate::export;
 use crate::fl;
+use crate::loading::{services_loaded, UnitList};
 use crate::message::Message;
-use crate::systemd::{ServiceScope, SystemdManager};
-use crate::types::Page;
+use crate::notes::set_note;
+use crate::notifications;
+use crate::search;
+use crate::systemd::{error_message, find_reloaded, is_restart_loop, is_valid_journal_time, split_command, KillSignal, LogPage, LogRange, Reloaded, ServiceAction, ServiceScope, SystemdManager, SystemdService};
+use crate::views::service_detail::{boot_labels, displayed_logs, log_matches, logs_scrollable_id, relative_logs};
+use crate::views::command_palette::command_palette_input_id;
+use crate::views::service_list::{filter_services, list_scrollable_id, shown_rows};
+use crate::types::{ContextPage, DetailTab, LogTimestampMode, Page};
+use cosmic::dialog::file_chooser;
+use cosmic::iced::widget::{scrollable, text_editor};
 use cosmic::prelude::*;
+use cosmic::widget::{self, Toast};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How many levels of dependencies the dependency tree resolves.
+const DEPENDENCY_TREE_DEPTH: usize = 3;
+
+/// How long after an action it can still be undone, about as long as its toast shows.
+const UNDO_TIMEOUT: Duration = Duration::from_secs(10);
 
 impl AppModel {
+    /// Names the window after the nav page, or after the unit while its details
+    /// are shown, so the taskbar tells which one it is.
     pub fn update_title(&mut self) -> Task<cosmic::Action<Message>> {
         let mut window_title = fl!("app-title");
 
-        if let Some(page) = self.nav.text(self.nav.active()) {
-            window_title.push_str(" — ");
-            window_title.push_str(page);
+        match &self.selected_service {
+            Some(service) if self.current_page == Page::Details => {
+                window_title = format!("{} — {}", service.name, window_title);
+            }
+            _ => {
+                if let Some(page) = self.nav.text(self.nav.active()) {
+                    window_title.push_str(" — ");
+                    window_title.push_str(page);
+                }
+            }
         }
 
         if let Some(id) = self.core.main_window_id() {
@@ -24,6 +52,371 @@ impl AppModel {
     }
 }
 
+impl AppModel {
+    /// The message that (re)loads the list shown by the active nav page.
+    pub fn load_list_message(&self) -> Message {
+        match self.nav.active_data::<Page>() {
+            Some(Page::Timers) => Message::LoadTimers(self.unit_scope),
+            Some(Page::Sockets) => Message::LoadSockets(self.unit_scope),
+            Some(Page::Targets) => Message::LoadTargets,
+            Some(page) => Message::LoadServices(Some(page.scope())),
+            None => Message::LoadServices(None),
+        }
+    }
+
+    /// The scope whose units `page` lists, which the Timers and Sockets pages
+    /// leave to the user.
+    pub(crate) fn page_scope(&self, page: Page) -> ServiceScope {
+        match page {
+            Page::Timers | Page::Sockets => self.unit_scope,
+            page => page.scope(),
+        }
+    }
+
+    /// How the escaped object paths of the units the current page lists end.
+    fn unit_path_suffix(&self) -> &'static str {
+        match self.current_page {
+            Page::Timers => "_2etimer",
+            Page::Sockets => "_2esocket",
+            Page::Targets => "_2etarget",
+            _ => "_2eservice",
+        }
+    }
+
+    /// The scope's manager, on the connection kept from `ScopeConnected`, or on a
+    /// new one while there is none, e.g. when the scope failed to connect at startup.
+    fn manager(&self, scope: ServiceScope) -> impl Future<Output = zbus::Result<SystemdManager>> + use<> {
+        let connected = self.managers.get(&scope).cloned();
+        async move {
+            match connected {
+                Some(manager) => Ok(manager),
+                None => SystemdManager::new(scope).await,
+            }
+        }
+    }
+
+    /// Performs `action` on the service and reports the outcome through
+    /// `ServiceActionResult`.
+    fn run_service_action(&self, action: ServiceAction, name: String) -> Task<cosmic::Action<Message>> {
+        self.run_scoped_service_action(self.current_scope, action, name)
+    }
+
+    /// Like `run_service_action`, for a unit that may be outside the current scope.
+    fn run_scoped_service_action(&self, scope: ServiceScope, action: ServiceAction, name: String) -> Task<cosmic::Action<Message>> {
+        let manager = self.manager(scope);
+        Task::perform(
+            async move {
+                let result = match manager.await {
+                    Ok(manager) => manager.perform_action(action, &name).await.map_err(|e| error_message(&e)),
+                    Err(e) => Err(error_message(&e)),
+                };
+                (name, result)
+            },
+            move |(name, result)| cosmic::Action::from(Message::ServiceActionResult(scope, action, name, result)),
+        )
+    }
+
+    /// Whether stopping or disabling the unit is asked about first, which it is for
+    /// system units and the ones listed as critical in the config.
+    fn is_critical(&self, scope: ServiceScope, name: &str) -> bool {
+        scope == ServiceScope::System || self.config.critical_units.iter().any(|unit| unit == name)
+    }
+
+    /// Runs `action` right away, unless the service is a system or critical unit,
+    /// in which case it waits for the user to confirm it in a dialog.
+    fn confirm_service_action(&mut self, action: ServiceAction, name: String) -> Task<cosmic::Action<Message>> {
+        self.confirm_scoped_service_action(self.current_scope, action, name)
+    }
+
+    fn confirm_scoped_service_action(&mut self, scope: ServiceScope, action: ServiceAction, name: String) -> Task<cosmic::Action<Message>> {
+        if self.is_critical(scope, &name) {
+            self.pending_action = Some((scope, action, name));
+            Task::none()
+        } else {
+            self.run_scoped_service_action(scope, action, name)
+        }
+    }
+
+    /// Opens the dialog showing what starting `name` would pull in, to confirm the start.
+    fn preview_start(&mut self, name: String) -> Task<cosmic::Action<Message>> {
+        let scope = self.current_scope;
+        self.start_preview = Some(StartPreview { scope, name: name.clone(), units: None });
+
+        let manager = self.manager(scope);
+        Task::perform(
+            async move {
+                let result = match manager.await {
+                    Ok(manager) => manager.start_preview(&name).await.map_err(|e| error_message(&e)),
+                    Err(e) => Err(error_message(&e)),
+                };
+                (name, result)
+            },
+            |(name, result)| cosmic::Action::from(Message::StartPreviewLoaded(name, result)),
+        )
+    }
+
+    /// Runs `action` on each of `names` in the current scope, all at once unless
+    /// that would stack authentication prompts, and reports the outcome of each,
+    /// with the symlinks an enable or disable changed.
+    fn run_batch_action(&self, action: ServiceAction, names: Vec<String>) -> Task<cosmic::Action<Message>> {
+        let scope = self.current_scope;
+        let manager = self.manager(scope);
+        Task::perform(
+            async move {
+                let manager = match manager.await {
+                    Ok(manager) => manager,
+                    Err(e) => {
+                        let error = error_message(&e);
+                        return names.into_iter().map(|name| (name, Err(error.clone()))).collect();
+                    }
+                };
+
+                let run = |name: String| {
+                    let manager = &manager;
+                    async move {
+                        let result = match action {
+                            ServiceAction::Enable => manager.enable_service(&name).await,
+                            ServiceAction::Disable => manager.disable_service(&name).await,
+                            _ => manager.perform_action(action, &name).await.map(|()| Vec::new()),
+                        };
+                        (name, result.map_err(|e| error_message(&e)))
+                    }
+                };
+
+                match action {
+                    // Each of these goes through pkexec, and running them at once
+                    // would stack several authentication prompts on top of each other.
+                    ServiceAction::Enable | ServiceAction::Disable => {
+                        let mut results = Vec::with_capacity(names.len());
+                        for name in names {
+                            results.push(run(name).await);
+                        }
+                        results
+                    }
+                    _ => futures_util::future::join_all(names.into_iter().map(run)).await,
+                }
+            },
+            move |results| cosmic::Action::from(Message::BatchActionComplete(action, results)),
+        )
+    }
+
+    /// Reads the manager's `SystemState` for the current scope.
+    fn load_system_state(&self) -> Task<cosmic::Action<Message>> {
+        let scope = self.current_scope;
+        let manager = self.manager(scope);
+        Task::perform(
+            async move {
+                let manager = manager.await.ok()?;
+                manager.get_system_state().await.ok()
+            },
+            move |state| cosmic::Action::from(Message::SystemStateLoaded(scope, state)),
+        )
+    }
+
+    /// At most how many log lines to fetch, counting the older ones loaded on
+    /// request. A time range shows all of its entries unless the user asked to
+    /// keep the limit.
+    fn log_line_limit(&self) -> Option<u32> {
+        (self.log_range.is_empty() || self.log_range_limited).then_some(self.log_lines + self.older_log_lines)
+    }
+
+    /// Shows `page` as the selected service's logs.
+    fn set_logs(&mut self, page: LogPage) {
+        self.service_logs = page.text;
+        self.log_cursor = page.cursor;
+        self.logs_at_start = page.at_start;
+        self.update_relative_logs();
+    }
+
+    /// Rebuilds the logs with relative timestamps, for when the logs or the time
+    /// they're relative to changed. Nothing is kept while they aren't shown.
+    fn update_relative_logs(&mut self) {
+        self.relative_logs = match self.log_timestamp_mode {
+            LogTimestampMode::Relative => relative_logs(&self.service_logs),
+            LogTimestampMode::Absolute => String::new(),
+        };
+    }
+
+    /// Fetches the selected service's logs again, for when what to show changed.
+    /// Only the latest entries are fetched, older ones have to be asked for again.
+    fn reload_logs(&mut self) -> Task<cosmic::Action<Message>> {
+        self.older_log_lines = 0;
+        self.loading_older_logs = false;
+
+        let Some(service) = &self.selected_service else {
+            return Task::none();
+        };
+
+        let scope = self.current_scope;
+        let log_lines = self.log_line_limit();
+        let priority = self.log_priority;
+        let range = self.log_range.clone();
+        let units = service.log_units(self.include_related_logs);
+        let manager = self.manager(scope);
+        Task::perform(
+            async move {
+                let manager = manager.await.ok()?;
+                manager.get_service_logs(&units, log_lines, priority, &range).await.ok()
+            },
+            |logs| cosmic::Action::from(Message::LogsLoaded(logs.unwrap_or_else(logs_unavailable))),
+        )
+    }
+
+    /// Reads the selected unit's dependency tree of the chosen kind, collapsed.
+    fn load_dependency_tree(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(service) = &self.selected_service else {
+            return Task::none();
+        };
+
+        self.dependency_tree = None;
+        self.expanded_dependencies.clear();
+
+        let scope = self.current_scope;
+        let kind = self.dependency_kind;
+        let name = service.name.clone();
+        let manager = self.manager(scope);
+        Task::perform(
+            async move {
+                let tree = match manager.await {
+                    Ok(manager) => manager.get_dependency_tree(&name, kind, DEPENDENCY_TREE_DEPTH).await,
+                    Err(e) => Err(e),
+                };
+                (name, kind, tree.map_err(|e| error_message(&e)))
+            },
+            |(name, kind, tree)| cosmic::Action::from(Message::DependencyTreeLoaded(name, kind, tree)),
+        )
+    }
+
+    /// Scrolls the list back to the top, for when what it shows has changed.
+    pub fn reset_list_scroll(&mut self) -> Task<cosmic::Action<Message>> {
+        self.list_scroll = scrollable::AbsoluteOffset::default();
+        scrollable::scroll_to(list_scrollable_id(), self.list_scroll)
+    }
+
+    /// Scrolls the logs to the line of the current search match.
+    fn scroll_to_log_match(&mut self) -> Task<cosmic::Action<Message>> {
+        let logs = displayed_logs(self);
+        let matches = log_matches(logs, &self.log_search);
+        if matches.is_empty() {
+            return Task::none();
+        }
+
+        let index = self.log_match % matches.len();
+        let line = logs[..matches[index]].matches('\n').count();
+        let lines = logs.lines().count().saturating_sub(1).max(1);
+        self.log_match = index;
+
+        scrollable::snap_to(
+            logs_scrollable_id(),
+            scrollable::RelativeOffset { x: 0.0, y: line as f32 / lines as f32 },
+        )
+    }
+
+    /// The loaded units of the list shown by the active nav page.
+    pub(crate) fn current_units(&self) -> &[SystemdService] {
+        match self.nav.active_data::<Page>() {
+            Some(Page::Timers) => &self.timers,
+            Some(Page::Sockets) => &self.sockets,
+            Some(Page::Targets) => &self.targets,
+            Some(Page::UserServices) => &self.user_services,
+            _ => &self.system_services,
+        }
+    }
+
+    /// Adds the applied search to the persisted recent searches.
+    fn remember_search(&mut self) {
+        let mut history = self.config.search_history.clone();
+        search::remember(&mut history, &self.search.filter);
+        if history == self.config.search_history {
+            return;
+        }
+
+        if let Some(handler) = &self.config_handler {
+            if let Err(why) = self.config.set_search_history(handler, history) {
+                eprintln!("failed to save search history: {why}");
+            }
+        }
+    }
+
+    /// Whether the arrow keys and Enter move through the list's rows, which they
+    /// don't while the details or a dialog are open.
+    fn takes_row_keys(&self) -> bool {
+        self.current_page != Page::Details
+            && self.pending_action.is_none()
+            && self.pending_shown_action.is_none()
+            && self.pending_batch_action.is_none()
+            && self.start_preview.is_none()
+            && self.drop_in_editor.is_none()
+            && self.transient_service.is_none()
+            && !self.unit_file_changes_open
+            && self.command_palette.is_none()
+    }
+
+    /// The list shown by the active nav page, the system services if none is.
+    fn current_list(&self) -> UnitList {
+        self.nav
+            .active_data::<Page>()
+            .and_then(Page::unit_list)
+            .unwrap_or(UnitList::Services(ServiceScope::System))
+    }
+
+    /// Points the selection at the freshly loaded copy of the selected unit. A unit
+    /// that is no longer listed, e.g. a transient unit that stopped, can't be shown,
+    /// so this returns to the list rather than leaving an empty detail page behind.
+    fn refresh_selected_unit(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(name) = self.selected_service.as_ref().map(|s| s.name.clone()) else {
+            return Task::none();
+        };
+
+        match find_reloaded(self.current_units(), &name) {
+            Reloaded::Listed(unit) => {
+                self.selected_service = Some(unit.clone());
+                return Task::none();
+            }
+            Reloaded::Unknown => return Task::none(),
+            Reloaded::Gone => {}
+        }
+
+        Task::batch(vec![
+            self.toasts.push(Toast::new(fl!("service-gone", service = name))).map(cosmic::Action::App),
+            Task::perform(async {}, |_| cosmic::Action::from(Message::BackToList)),
+        ])
+    }
+
+    /// The loaded units of the list shown by the active nav page.
+    fn current_units_mut(&mut self) -> &mut Vec<SystemdService> {
+        match self.nav.active_data::<Page>() {
+            Some(Page::Timers) => &mut self.timers,
+            Some(Page::Sockets) => &mut self.sockets,
+            Some(Page::Targets) => &mut self.targets,
+            Some(Page::UserServices) => &mut self.user_services,
+            _ => &mut self.system_services,
+        }
+    }
+
+    /// Derives the CPU usage percentage from the difference between two
+    /// consecutive `CPUUsageNSec` readings of the selected service.
+    fn update_cpu_percent(&mut self, cpu_usage_nsec: Option<u64>) {
+        let Some(usage) = cpu_usage_nsec else {
+            self.cpu_sample = None;
+            self.cpu_percent = None;
+            return;
+        };
+
+        let now = Instant::now();
+
+        if let Some((previous_usage, previous_at)) = self.cpu_sample {
+            let elapsed = now.duration_since(previous_at).as_nanos() as f64;
+            if elapsed > 0.0 {
+                let used = usage.saturating_sub(previous_usage) as f64;
+                self.cpu_percent = Some(used / elapsed * 100.0);
+            }
+        }
+
+        self.cpu_sample = Some((usage, now));
+    }
+}
+
 impl AppModel {
     /// Handles messages emitted by the application and its widgets.
     pub fn update_message(&mut self, message: Message) -> Task<cosmic::Action<Message>> {
@@ -45,249 +438,1346 @@ impl AppModel {
 
                 // Only show loader if services aren't already loaded
                 if !already_loaded {
-                    self.is_loading = true;
+                    self.loading.start(UnitList::Services(scope));
                 }
 
                 self.current_scope = scope;
-                return Task::perform(
-                    async move {
-                        let manager = SystemdManager::new(scope).await.ok()?;
-                        let services = manager.list_services().await.ok()?;
-                        Some((scope, services))
-                    },
-                    |result| {
-                        if let Some((scope, services)) = result {
-                            cosmic::Action::from(Message::ServicesLoaded(scope, services))
-                        } else {
-                            cosmic::Action::from(Message::ServicesLoaded(ServiceScope::System, Vec::new()))
-                        }
-                    },
-                );
+                let manager = self.manager(scope);
+                return Task::batch(vec![
+                    Task::perform(
+                        async move {
+                            let services = match manager.await {
+                                Ok(manager) => manager.list_services().await,
+                                Err(e) => Err(e),
+                            };
+                            (scope, services.map_err(|e| error_message(&e)))
+                        },
+                        |(scope, result)| match result {
+                            Ok(services) => cosmic::Action::from(Message::ServicesLoaded(scope, services)),
+                            Err(error) => cosmic::Action::from(Message::ScopeUnavailable(scope, error)),
+                        },
+                    ),
+                    self.load_system_state(),
+                ]);
+            }
+
+            Message::LoadTimers(scope) => {
+                if self.timers.is_empty() {
+                    self.loading.start(UnitList::Timers);
+                }
+
+                // Actions on the listed timers go to the scope's manager.
+                self.current_scope = scope;
+                let manager = self.manager(scope);
+                return Task::batch(vec![
+                    Task::perform(
+                        async move {
+                            let manager = manager.await.ok()?;
+                            manager.list_timers().await.ok()
+                        },
+                        move |result| cosmic::Action::from(Message::TimersLoaded(scope, result.unwrap_or_default())),
+                    ),
+                    self.load_system_state(),
+                ]);
+            }
+
+            Message::TimersLoaded(scope, timers) => {
+                self.loading.finish(UnitList::Timers);
+
+                // Timers listed before the scope was switched are the other scope's.
+                if scope == self.unit_scope {
+                    self.last_refreshed = Some(Instant::now());
+                    self.timers = timers;
+                    return self.refresh_selected_unit();
+                }
+            }
+
+            Message::SetUnitScope(scope) => {
+                if scope != self.unit_scope {
+                    self.unit_scope = scope;
+                    self.selected_names.clear();
+                    self.pending_jobs.clear();
+                    self.system_state = None;
+                    self.list_page = 0;
+                    // The other scope's units aren't shown while this one's load.
+                    self.timers.clear();
+                    self.sockets.clear();
+
+                    let load_message = self.load_list_message();
+                    return Task::batch([
+                        self.reset_list_scroll(),
+                        Task::perform(async {}, move |_| cosmic::Action::from(load_message)),
+                    ]);
+                }
+            }
+
+            Message::LoadSockets(scope) => {
+                if self.sockets.is_empty() {
+                    self.loading.start(UnitList::Sockets);
+                }
+
+                self.current_scope = scope;
+                let manager = self.manager(scope);
+                return Task::batch(vec![
+                    Task::perform(
+                        async move {
+                            let manager = manager.await.ok()?;
+                            manager.list_sockets().await.ok()
+                        },
+                        move |result| cosmic::Action::from(Message::SocketsLoaded(scope, result.unwrap_or_default())),
+                    ),
+                    self.load_system_state(),
+                ]);
+            }
+
+            Message::SocketsLoaded(scope, sockets) => {
+                self.loading.finish(UnitList::Sockets);
+
+                if scope == self.unit_scope {
+                    self.last_refreshed = Some(Instant::now());
+                    self.sockets = sockets;
+                    return self.refresh_selected_unit();
+                }
+            }
+
+            Message::LoadTargets => {
+                if self.targets.is_empty() {
+                    self.loading.start(UnitList::Targets);
+                }
+
+                self.current_scope = ServiceScope::System;
+                let manager = self.manager(ServiceScope::System);
+                return Task::batch(vec![
+                    Task::perform(
+                        async move {
+                            let manager = manager.await.ok()?;
+                            manager.list_targets().await.ok()
+                        },
+                        |result| cosmic::Action::from(Message::TargetsLoaded(result.unwrap_or_default())),
+                    ),
+                    self.load_system_state(),
+                ]);
+            }
+
+            Message::TargetsLoaded(targets) => {
+                self.loading.finish(UnitList::Targets);
+                self.last_refreshed = Some(Instant::now());
+                self.targets = targets;
+
+                return self.refresh_selected_unit();
             }
 
             Message::ServicesLoaded(scope, services) => {
-                self.is_loading = false;
-
-                let selected_service_name = self
-                    .selected_service
-                    .as_ref()
-                    .map(|s| s.name.clone());
-
-                match scope {
-                    ServiceScope::System => {
-                        // If services were already loaded, update only changed items
-                        if !self.system_services.is_empty() {
-                            for new_service in &services {
-                                if let Some(index) = self.system_services.iter().position(|s| s.name == new_service.name) {
-                                    // Only update if the service data has changed
-                                    let existing_service = &self.system_services[index];
-                                    if existing_service.active_state != new_service.active_state
-                                        || existing_service.sub_state != new_service.sub_state
-                                        || existing_service.load_state != new_service.load_state
-                                        || existing_service.unit_file_state != new_service.unit_file_state
-                                        || existing_service.description != new_service.description {
-                                        self.system_services[index] = new_service.clone();
-                                    }
-                                } else {
-                                    // New service appeared, add it
-                                    self.system_services.push(new_service.clone());
-                                }
-                            }
-                            // Remove services that no longer exist
-                            self.system_services.retain(|s| services.iter().any(|new_s| new_s.name == s.name));
-                        } else {
-                            // First load, replace everything
-                            self.system_services = services;
-                        }
+                services_loaded(&mut self.loading, &mut self.system_services, &mut self.user_services, scope, services);
+                self.last_refreshed = Some(Instant::now());
+                self.scope_errors.remove(&scope);
 
-                        if let Some(name) = selected_service_name {
-                            self.selected_service = self.system_services
-                                .iter()
-                                .find(|s| s.name == name)
-                                .cloned();
-                        }
-                    },
-                    ServiceScope::User => {
-                        // If services were already loaded, update only changed items
-                        if !self.user_services.is_empty() {
-                            for new_service in &services {
-                                if let Some(index) = self.user_services.iter().position(|s| s.name == new_service.name) {
-                                    // Only update if the service data has changed
-                                    let existing_service = &self.user_services[index];
-                                    if existing_service.active_state != new_service.active_state
-                                        || existing_service.sub_state != new_service.sub_state
-                                        || existing_service.load_state != new_service.load_state
-                                        || existing_service.unit_file_state != new_service.unit_file_state
-                                        || existing_service.description != new_service.description {
-                                        self.user_services[index] = new_service.clone();
-                                    }
-                                } else {
-                                    // New service appeared, add it
-                                    self.user_services.push(new_service.clone());
-                                }
-                            }
-                            // Remove services that no longer exist
-                            self.user_services.retain(|s| services.iter().any(|new_s| new_s.name == s.name));
-                        } else {
-                            // First load, replace everything
-                            self.user_services = services;
-                        }
+                return self.refresh_selected_unit();
+            }
 
-                        if let Some(name) = selected_service_name {
-                            self.selected_service = self.user_services
-                                .iter()
-                                .find(|s| s.name == name)
-                                .cloned();
-                        }
-                    },
+            Message::ScopeConnected(scope, manager) => {
+                self.scope_errors.remove(&scope);
+                self.managers.insert(scope, manager.clone());
+
+                // Told up front, rather than by the first action's password prompt.
+                if scope == ServiceScope::System {
+                    return Task::perform(
+                        async move { manager.can_manage_units().await.ok() },
+                        // Without polkit to ask there's nothing to warn about.
+                        |authorized| cosmic::Action::from(Message::SystemAuthorizationChecked(authorized.unwrap_or(true))),
+                    );
                 }
             }
 
+            Message::SystemAuthorizationChecked(authorized) => {
+                self.system_needs_authentication = !authorized;
+            }
+
+            Message::DismissAuthenticationNotice => {
+                self.system_needs_authentication = false;
+            }
+
+            Message::ScopeUnavailable(scope, error) => {
+                // The other scope's list is left alone, and this one keeps what it last showed.
+                self.loading.finish(UnitList::Services(scope));
+                self.scope_errors.insert(scope, error);
+            }
+
             Message::SelectService(service) => {
+                // Opening a unit found by searching counts as having searched for it.
+                self.remember_search();
+                self.search.history_open = false;
                 self.selected_service = Some(service.clone());
+                self.action_error = None;
+                self.kill_menu_open = false;
+                self.show_processes = false;
+                self.log_search.clear();
+                self.log_match = 0;
+                self.dependencies = None;
+                self.dependency_tree = None;
+                self.unit_file = None;
+                self.note = text_editor::Content::with_text(
+                    self.config.notes(self.current_scope).get(&service.name).map_or("", String::as_str),
+                );
+                self.follow_logs = false;
+                self.cpu_sample = None;
+                self.cpu_percent = None;
+                self.restart_loop = false;
                 self.current_page = Page::Details;
+                self.older_log_lines = 0;
+                self.loading_older_logs = false;
                 let scope = self.current_scope;
-                return Task::perform(
+                let log_lines = self.log_line_limit();
+                let log_priority = self.log_priority;
+                let log_range = self.log_range.clone();
+                let related_logs = self.include_related_logs;
+                let unit_path = service.unit_path.clone();
+                let name = service.name.clone();
+
+                let manager = self.manager(scope);
+                let details_task = Task::perform(
                     async move {
-                        let manager = SystemdManager::new(scope).await.ok()?;
-                        let logs = manager.get_service_logs(&service.name, 100).await.unwrap_or_default();
-                        Some(logs)
+                        let mut service = service;
+                        let manager = manager.await.ok()?;
+                        let _ = manager.load_details(&mut service).await;
+                        let units = service.log_units(related_logs);
+                        let logs = manager.get_service_logs(&units, log_lines, log_priority, &log_range).await.unwrap_or_default();
+                        Some((service, logs))
                     },
                     |result| {
-                        if let Some(logs) = result {
-                            cosmic::Action::from(Message::LogsLoaded(logs))
+                        if let Some((service, logs)) = result {
+                            cosmic::Action::from(Message::CurrentServiceRefreshed(Some(service), logs))
                         }
                         else {
-                            cosmic::Action::from(Message::LogsLoaded("Could not load logs".to_string()))
+                            cosmic::Action::from(Message::LogsLoaded(logs_unavailable()))
                         }
                     },
                 );
+
+                let unit_file_task = {
+                    let unit_path = unit_path.clone();
+                    let name = name.clone();
+                    let manager = self.manager(scope);
+                    Task::perform(
+                        async move {
+                            let contents = match manager.await {
+                                Ok(manager) => manager.get_unit_file_contents(&unit_path, &name).await,
+                                Err(e) => Err(e),
+                            };
+                            let contents = match contents {
+                                Ok(Some(contents)) => contents,
+                                Ok(None) => fl!("no-unit-file"),
+                                Err(e) => error_message(&e),
+                            };
+                            (name, contents)
+                        },
+                        |(name, contents)| cosmic::Action::from(Message::UnitFileLoaded(name, contents)),
+                    )
+                };
+
+                let manager = self.manager(scope);
+                let dependencies_task = Task::perform(
+                    async move {
+                        let manager = manager.await.ok()?;
+                        manager.get_dependencies(&unit_path).await.ok()
+                    },
+                    move |result| {
+                        cosmic::Action::from(Message::DependenciesLoaded(name, result.unwrap_or_default()))
+                    },
+                );
+
+                let mut tasks = vec![self.update_title(), details_task, unit_file_task, dependencies_task];
+
+                // The boots are listed once, and again only when asked to.
+                if self.boots.is_none() {
+                    tasks.push(load_boots());
+                }
+
+                // Resolving the tree takes a call per unit in it, so it's only done while it's shown.
+                if self.core.window.show_context && self.context_page == ContextPage::Dependencies {
+                    tasks.push(self.load_dependency_tree());
+                }
+
+                return Task::batch(tasks);
             }
 
             Message::LogsLoaded(logs) => {
-                self.service_logs = logs;
+                self.set_logs(logs);
             }
 
-            Message::BackToList => {
-                self.selected_service = None;
-                match self.current_scope {
-                    ServiceScope::System => self.current_page = Page::SystemServices,
-                    ServiceScope::User => self.current_page = Page::UserServices,
+            Message::LoadOlderLogs => {
+                let (Some(service), Some(cursor)) = (&self.selected_service, self.log_cursor.clone()) else {
+                    return Task::none();
+                };
+                if self.logs_at_start || self.loading_older_logs {
+                    return Task::none();
                 }
-            }
 
-            Message::StartService(name) => {
+                self.loading_older_logs = true;
                 let scope = self.current_scope;
+                let lines = self.log_lines;
+                let priority = self.log_priority;
+                let range = self.log_range.clone();
+                let units = service.log_units(self.include_related_logs);
+                let manager = self.manager(scope);
                 return Task::perform(
                     async move {
-                        if let Ok(manager) = SystemdManager::new(scope).await {
-                            let _ = manager.start_service(&name).await;
-                        }
+                        let logs = match manager.await {
+                            Ok(manager) => manager.get_older_logs(&units, lines, priority, &range, &cursor).await,
+                            Err(e) => Err(e),
+                        };
+                        (cursor, logs.map_err(|e| error_message(&e)))
                     },
-                    |_| cosmic::Action::from(Message::ServiceActionComplete),
+                    |(cursor, logs)| cosmic::Action::from(Message::OlderLogsLoaded(cursor, logs)),
                 );
             }
 
-            Message::StopService(name) => {
-                let scope = self.current_scope;
-                return Task::perform(
-                    async move {
-                        if let Ok(manager) = SystemdManager::new(scope).await {
-                            let _ = manager.stop_service(&name).await;
+            Message::OlderLogsLoaded(cursor, logs) => {
+                // Dropped if the logs were reloaded meanwhile, the entries may not
+                // line up with the ones shown anymore.
+                if self.log_cursor.as_ref() != Some(&cursor) {
+                    return Task::none();
+                }
+                self.loading_older_logs = false;
+
+                match logs {
+                    Ok(page) => {
+                        self.older_log_lines += self.log_lines;
+                        self.service_logs.insert_str(0, &page.text);
+                        self.update_relative_logs();
+                        self.logs_at_start = page.at_start;
+                        if page.cursor.is_some() {
+                            self.log_cursor = page.cursor;
                         }
-                    },
-                    |_| cosmic::Action::from(Message::ServiceActionComplete),
-                );
+                    }
+                    Err(error) => {
+                        return self
+                            .toasts
+                            .push(Toast::new(fl!("older-logs-failed", error = error)))
+                            .map(cosmic::Action::App);
+                    }
+                }
             }
 
-            Message::RestartService(name) => {
-                let scope = self.current_scope;
-                return Task::perform(
-                    async move {
-                        if let Ok(manager) = SystemdManager::new(scope).await {
-                            let _ = manager.restart_service(&name).await;
-                        }
-                    },
-                    |_| cosmic::Action::from(Message::ServiceActionComplete),
-                );
+            Message::DependenciesLoaded(name, dependencies) => {
+                // Ignore results that arrive after another service was selected.
+                if self.selected_service.as_ref().is_some_and(|s| s.name == name) {
+                    self.dependencies = Some(dependencies);
+                }
             }
 
-            Message::EnableService(name) => {
-                let scope = self.current_scope;
-                return Task::perform(
-                    async move {
-                        if let Ok(manager) = SystemdManager::new(scope).await {
-                            match manager.enable_service(&name).await {
-                                Ok(_) => eprintln!("Successfully enabled: {}", name),
-                                Err(e) => eprintln!("Failed to enable {}: {:?}", name, e),
-                            }
-                        } else {
-                            eprintln!("Failed to create SystemdManager");
-                        }
-                    },
-                    |_| cosmic::Action::from(Message::ServiceActionComplete),
-                );
+            Message::DependencyKindChanged(kind) => {
+                self.dependency_kind = kind;
+                return self.load_dependency_tree();
             }
 
-            Message::DisableService(name) => {
-                eprintln!("DisableService called for: {}", name);
-                let scope = self.current_scope;
-                return Task::perform(
-                    async move {
-                        eprintln!("Attempting to disable service: {} with scope: {:?}", name, scope);
-                        if let Ok(manager) = SystemdManager::new(scope).await {
-                            match manager.disable_service(&name).await {
-                                Ok(_) => eprintln!("Successfully disabled: {}", name),
-                                Err(e) => eprintln!("Failed to disable {}: {:?}", name, e),
-                            }
-                        } else {
-                            eprintln!("Failed to create SystemdManager");
-                        }
-                    },
-                    |_| cosmic::Action::from(Message::ServiceActionComplete),
-                );
+            Message::DependencyTreeLoaded(name, kind, tree) => {
+                if kind == self.dependency_kind && self.selected_service.as_ref().is_some_and(|s| s.name == name) {
+                    self.dependency_tree = Some(tree);
+                }
             }
 
-            Message::ServiceActionComplete => {
-                let scope = self.current_scope;
-                return Task::perform(async {}, move |_| {
-                    cosmic::Action::from(Message::LoadServices(Some(scope)))
-                });
+            Message::ToggleDependencyNode(path) => {
+                if !self.expanded_dependencies.remove(&path) {
+                    self.expanded_dependencies.insert(path);
+                }
             }
 
-            Message::Tick => {
-                if self.selected_service.is_some() {
-                    return Task::perform(async {}, |_| {
-                        cosmic::Action::from(Message::RefreshCurrentService)
-                    });
+            Message::UnitFileLoaded(name, contents) => {
+                if self.selected_service.as_ref().is_some_and(|s| s.name == name) {
+                    self.unit_file = Some(contents);
                 }
+            }
 
-                return Task::perform(async {}, |_| {
-                    cosmic::Action::from(Message::LoadServices(None))
-                });
+            Message::DetailTabSelected(id) => {
+                self.detail_tabs.activate(id);
             }
 
-            Message::RefreshCurrentService => {
-                if let Some(service) = &self.selected_service {
-                    let service_name = service.name.clone();
-                    let scope = self.current_scope;
-                    return Task::perform(
-                        async move {
-                            let manager = SystemdManager::new(scope).await.ok()?;
-                            let services = manager.list_services().await.ok()?;
-                            let updated_service = services.into_iter().find(|s| s.name == service_name);
-                            let logs = if let Some(_) = &updated_service {
-                                manager.get_service_logs(&service_name, 100).await.unwrap_or_default()
-                            } else {
-                                String::new()
-                            };
-                            Some((updated_service, logs))
-                        },
+            Message::FollowLogs(follow) => {
+                self.follow_logs = follow;
+            }
+
+            Message::IncludeRelatedLogs(include) => {
+                self.include_related_logs = include;
+                return self.reload_logs();
+            }
+
+            Message::LogLineReceived(line) => {
+                if !self.service_logs.is_empty() && !self.service_logs.ends_with('\n') {
+                    self.service_logs.push('\n');
+                }
+                self.service_logs.push_str(&line);
+                self.service_logs.push('\n');
+
+                // Only the new line is formatted, `ClockTick` brings the rest up to date.
+                if self.log_timestamp_mode == LogTimestampMode::Relative {
+                    self.relative_logs.push_str(&relative_logs(&line));
+                }
+            }
+
+            Message::BackToList => {
+                // Dropping the follow subscription kills its journalctl process.
+                self.follow_logs = false;
+                self.selected_service = None;
+                self.action_error = None;
+                self.dependencies = None;
+                self.dependency_tree = None;
+
+                if self.context_page == ContextPage::Dependencies {
+                    self.core.window.show_context = false;
+                }
+
+                // The nav bar always points at the list the service was opened from.
+                if let Some(page) = self.nav.active_data::<Page>() {
+                    self.current_page = *page;
+                }
+
+                return Task::batch([
+                    self.update_title(),
+                    scrollable::scroll_to(list_scrollable_id(), self.list_scroll),
+                ]);
+            }
+
+            Message::StartService(name) => {
+                // A target can pull in many units, so what it would start is shown first.
+                if name.ends_with(".target") {
+                    return self.preview_start(name);
+                }
+                return self.run_service_action(ServiceAction::Start, name);
+            }
+
+            Message::PreviewStart(name) => {
+                return self.preview_start(name);
+            }
+
+            Message::StartPreviewLoaded(name, result) => {
+                // Ignored if the dialog was closed or another preview opened meanwhile.
+                if let Some(preview) = self.start_preview.as_mut().filter(|preview| preview.name == name) {
+                    preview.units = Some(result);
+                }
+            }
+
+            Message::ConfirmStartPreview => {
+                if let Some(preview) = self.start_preview.take() {
+                    return self.run_scoped_service_action(preview.scope, ServiceAction::Start, preview.name);
+                }
+            }
+
+            Message::StopService(name) => {
+                return self.confirm_service_action(ServiceAction::Stop, name);
+            }
+
+            Message::RestartService(name) => {
+                return self.run_service_action(ServiceAction::Restart, name);
+            }
+
+            Message::ReloadService(name) => {
+                return self.run_service_action(ServiceAction::Reload, name);
+            }
+
+            Message::ReloadOrRestartService(name) => {
+                return self.run_service_action(ServiceAction::ReloadOrRestart, name);
+            }
+
+            Message::EnableService(name) => {
+                return self.run_service_action(ServiceAction::Enable, name);
+            }
+
+            Message::DisableService(name) => {
+                return self.confirm_service_action(ServiceAction::Disable, name);
+            }
+
+            Message::ToggleKillMenu => {
+                self.kill_menu_open = !self.kill_menu_open;
+            }
+
+            Message::KillService(name, signal) => {
+                self.kill_menu_open = false;
+
+                // SIGKILL can't be handled by the service, so it gets no chance to clean up.
+                if signal == KillSignal::Kill {
+                    self.pending_action = Some((self.current_scope, ServiceAction::Kill(signal), name));
+                    return Task::none();
+                }
+
+                return self.run_service_action(ServiceAction::Kill(signal), name);
+            }
+
+            Message::ToggleProcesses => {
+                self.show_processes = !self.show_processes;
+            }
+
+            Message::FreezeService(name, freeze) => {
+                let action = if freeze { ServiceAction::Freeze } else { ServiceAction::Thaw };
+                return self.run_service_action(action, name);
+            }
+
+            Message::ConfirmPendingAction => {
+                if let Some((scope, action, name)) = self.pending_action.take() {
+                    return self.run_scoped_service_action(scope, action, name);
+                }
+            }
+
+            Message::CancelPendingAction => {
+                self.pending_action = None;
+                self.pending_shown_action = None;
+                self.pending_batch_action = None;
+                self.start_preview = None;
+            }
+
+            Message::EditDropIn(name) => {
+                let scope = self.current_scope;
+                let manager = self.manager(scope);
+                return Task::perform(
+                    async move {
+                        let result = match manager.await {
+                            Ok(manager) => manager
+                                .read_drop_in(&name)
+                                .await
+                                .map(|contents| (manager.drop_in_path(&name).display().to_string(), contents))
+                                .map_err(|e| error_message(&e)),
+                            Err(e) => Err(error_message(&e)),
+                        };
+                        (name, result)
+                    },
+                    move |(name, result)| cosmic::Action::from(Message::DropInLoaded(scope, name, result)),
+                );
+            }
+
+            Message::DropInLoaded(scope, name, result) => match result {
+                Ok((path, contents)) => {
+                    // A new drop-in starts with the section most overrides go in.
+                    let text = contents.clone().unwrap_or_else(|| "[Service]\n".to_string());
+                    self.drop_in_editor = Some(DropInEditor {
+                        scope,
+                        service_name: name,
+                        path,
+                        content: text_editor::Content::with_text(&text),
+                        exists: contents.is_some(),
+                        confirm_overwrite: false,
+                        error: None,
+                        saving: false,
+                    });
+                }
+                Err(error) => {
+                    return self.toasts
+                        .push(Toast::new(fl!("drop-in-load-failed", service = name, error = error)))
+                        .map(cosmic::Action::App);
+                }
+            },
+
+            Message::DropInEdited(action) => {
+                if let Some(editor) = &mut self.drop_in_editor {
+                    // Editing after being asked to confirm means asking again.
+                    if action.is_edit() {
+                        editor.confirm_overwrite = false;
+                    }
+                    editor.content.perform(action);
+                }
+            }
+
+            Message::SaveDropIn => {
+                let Some(editor) = &mut self.drop_in_editor else {
+                    return Task::none();
+                };
+
+                let contents = editor.content.text();
+                if contents.trim().is_empty() {
+                    editor.error = Some(fl!("drop-in-empty"));
+                    return Task::none();
+                }
+
+                if editor.exists && !editor.confirm_overwrite {
+                    editor.confirm_overwrite = true;
+                    return Task::none();
+                }
+
+                editor.error = None;
+                editor.saving = true;
+
+                let scope = editor.scope;
+                let name = editor.service_name.clone();
+                let manager = self.manager(scope);
+                return Task::perform(
+                    async move {
+                        let manager = manager.await.map_err(|e| error_message(&e))?;
+                        manager.write_drop_in(&name, &contents).await.map_err(|e| error_message(&e))
+                    },
+                    |result| cosmic::Action::from(Message::DropInSaved(result)),
+                );
+            }
+
+            Message::DropInSaved(result) => {
+                let Some(editor) = &mut self.drop_in_editor else {
+                    return Task::none();
+                };
+                editor.saving = false;
+
+                if let Err(error) = result {
+                    editor.error = Some(error);
+                    return Task::none();
+                }
+
+                let name = editor.service_name.clone();
+                self.drop_in_editor = None;
+
+                return Task::batch(vec![
+                    self.toasts.push(Toast::new(fl!("drop-in-saved", service = name))).map(cosmic::Action::App),
+                    Task::perform(async {}, |_| cosmic::Action::from(Message::RefreshCurrentService)),
+                ]);
+            }
+
+            Message::CloseDropInEditor => {
+                self.drop_in_editor = None;
+            }
+
+            Message::NoteEdited(action) => {
+                let is_edit = action.is_edit();
+                self.note.perform(action);
+
+                let Some(service) = self.selected_service.as_ref().filter(|_| is_edit) else {
+                    return Task::none();
+                };
+
+                // Saved once typing pauses rather than on every keystroke, for the
+                // unit it was written for even if another one is opened meanwhile.
+                let scope = self.current_scope;
+                let name = service.name.clone();
+                let token = self.note_debounce.change((scope, name.clone()));
+                let text = self.note.text();
+                return Task::perform(
+                    tokio::time::sleep(Duration::from_millis(500)),
+                    move |_| cosmic::Action::from(Message::SaveNote(scope, name, text, token)),
+                );
+            }
+
+            Message::SaveNote(scope, name, text, token) => {
+                if !self.note_debounce.settle(&(scope, name.clone()), token) {
+                    return Task::none();
+                }
+
+                let mut notes = self.config.notes(scope).clone();
+                set_note(&mut notes, name, &text);
+
+                if let Some(handler) = &self.config_handler {
+                    let saved = match scope {
+                        ServiceScope::System => self.config.set_system_notes(handler, notes),
+                        ServiceScope::User => self.config.set_user_notes(handler, notes),
+                    };
+                    if let Err(why) = saved {
+                        eprintln!("failed to save note: {why}");
+                    }
+                }
+            }
+
+            Message::OpenTransientService => {
+                self.transient_service = Some(TransientService {
+                    scope: self.current_scope,
+                    command: String::new(),
+                    description: String::new(),
+                    error: None,
+                    starting: false,
+                });
+            }
+
+            Message::TransientCommandChanged(command) => {
+                if let Some(transient) = &mut self.transient_service {
+                    transient.command = command;
+                }
+            }
+
+            Message::TransientDescriptionChanged(description) => {
+                if let Some(transient) = &mut self.transient_service {
+                    transient.description = description;
+                }
+            }
+
+            Message::TransientScopeChanged(scope) => {
+                if let Some(transient) = &mut self.transient_service {
+                    transient.scope = scope;
+                }
+            }
+
+            Message::StartTransientService => {
+                let Some(transient) = &mut self.transient_service else {
+                    return Task::none();
+                };
+
+                let Some(command) = split_command(&transient.command) else {
+                    transient.error = Some(fl!("transient-invalid-command"));
+                    return Task::none();
+                };
+
+                transient.error = None;
+                transient.starting = true;
+
+                let scope = transient.scope;
+                let description = transient.description.clone();
+                let manager = self.manager(scope);
+                return Task::perform(
+                    async move {
+                        let manager = manager.await.map_err(|e| error_message(&e))?;
+                        manager
+                            .start_transient_service(command, &description)
+                            .await
+                            .map_err(|e| error_message(&e))
+                    },
+                    move |result| cosmic::Action::from(Message::TransientServiceStarted(scope, result)),
+                );
+            }
+
+            Message::TransientServiceStarted(scope, result) => {
+                let Some(transient) = &mut self.transient_service else {
+                    return Task::none();
+                };
+                transient.starting = false;
+
+                let name = match result {
+                    Ok(name) => name,
+                    Err(error) => {
+                        transient.error = Some(error);
+                        return Task::none();
+                    }
+                };
+
+                self.transient_service = None;
+
+                // Opened right away, it's listed once its scope's list reloads.
+                return Task::batch(vec![
+                    self.toasts.push(Toast::new(fl!("transient-started", service = name.as_str()))).map(cosmic::Action::App),
+                    Task::perform(async {}, move |_| cosmic::Action::from(Message::ShowUnit(scope, name, None))),
+                ]);
+            }
+
+            Message::CloseTransientService => {
+                self.transient_service = None;
+            }
+
+            Message::ToggleCommandPalette => {
+                if self.command_palette.take().is_some() {
+                    return Task::none();
+                }
+
+                self.command_palette = Some(String::new());
+
+                // The palette searches both scopes, including one whose page wasn't opened yet.
+                let mut tasks = vec![widget::text_input::focus(command_palette_input_id())];
+                for (scope, services) in [
+                    (ServiceScope::System, &self.system_services),
+                    (ServiceScope::User, &self.user_services),
+                ] {
+                    if services.is_empty() {
+                        let manager = self.manager(scope);
+                        tasks.push(Task::perform(
+                            async move {
+                                let services = match manager.await {
+                                    Ok(manager) => manager.list_services().await,
+                                    Err(e) => Err(e),
+                                };
+                                services.map_err(|e| error_message(&e))
+                            },
+                            move |result| match result {
+                                Ok(services) => cosmic::Action::from(Message::ServicesLoaded(scope, services)),
+                                Err(error) => cosmic::Action::from(Message::ScopeUnavailable(scope, error)),
+                            },
+                        ));
+                    }
+                }
+
+                return Task::batch(tasks);
+            }
+
+            Message::CloseCommandPalette => {
+                self.command_palette = None;
+            }
+
+            Message::CommandPaletteQueryChanged(query) => {
+                self.command_palette = Some(query);
+            }
+
+            Message::CommandPaletteAction(scope, action, name) => {
+                self.command_palette = None;
+
+                // The same actions ask for confirmation as from the list and details.
+                return match action {
+                    ServiceAction::Stop | ServiceAction::Disable => self.confirm_scoped_service_action(scope, action, name),
+                    _ => self.run_scoped_service_action(scope, action, name),
+                };
+            }
+
+            Message::ShowUnit(scope, name, tab) => {
+                // The scope's list is opened under the details, as if the unit had
+                // been picked from it.
+                let page = Page::services(scope);
+                let mut tasks = Vec::new();
+                if let Some(id) = self.nav.iter().find(|id| self.nav.data::<Page>(*id) == Some(&page)) {
+                    tasks.push(self.on_nav_select(id));
+                }
+                self.current_scope = scope;
+
+                if let Some(tab) = tab {
+                    if let Some(id) = self.detail_tabs.iter().find(|id| self.detail_tabs.data::<DetailTab>(*id) == Some(&tab)) {
+                        self.detail_tabs.activate(id);
+                    }
+                }
+
+                if let Some(id) = self.core.main_window_id() {
+                    tasks.push(cosmic::iced::window::gain_focus(id));
+                }
+
+                let manager = self.manager(scope);
+                tasks.push(Task::perform(
+                    async move {
+                        let unit = match manager.await {
+                            Ok(manager) => manager.get_unit(&name).await.ok().flatten(),
+                            Err(_) => None,
+                        };
+                        (name, unit)
+                    },
+                    |(name, unit)| match unit {
+                        Some(service) => cosmic::Action::from(Message::SelectService(service)),
+                        None => cosmic::Action::from(Message::UnitNotFound(name)),
+                    },
+                ));
+
+                return Task::batch(tasks);
+            }
+
+            Message::UnitNotFound(name) => {
+                return self
+                    .toasts
+                    .push(Toast::new(fl!("unit-not-found", service = name.as_str())))
+                    .map(cosmic::Action::App);
+            }
+
+            Message::ServiceActionResult(scope, action, name, result) => {
+                let is_selected = self.selected_service.as_ref().is_some_and(|s| s.name == name);
+
+                // Errors for the open service are also kept inline in the detail
+                // view, since toasts disappear after a few seconds.
+                if is_selected {
+                    self.action_error = result.as_ref().err().cloned();
+                }
+
+                // Only the latest action can be undone.
+                let undo = action.inverse().filter(|_| result.is_ok());
+                self.undo = undo.map(|inverse| (Instant::now(), scope, inverse, name.clone()));
+
+                let label = action.label();
+                let toast = match result {
+                    Ok(()) => Toast::new(fl!("action-succeeded", action = label, service = name.as_str())),
+                    Err(error) => Toast::new(fl!("action-failed", action = label, service = name.as_str(), error = error)),
+                };
+                let toast = if undo.is_some() { toast.action(fl!("undo"), Message::UndoAction) } else { toast };
+
+                let load_message = self.load_list_message();
+                let mut tasks = vec![
+                    self.toasts.push(toast).map(cosmic::Action::App),
+                    Task::perform(async {}, move |_| cosmic::Action::from(load_message)),
+                ];
+
+                if is_selected {
+                    tasks.push(Task::perform(async {}, |_| cosmic::Action::from(Message::RefreshCurrentService)));
+                }
+
+                return Task::batch(tasks);
+            }
+
+            Message::DismissActionError => {
+                self.action_error = None;
+            }
+
+            Message::CloseToast(id) => {
+                self.toasts.remove(id);
+            }
+
+            Message::UndoAction(id) => {
+                self.toasts.remove(id);
+                if let Some((at, scope, action, name)) = self.undo.take() {
+                    if at.elapsed() <= UNDO_TIMEOUT {
+                        // Undoing a start or enable stops or disables the unit, which
+                        // is confirmed like when asked for directly.
+                        if matches!(action, ServiceAction::Stop | ServiceAction::Disable) {
+                            return self.confirm_scoped_service_action(scope, action, name);
+                        }
+                        return self.run_scoped_service_action(scope, action, name);
+                    }
+                }
+            }
+
+            Message::MaskService(name) => {
+                return self.run_service_action(ServiceAction::Mask, name);
+            }
+
+            Message::UnmaskService(name) => {
+                return self.run_service_action(ServiceAction::Unmask, name);
+            }
+
+            Message::ResetFailedService(name) => {
+                return self.run_service_action(ServiceAction::ResetFailed, name);
+            }
+
+            // Isolating stops whatever the target doesn't pull in, possibly the
+            // whole desktop, so it's always confirmed.
+            Message::IsolateTarget(name) => {
+                self.pending_action = Some((self.current_scope, ServiceAction::Isolate, name));
+            }
+
+            Message::DaemonReload => {
+                let scope = self.current_scope;
+                let list = self.current_list();
+                self.loading.start(list);
+                let manager = self.manager(scope);
+                return Task::perform(
+                    async move {
+                        let manager = manager.await.map_err(|e| error_message(&e))?;
+                        manager.daemon_reload().await.map_err(|e| error_message(&e))
+                    },
+                    move |result| cosmic::Action::from(Message::DaemonReloadComplete(list, result)),
+                );
+            }
+
+            Message::DaemonReloadComplete(list, result) => {
+                // The reload may have been refused or the polkit prompt dismissed,
+                // in which case nothing changed but the loader still has to go away.
+                self.loading.finish(list);
+
+                if let Err(error) = result {
+                    let text = fl!("daemon-reload-failed", error = error);
+                    return self.toasts.push(Toast::new(text)).map(cosmic::Action::App);
+                }
+
+                // The open unit's details are reread too, they may have changed with its file.
+                let load_message = self.load_list_message();
+                return Task::batch(vec![
+                    Task::perform(async {}, move |_| cosmic::Action::from(load_message)),
+                    Task::perform(async {}, |_| cosmic::Action::from(Message::RefreshCurrentService)),
+                ]);
+            }
+
+            Message::Export(format) => {
+                let content = export::export(&filter_services(self, self.current_units()), format);
+                let file_name = format!("{}.{}", self.nav.text(self.nav.active()).unwrap_or("units"), format.extension());
+
+                return Task::perform(
+                    async move { save_to_file(fl!("export"), file_name, content?).await },
+                    |result| cosmic::Action::from(Message::ExportComplete(result)),
+                );
+            }
+
+            Message::ExportComplete(result) => {
+                let text = match result {
+                    Ok(Some(path)) => fl!("export-succeeded", path = path.display().to_string()),
+                    Ok(None) => return Task::none(),
+                    Err(error) => fl!("export-failed", error = error),
+                };
+                return self.toasts.push(Toast::new(text)).map(cosmic::Action::App);
+            }
+
+            Message::LogPriorityChanged(priority) => {
+                self.log_priority = priority;
+                return self.reload_logs();
+            }
+
+            Message::LogSinceChanged(since) => {
+                self.log_since = since;
+            }
+
+            Message::LogUntilChanged(until) => {
+                self.log_until = until;
+            }
+
+            Message::ApplyLogRange => {
+                let invalid = [&self.log_since, &self.log_until]
+                    .into_iter()
+                    .find(|input| !input.trim().is_empty() && !is_valid_journal_time(input));
+
+                if let Some(input) = invalid {
+                    self.log_range_error = Some(fl!("invalid-log-time", input = input.trim()));
+                    return Task::none();
+                }
+
+                let bound = |input: &str| Some(input.trim().to_string()).filter(|input| !input.is_empty());
+                self.log_range = LogRange {
+                    since: bound(&self.log_since),
+                    until: bound(&self.log_until),
+                    boot: self.log_range.boot.take(),
+                };
+                self.log_range_error = None;
+                return self.reload_logs();
+            }
+
+            Message::ClearLogRange => {
+                self.log_since.clear();
+                self.log_until.clear();
+                // The boot has a selector of its own to go back to every boot with.
+                self.log_range = LogRange { boot: self.log_range.boot.take(), ..LogRange::default() };
+                self.log_range_error = None;
+                return self.reload_logs();
+            }
+
+            Message::LimitLogRange(limited) => {
+                self.log_range_limited = limited;
+                return self.reload_logs();
+            }
+
+            Message::RefreshBoots => {
+                return load_boots();
+            }
+
+            Message::BootsLoaded(result) => {
+                let boots = match result {
+                    Ok(boots) => boots,
+                    Err(error) => {
+                        // Boots listed before are kept, there's no telling what changed.
+                        if self.boots.is_none() {
+                            self.boots = Some(Vec::new());
+                        }
+                        let text = fl!("boots-failed", error = error);
+                        return self.toasts.push(Toast::new(text)).map(cosmic::Action::App);
+                    }
+                };
+
+                self.boot_labels = boot_labels(&boots);
+                self.boots = Some(boots);
+
+                // The selected boot's entries may have been rotated out of the journal since.
+                let selected = self.log_range.boot.as_ref();
+                if selected.is_some_and(|id| !self.boots.iter().flatten().any(|boot| &boot.id == id)) {
+                    self.log_range.boot = None;
+                    return self.reload_logs();
+                }
+            }
+
+            Message::SelectBoot(index) => {
+                self.log_range.boot = index
+                    .checked_sub(1)
+                    .and_then(|index| self.boots.as_ref()?.get(index))
+                    .map(|boot| boot.id.clone());
+                return self.reload_logs();
+            }
+
+            Message::LogTimestampModeChanged(mode) => {
+                self.log_timestamp_mode = mode;
+                self.update_relative_logs();
+                return self.scroll_to_log_match();
+            }
+
+            Message::LogSearchChanged(query) => {
+                self.log_search = query;
+                self.log_match = 0;
+                return self.scroll_to_log_match();
+            }
+
+            Message::NextLogMatch => {
+                self.log_match = self.log_match.wrapping_add(1);
+                return self.scroll_to_log_match();
+            }
+
+            Message::PreviousLogMatch => {
+                let count = log_matches(displayed_logs(self), &self.log_search).len();
+                if count > 0 {
+                    self.log_match = (self.log_match % count + count - 1) % count;
+                }
+                return self.scroll_to_log_match();
+            }
+
+            Message::CopyLogs => {
+                return Task::batch(vec![
+                    cosmic::iced::clipboard::write(self.service_logs.clone()),
+                    self.toasts.push(Toast::new(fl!("logs-copied"))).map(cosmic::Action::App),
+                ]);
+            }
+
+            Message::CopyText(text) => {
+                return Task::batch(vec![
+                    cosmic::iced::clipboard::write(text),
+                    self.toasts.push(Toast::new(fl!("copied"))).map(cosmic::Action::App),
+                ]);
+            }
+
+            Message::SaveLogs => {
+                let Some(service) = &self.selected_service else {
+                    return Task::none();
+                };
+
+                let file_name = format!("{}.log", service.name);
+                let logs = self.service_logs.clone();
+                return Task::perform(
+                    async move { save_to_file(fl!("save-logs"), file_name, logs).await },
+                    |result| cosmic::Action::from(Message::LogsSaved(result)),
+                );
+            }
+
+            Message::LogsSaved(result) => {
+                let text = match result {
+                    Ok(Some(path)) => fl!("logs-saved", path = path.display().to_string()),
+                    Ok(None) => return Task::none(),
+                    Err(error) => fl!("logs-save-failed", error = error),
+                };
+                return self.toasts.push(Toast::new(text)).map(cosmic::Action::App);
+            }
+
+            Message::OpenLogsInTerminal => {
+                if let Some(service) = &self.selected_service {
+                    let scope = self.current_scope;
+                    let name = service.name.clone();
+                    let manager = self.manager(scope);
+                    return Task::perform(
+                        async move {
+                            let manager = manager.await.map_err(|e| error_message(&e))?;
+                            manager.open_logs_in_terminal(&name).await.map_err(|e| error_message(&e))
+                        },
+                        |result| cosmic::Action::from(Message::LogsOpenedInTerminal(result)),
+                    );
+                }
+            }
+
+            Message::CopyToUserScope(name) => {
+                let Some(service) = self.selected_service.as_ref().filter(|s| s.name == name) else {
+                    return Task::none();
+                };
+                let unit_path = service.unit_path.clone();
+                let system = self.manager(ServiceScope::System);
+                let user = self.manager(ServiceScope::User);
+                return Task::perform(
+                    async move {
+                        let system = system.await.map_err(|e| error_message(&e))?;
+                        let user = user.await.map_err(|e| error_message(&e))?;
+                        let (path, removed) = system
+                            .copy_to_user_scope(&user, &unit_path, &name)
+                            .await
+                            .map_err(|e| error_message(&e))?;
+                        Ok((path.display().to_string(), removed))
+                    },
+                    |result| cosmic::Action::from(Message::CopiedToUserScope(result)),
+                );
+            }
+
+            Message::CopiedToUserScope(result) => {
+                let text = match result {
+                    Ok((path, removed)) if removed.is_empty() => fl!("copied-to-user", path = path),
+                    Ok((path, removed)) => fl!("copied-to-user-without", path = path, directives = removed.join(", ")),
+                    Err(error) => fl!("copy-to-user-failed", error = error),
+                };
+                return self.toasts.push(Toast::new(text)).map(cosmic::Action::App);
+            }
+
+            Message::OpenUnitFileLocation(fragment_path) => {
+                return Task::perform(
+                    async move {
+                        SystemdManager::open_unit_file_location(&fragment_path)
+                            .await
+                            .map_err(|e| error_message(&e))
+                    },
+                    |result| cosmic::Action::from(Message::UnitFileLocationOpened(result)),
+                );
+            }
+
+            Message::UnitFileLocationOpened(result) => {
+                if let Err(error) = result {
+                    let text = fl!("open-file-location-failed", error = error);
+                    return self.toasts.push(Toast::new(text)).map(cosmic::Action::App);
+                }
+            }
+
+            Message::LogsOpenedInTerminal(result) => {
+                if let Err(error) = result {
+                    let text = fl!("open-in-terminal-failed", error = error);
+                    return self.toasts.push(Toast::new(text)).map(cosmic::Action::App);
+                }
+            }
+
+            Message::SetServicesSelected(names, selected) => {
+                for name in names {
+                    if selected {
+                        self.selected_names.insert(name);
+                    } else {
+                        self.selected_names.remove(&name);
+                    }
+                }
+            }
+
+            Message::ClearSelection => {
+                self.selected_names.clear();
+            }
+
+            Message::BatchAction(action) => {
+                let mut names: Vec<String> = self.selected_names.iter().cloned().collect();
+                names.sort();
+
+                // Like a single stop or disable, one on a critical unit is confirmed first.
+                let scope = self.current_scope;
+                if matches!(action, ServiceAction::Stop | ServiceAction::Disable)
+                    && names.iter().any(|name| self.is_critical(scope, name))
+                {
+                    self.pending_batch_action = Some((action, names));
+                    return Task::none();
+                }
+                return self.run_batch_action(action, names);
+            }
+
+            Message::ConfirmBatchAction => {
+                if let Some((action, names)) = self.pending_batch_action.take() {
+                    return self.run_batch_action(action, names);
+                }
+            }
+
+            Message::ActOnShown(action) => {
+                let names: Vec<String> = shown_rows(self, self.current_units())
+                    .into_iter()
+                    .map(|s| s.name.clone())
+                    .collect();
+                if !names.is_empty() {
+                    self.pending_shown_action = Some((action, names));
+                }
+            }
+
+            Message::ConfirmShownAction => {
+                if let Some((action, names)) = self.pending_shown_action.take() {
+                    return self.run_batch_action(action, names);
+                }
+            }
+
+            Message::BatchActionComplete(action, results) => {
+                self.unit_file_changes_open = matches!(action, ServiceAction::Enable | ServiceAction::Disable);
+                self.batch_results = Some((action, results));
+                // What's undone is the last single action, which this one came after.
+                self.undo = None;
+
+                let load_message = self.load_list_message();
+                return Task::perform(async {}, move |_| {
+                    cosmic::Action::from(load_message)
+                });
+            }
+
+            Message::DismissBatchResults => {
+                self.batch_results = None;
+                self.unit_file_changes_open = false;
+            }
+
+            Message::ShowUnitFileChanges(open) => {
+                self.unit_file_changes_open = open;
+            }
+
+            Message::Tick => {
+                if self.selected_service.is_some() {
+                    return Task::perform(async {}, |_| {
+                        cosmic::Action::from(Message::RefreshCurrentService)
+                    });
+                }
+
+                let load_message = self.load_list_message();
+                return Task::perform(async {}, move |_| {
+                    cosmic::Action::from(load_message)
+                });
+            }
+
+            Message::UnitsChanged(scope, paths) => {
+                // Only changes to what's shown are worth a refresh.
+                let shown = match &self.selected_service {
+                    Some(service) => paths.contains(&service.unit_path),
+                    None => {
+                        let suffix = self.unit_path_suffix();
+                        paths.iter().any(|path| path.ends_with(suffix))
+                    }
+                };
+
+                if scope == self.current_scope && shown {
+                    return Task::perform(async {}, |_| cosmic::Action::from(Message::Tick));
+                }
+            }
+
+            Message::JobStarted(id, unit) => {
+                self.pending_jobs.insert(id, unit);
+            }
+
+            Message::JobFinished(id, unit) => {
+                self.pending_jobs.remove(&id);
+
+                if self.selected_service.as_ref().is_some_and(|s| s.name == unit) {
+                    return Task::perform(async {}, |_| {
+                        cosmic::Action::from(Message::RefreshCurrentService)
+                    });
+                }
+            }
+
+            Message::RefreshServices => {
+                self.loading.start(self.current_list());
+                let load_message = self.load_list_message();
+                return Task::perform(async {}, move |_| {
+                    cosmic::Action::from(load_message)
+                });
+            }
+
+            Message::ClockTick => {
+                if self.log_timestamp_mode == LogTimestampMode::Relative && self.selected_service.is_some() {
+                    self.update_relative_logs();
+                }
+            }
+
+            Message::SignalsUnavailable => {
+                self.signals_unavailable = true;
+            }
+
+            Message::ConnectionLost(scope) => {
+                // Operations open a connection of their own until it's restored.
+                self.managers.remove(&scope);
+                self.reconnecting.insert(scope);
+                // Jobs that finish while disconnected are never reported removed.
+                if scope == self.current_scope {
+                    self.pending_jobs.clear();
+                }
+            }
+
+            Message::ConnectionRestored(scope, manager) => {
+                // Only a connection that was lost is replaced, the first one comes from `ScopeConnected`.
+                if !self.reconnecting.remove(&scope) {
+                    return Task::none();
+                }
+                self.managers.insert(scope, manager);
+
+                // Whatever changed while disconnected went unnoticed, so everything is reloaded.
+                if scope == self.current_scope {
+                    let mut messages = vec![self.load_list_message()];
+                    if self.selected_service.is_some() {
+                        messages.push(Message::RefreshCurrentService);
+                    }
+                    return Task::batch(messages.into_iter().map(|message| {
+                        Task::perform(async {}, move |_| cosmic::Action::from(message))
+                    }));
+                }
+            }
+
+            Message::RefreshCurrentService => {
+                if let Some(service) = &self.selected_service {
+                    let service_name = service.name.clone();
+                    let scope = self.current_scope;
+                    let log_lines = self.log_line_limit();
+                    let log_range = self.log_range.clone();
+                    let log_priority = self.log_priority;
+                    let related_logs = self.include_related_logs;
+                    let manager = self.manager(scope);
+                    return Task::perform(
+                        async move {
+                            let manager = manager.await.ok()?;
+                            let mut updated_service = manager.get_unit(&service_name).await.ok()?;
+                            if let Some(service) = updated_service.as_mut() {
+                                let _ = manager.load_details(service).await;
+                            }
+                            let logs = if let Some(service) = &updated_service {
+                                let units = service.log_units(related_logs);
+                                manager.get_service_logs(&units, log_lines, log_priority, &log_range).await.unwrap_or_default()
+                            } else {
+                                LogPage::default()
+                            };
+                            Some((updated_service, logs))
+                        },
                         |result| {
                             if let Some((service, logs)) = result {
                                 cosmic::Action::from(Message::CurrentServiceRefreshed(service, logs))
                             } else {
-                                cosmic::Action::from(Message::CurrentServiceRefreshed(None, String::new()))
+                                cosmic::Action::from(Message::CurrentServiceRefreshed(None, LogPage::default()))
                             }
                         },
                     );
@@ -296,20 +1786,31 @@ impl AppModel {
 
             Message::CurrentServiceRefreshed(service, logs) => {
                 if let Some(updated_service) = service {
+                    self.update_cpu_percent(updated_service.cpu_usage_nsec);
+
+                    // Once climbing, it stays marked until another unit is opened.
+                    if let Some(restarts) = updated_service.n_restarts {
+                        let previous = self
+                            .selected_service
+                            .as_ref()
+                            .filter(|service| service.name == updated_service.name)
+                            .and_then(|service| service.n_restarts);
+                        self.restart_loop |= is_restart_loop(previous, restarts);
+                    }
                     self.selected_service = Some(updated_service.clone());
-                    self.service_logs = logs;
 
-                    match self.current_scope {
-                        ServiceScope::System => {
-                            if let Some(index) = self.system_services.iter().position(|s| s.name == updated_service.name) {
-                                self.system_services[index] = updated_service;
-                            }
-                        },
-                        ServiceScope::User => {
-                            if let Some(index) = self.user_services.iter().position(|s| s.name == updated_service.name) {
-                                self.user_services[index] = updated_service;
-                            }
-                        },
+                    // While following, the buffer is fed line by line and a
+                    // fresh snapshot would throw away what was streamed in.
+                    if !self.follow_logs {
+                        self.set_logs(logs);
+                    }
+
+                    if let Some(existing) = self
+                        .current_units_mut()
+                        .iter_mut()
+                        .find(|s| s.name == updated_service.name)
+                    {
+                        *existing = updated_service;
                     }
                 }
             }
@@ -321,10 +1822,378 @@ impl AppModel {
                     self.context_page = context_page;
                     self.core.window.show_context = true;
                 }
+
+                if self.core.window.show_context
+                    && self.context_page == ContextPage::Dependencies
+                    && self.dependency_tree.is_none()
+                {
+                    return self.load_dependency_tree();
+                }
             }
 
             Message::SearchFilterChanged(filter) => {
-                self.search_filter = filter;
+                // Filtering a long list on every keystroke makes typing lag, so the
+                // list only follows once typing pauses.
+                self.search.input = filter;
+                let token = self.search.debounce.change();
+                return Task::perform(
+                    tokio::time::sleep(Duration::from_millis(150)),
+                    move |_| cosmic::Action::from(Message::ApplySearchFilter(token)),
+                );
+            }
+
+            Message::ApplySearchFilter(token) => {
+                if self.search.debounce.is_latest(token) {
+                    self.search.filter = self.search.input.clone();
+                    self.list_page = 0;
+                    return self.reset_list_scroll();
+                }
+            }
+
+            Message::SubmitSearch => {
+                // Enter applies the search without waiting, and is what makes it recent.
+                self.search.debounce.change();
+                self.search.filter = self.search.input.clone();
+                self.search.history_open = false;
+                self.list_page = 0;
+                self.remember_search();
+                return self.reset_list_scroll();
+            }
+
+            Message::OpenSearchHistory => {
+                self.search.history_open = !self.config.search_history.is_empty();
+            }
+
+            Message::CloseSearchHistory => {
+                self.search.history_open = false;
+            }
+
+            Message::SearchFromHistory(term) => {
+                self.search.debounce.change();
+                self.search.input = term.clone();
+                self.search.filter = term;
+                self.search.history_open = false;
+                self.list_page = 0;
+                self.remember_search();
+                return self.reset_list_scroll();
+            }
+
+            Message::SetStateFilter(filter) => {
+                self.state_filter = filter;
+                self.list_page = 0;
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_state_filter(handler, filter) {
+                        eprintln!("failed to save state filter: {why}");
+                    }
+                }
+                return self.reset_list_scroll();
+            }
+
+            Message::SetFileStateFilter(filter) => {
+                self.file_state_filter = filter;
+                self.list_page = 0;
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_file_state_filter(handler, filter) {
+                        eprintln!("failed to save file state filter: {why}");
+                    }
+                }
+
+                return self.reset_list_scroll();
+            }
+
+            Message::SystemStateLoaded(scope, state) => {
+                // A state read before the scope was switched is for the other manager.
+                if scope == self.current_scope {
+                    self.system_state = state;
+                }
+            }
+
+            Message::ListPage(page) => {
+                self.list_page = page;
+                return self.reset_list_scroll();
+            }
+
+            Message::MoveRowFocus(forward) => {
+                if !self.takes_row_keys() {
+                    return Task::none();
+                }
+
+                let rows = shown_rows(self, self.current_units());
+                if rows.is_empty() {
+                    return Task::none();
+                }
+
+                let position = self.focused_row.as_ref().and_then(|name| rows.iter().position(|s| &s.name == name));
+                let next = match (position, forward) {
+                    (Some(index), true) => (index + 1).min(rows.len() - 1),
+                    (Some(index), false) => index.saturating_sub(1),
+                    (None, true) => 0,
+                    (None, false) => rows.len() - 1,
+                };
+                self.focused_row = Some(rows[next].name.clone());
+
+                // Rows are about the same height, so this keeps the focused one in view.
+                let offset = next as f32 / (rows.len() - 1).max(1) as f32;
+                return scrollable::snap_to(list_scrollable_id(), scrollable::RelativeOffset { x: 0.0, y: offset });
+            }
+
+            Message::OpenFocusedRow => {
+                if !self.takes_row_keys() {
+                    return Task::none();
+                }
+
+                let focused = self.focused_row.as_ref().and_then(|name| {
+                    shown_rows(self, self.current_units()).into_iter().find(|s| &s.name == name).cloned()
+                });
+                if let Some(service) = focused {
+                    return Task::perform(async {}, move |_| cosmic::Action::from(Message::SelectService(service)));
+                }
+            }
+
+            Message::ListScrolled(offset) => {
+                self.list_scroll = offset;
+            }
+
+            Message::SortBy(column) => {
+                self.list_page = 0;
+
+                if self.sort_column == column {
+                    self.sort_ascending = !self.sort_ascending;
+                } else {
+                    self.sort_column = column;
+                    self.sort_ascending = true;
+                }
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_sort_column(handler, self.sort_column) {
+                        eprintln!("failed to save sort column: {why}");
+                    }
+                    if let Err(why) = self.config.set_sort_ascending(handler, self.sort_ascending) {
+                        eprintln!("failed to save sort direction: {why}");
+                    }
+                }
+            }
+
+            Message::HideVendorUnits(hide) => {
+                self.hide_vendor_units = hide;
+                self.list_page = 0;
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_hide_vendor_units(handler, hide) {
+                        eprintln!("failed to save vendor unit filter: {why}");
+                    }
+                }
+
+                return self.reset_list_scroll();
+            }
+
+            Message::GroupServices(group) => {
+                self.group_services = group;
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_group_services(handler, group) {
+                        eprintln!("failed to save grouping: {why}");
+                    }
+                }
+            }
+
+            Message::ToggleGroup(group) => {
+                if !self.expanded_groups.remove(&group) {
+                    self.expanded_groups.insert(group);
+                }
+            }
+
+            Message::ToggleFavorite(name) => {
+                if !self.favorites.remove(&name) {
+                    self.favorites.insert(name);
+                }
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_favorites(handler, self.favorites.clone()) {
+                        eprintln!("failed to save favorites: {why}");
+                    }
+                }
+            }
+
+            Message::WindowResized(width, height) => {
+                // A resize reports every size the window passes through, only the
+                // one it's left at is saved.
+                let token = self.window_size_debounce.change();
+                return Task::perform(
+                    tokio::time::sleep(Duration::from_millis(500)),
+                    move |_| cosmic::Action::from(Message::SaveWindowSize(width, height, token)),
+                );
+            }
+
+            Message::SaveWindowSize(width, height, token) => {
+                if !self.window_size_debounce.is_latest(token) {
+                    return Task::none();
+                }
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_window_width(handler, width) {
+                        eprintln!("failed to save window width: {why}");
+                    }
+                    if let Err(why) = self.config.set_window_height(handler, height) {
+                        eprintln!("failed to save window height: {why}");
+                    }
+                }
+            }
+
+            Message::SetFailureNotifications(scope, enabled) => {
+                if let Some(handler) = &self.config_handler {
+                    let saved = match scope {
+                        ServiceScope::System => self.config.set_notify_system_failures(handler, enabled),
+                        ServiceScope::User => self.config.set_notify_user_failures(handler, enabled),
+                    };
+                    if let Err(why) = saved {
+                        eprintln!("failed to save failure notifications: {why}");
+                    }
+                }
+            }
+
+            Message::SetRunInBackground(enabled) => {
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_run_in_background(handler, enabled) {
+                        eprintln!("failed to save background mode: {why}");
+                    }
+                }
+            }
+
+            Message::CollapseLogs(collapsed) => {
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_logs_collapsed(handler, collapsed) {
+                        eprintln!("failed to save logs layout: {why}");
+                    }
+                }
+            }
+
+            Message::SetKeepSearch(keep) => {
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_keep_search_on_page_change(handler, keep) {
+                        eprintln!("failed to save search setting: {why}");
+                    }
+                }
+            }
+
+            Message::SetAppTheme(theme) => {
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_app_theme(handler, theme) {
+                        eprintln!("failed to save theme: {why}");
+                    }
+                }
+                return cosmic::command::set_theme(theme.theme());
+            }
+
+            Message::SetStartupPage(page) => {
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_startup_page(handler, page) {
+                        eprintln!("failed to save startup page: {why}");
+                    }
+                }
+            }
+
+            Message::SetPollInterval(seconds) => {
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_poll_interval(handler, seconds) {
+                        eprintln!("failed to save poll interval: {why}");
+                    }
+                }
+            }
+
+            Message::SetListDensity(density) => {
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_list_density(handler, density) {
+                        eprintln!("failed to save list density: {why}");
+                    }
+                }
+            }
+
+            Message::ToggleColumnMenu => {
+                self.column_menu_open = !self.column_menu_open;
+            }
+
+            Message::SetColumnVisible(column, visible) => {
+                if visible {
+                    self.hidden_columns.remove(&column);
+                } else {
+                    self.hidden_columns.insert(column);
+                }
+
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_hidden_columns(handler, self.hidden_columns.clone()) {
+                        eprintln!("failed to save hidden columns: {why}");
+                    }
+                }
+            }
+
+            Message::SetLogLines(lines) => {
+                self.log_lines = lines;
+                if let Some(handler) = &self.config_handler {
+                    if let Err(why) = self.config.set_log_lines(handler, lines) {
+                        eprintln!("failed to save log lines: {why}");
+                    }
+                }
+                return self.reload_logs();
+            }
+
+            Message::ServicesFailed(scope, units) => {
+                let body = match scope {
+                    ServiceScope::System => fl!("system-service-failed-body"),
+                    ServiceScope::User => fl!("user-service-failed-body"),
+                };
+
+                let notifications = units.into_iter().map(|unit| {
+                    let summary = fl!("service-failed", service = unit);
+                    let body = body.clone();
+                    Task::future(async move {
+                        if let Err(why) = notifications::notify(&fl!("app-title"), &summary, &body).await {
+                            eprintln!("failed to send notification: {why}");
+                        }
+                    })
+                    .discard()
+                });
+
+                return Task::batch(notifications);
+            }
+
+            Message::ToggleWatch(name) => {
+                let scope = self.current_scope;
+                let mut units = self.config.watched_units(scope).clone();
+                if !units.remove(&name) {
+                    units.insert(name);
+                }
+
+                if let Some(handler) = &self.config_handler {
+                    let saved = match scope {
+                        ServiceScope::System => self.config.set_watched_system_units(handler, units),
+                        ServiceScope::User => self.config.set_watched_user_units(handler, units),
+                    };
+                    if let Err(why) = saved {
+                        eprintln!("failed to save watched units: {why}");
+                    }
+                }
+            }
+
+            Message::WatchedUnitChanged(scope, unit, state) => {
+                let summary = if state == "failed" {
+                    fl!("service-failed", service = unit.as_str())
+                } else {
+                    fl!("service-finished", service = unit.as_str())
+                };
+                let body = match scope {
+                    ServiceScope::System => fl!("watched-system-unit-body"),
+                    ServiceScope::User => fl!("watched-user-unit-body"),
+                };
+
+                return Task::future(async move {
+                    if let Err(why) = notifications::notify(&fl!("app-title"), &summary, &body).await {
+                        eprintln!("failed to send notification: {why}");
+                    }
+                })
+                .discard();
             }
 
             Message::LaunchUrl(url) => match open::that_detached(&url) {
@@ -337,3 +2206,37 @@ impl AppModel {
         Task::none()
     }
 }
+
+/// Shown in place of the logs when they couldn't be read at all.
+fn logs_unavailable() -> LogPage {
+    LogPage { text: "Could not load logs".to_string(), cursor: None, at_start: true }
+}
+
+/// Lists the boots the journal has entries of, for the boot selector.
+fn load_boots() -> Task<cosmic::Action<Message>> {
+    Task::perform(
+        async { SystemdManager::list_boots().await.map_err(|e| error_message(&e)) },
+        |result| cosmic::Action::from(Message::BootsLoaded(result)),
+    )
+}
+
+/// Asks for a destination through the file chooser and writes `content` to it.
+/// Returns the path written to, or `None` if the dialog was cancelled.
+async fn save_to_file(title: String, file_name: String, content: String) -> Result<Option<PathBuf>, String> {
+    let dialog = file_chooser::save::Dialog::new()
+        .title(title)
+        .file_name(file_name);
+
+    let response = match dialog.save_file().await {
+        Ok(response) => response,
+        Err(file_chooser::Error::Cancelled) => return Ok(None),
+        Err(why) => return Err(why.to_string()),
+    };
+
+    let Some(path) = response.url().and_then(|url| url.to_file_path().ok()) else {
+        return Ok(None);
+    };
+
+    tokio::fs::write(&path, content).await.map_err(|e| e.to_string())?;
+    Ok(Some(path))
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::AppModel;
+use crate::fl;
+use crate::message::Message;
+use crate::systemd::{ServiceAction, ServiceScope, SystemdService};
+use crate::views::service_list::match_score;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{self, icon};
+use cosmic::Element;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+/// Results beyond this are left out, the query is meant to be narrowed instead.
+const MAX_RESULTS: usize = 12;
+
+/// Fuzzy searches the system and user services together, with the common actions
+/// inline on every result.
+pub fn view_command_palette<'a>(app: &'a AppModel, query: &'a str) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let search = widget::text_input(fl!("command-palette-placeholder"), query)
+        .id(command_palette_input_id())
+        .leading_icon(icon::from_name("system-search-symbolic").size(16).into())
+        .on_input(Message::CommandPaletteQueryChanged);
+
+    let mut results = widget::column().spacing(spacing.space_xxs);
+
+    if query.is_empty() {
+        results = results.push(widget::text::caption(fl!("command-palette-hint")));
+    } else {
+        let matcher = SkimMatcherV2::default();
+        let mut scored: Vec<(i64, ServiceScope, &SystemdService)> = app
+            .system_services
+            .iter()
+            .map(|s| (ServiceScope::System, s))
+            .chain(app.user_services.iter().map(|s| (ServiceScope::User, s)))
+            .filter_map(|(scope, s)| match_score(&matcher, s, query).map(|score| (score, scope, s)))
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+        if scored.is_empty() {
+            results = results.push(widget::text::caption(fl!("no-matching-units")));
+        }
+
+        for (_, scope, service) in scored.into_iter().take(MAX_RESULTS) {
+            results = results.push(result_row(scope, service));
+        }
+    }
+
+    let control = widget::column()
+        .push(search)
+        .push(widget::scrollable(results).height(Length::Fixed(360.0)))
+        .spacing(spacing.space_s);
+
+    widget::dialog()
+        .title(fl!("command-palette"))
+        .control(control)
+        .secondary_action(
+            widget::button::standard(fl!("close"))
+                .on_press(Message::CloseCommandPalette)
+        )
+        .into()
+}
+
+pub(crate) fn command_palette_input_id() -> widget::Id {
+    widget::Id::new("command-palette-input")
+}
+
+fn result_row(scope: ServiceScope, service: &SystemdService) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let scope_label = match scope {
+        ServiceScope::System => fl!("system"),
+        ServiceScope::User => fl!("user"),
+    };
+
+    let action = |icon_name: &'static str, tooltip: String, action: ServiceAction| {
+        widget::button::icon(icon::from_name(icon_name))
+            .tooltip(tooltip)
+            .on_press(Message::CommandPaletteAction(scope, action, service.name.clone()))
+    };
+
+    let start_stop = if service.active_state == "active" {
+        action("media-playback-stop-symbolic", fl!("stop"), ServiceAction::Stop)
+    } else {
+        action("media-playback-start-symbolic", fl!("start"), ServiceAction::Start)
+    };
+
+    let mut row = widget::row()
+        .push(
+            widget::column()
+                .push(widget::text(&service.name))
+                .push(widget::text::caption(format!("{scope_label} · {}", service.sub_state)))
+                .width(Length::Fill)
+        )
+        .push(start_stop)
+        .push(action("view-refresh-symbolic", fl!("restart"), ServiceAction::Restart));
+
+    // Like the detail view's toggler, only plain enabled or disabled units can be toggled.
+    match service.unit_file_state.as_str() {
+        "enabled" => row = row.push(action("list-remove-symbolic", fl!("disable"), ServiceAction::Disable)),
+        "disabled" => row = row.push(action("list-add-symbolic", fl!("enable"), ServiceAction::Enable)),
+        _ => {}
+    }
+
+    row
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_xxs)
+        .into()
+}
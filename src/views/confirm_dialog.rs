@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::StartPreview;
+use crate::fl;
+use crate::message::Message;
+use crate::systemd::ServiceAction;
+use cosmic::iced::Length;
+use cosmic::widget;
+use cosmic::Element;
+
+pub fn view_confirm_dialog<'a>(action: ServiceAction, service_name: &str) -> Element<'a, Message> {
+    let label = action.label();
+    let body = match action {
+        ServiceAction::Kill(_) => fl!("confirm-kill-body", service = service_name),
+        ServiceAction::Isolate => fl!("confirm-isolate-body", service = service_name),
+        _ => fl!("confirm-action-body", service = service_name),
+    };
+
+    widget::dialog()
+        .title(fl!("confirm-action-title", action = label.clone(), service = service_name))
+        .body(body)
+        .icon(widget::icon::from_name("dialog-warning-symbolic").size(64))
+        .primary_action(
+            widget::button::destructive(label)
+                .on_press(Message::ConfirmPendingAction)
+        )
+        .secondary_action(
+            widget::button::standard(fl!("cancel"))
+                .on_press(Message::CancelPendingAction)
+        )
+        .into()
+}
+
+/// Asks before an action on every unit the list shows, naming each of them.
+pub fn view_confirm_shown_dialog(action: ServiceAction, names: &[String]) -> Element<'_, Message> {
+    confirm_units_dialog(action, names, fl!("confirm-shown-body"), Message::ConfirmShownAction)
+}
+
+/// Asks before stopping or disabling the checked units when some are critical.
+pub fn view_confirm_batch_dialog(action: ServiceAction, names: &[String]) -> Element<'_, Message> {
+    confirm_units_dialog(action, names, fl!("confirm-batch-body"), Message::ConfirmBatchAction)
+}
+
+fn confirm_units_dialog(action: ServiceAction, names: &[String], body: String, on_confirm: Message) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+    let label = action.label();
+
+    let unit_list = names
+        .iter()
+        .fold(widget::column().spacing(spacing.space_xxxs), |column, name| column.push(widget::text(name)));
+
+    widget::dialog()
+        .title(fl!("confirm-shown-title", action = label.clone(), count = names.len()))
+        .body(body)
+        .icon(widget::icon::from_name("dialog-warning-symbolic").size(64))
+        .control(widget::scrollable(unit_list).height(Length::Fixed(240.0)))
+        .primary_action(
+            widget::button::destructive(label)
+                .on_press(on_confirm)
+        )
+        .secondary_action(
+            widget::button::standard(fl!("cancel"))
+                .on_press(Message::CancelPendingAction)
+        )
+        .into()
+}
+
+/// Lists the inactive units a start would pull in, then starts the unit once confirmed.
+pub fn view_start_preview_dialog(preview: &StartPreview) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let (body, units) = match &preview.units {
+        None => (fl!("start-preview-loading"), None),
+        Some(Err(error)) => (fl!("start-preview-failed", error = error.as_str()), None),
+        Some(Ok(units)) if units.is_empty() => (fl!("start-preview-nothing-else"), None),
+        Some(Ok(units)) => (fl!("start-preview-body", count = units.len()), Some(units)),
+    };
+
+    let mut dialog = widget::dialog()
+        .title(fl!("start-preview-title", service = preview.name.as_str()))
+        .body(body)
+        .primary_action(
+            widget::button::suggested(fl!("start"))
+                .on_press_maybe(preview.units.is_some().then_some(Message::ConfirmStartPreview))
+        )
+        .secondary_action(
+            widget::button::standard(fl!("cancel"))
+                .on_press(Message::CancelPendingAction)
+        );
+
+    if let Some(units) = units {
+        let unit_list = units
+            .iter()
+            .fold(widget::column().spacing(spacing.space_xxxs), |column, name| column.push(widget::text(name)));
+        dialog = dialog.control(widget::scrollable(unit_list).height(Length::Fixed(240.0)));
+    }
+
+    dialog.into()
+}
+
+/// Lists the symlinks a batch enable or disable created or removed for each unit
+/// it succeeded on, which is what enabling a unit actually does.
+pub fn view_unit_file_changes_dialog(
+    action: ServiceAction,
+    results: &[(String, Result<Vec<String>, String>)],
+) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let changed: Vec<(&String, &Vec<String>)> = results
+        .iter()
+        .filter_map(|(name, result)| result.as_ref().ok().map(|changes| (name, changes)))
+        .collect();
+    let count = changed.iter().map(|(_, changes)| changes.len()).sum::<usize>();
+
+    let unit_list = changed.into_iter().fold(widget::column().spacing(spacing.space_xs), |column, (name, changes)| {
+        let mut unit = widget::column().push(widget::text::heading(name)).spacing(spacing.space_xxxs);
+        if changes.is_empty() {
+            unit = unit.push(widget::text::caption(fl!("no-unit-file-changes")));
+        }
+        for change in changes {
+            unit = unit.push(widget::text(change).font(cosmic::font::mono()).size(12));
+        }
+        column.push(unit)
+    });
+
+    widget::dialog()
+        .title(fl!("unit-file-changes-title", action = action.label()))
+        .body(fl!("unit-file-changes-body", count = count))
+        .control(widget::scrollable(unit_list).height(Length::Fixed(240.0)))
+        .primary_action(
+            widget::button::standard(fl!("close"))
+                .on_press(Message::ShowUnitFileChanges(false))
+        )
+        .into()
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::AppModel;
+use crate::fl;
+use crate::message::Message;
+use crate::systemd::{DependencyKind, DependencyNode, ServiceScope, SystemdService};
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget;
+use cosmic::Element;
+
+pub fn view_dependencies(app: &AppModel) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let Some(dependencies) = &app.dependencies else {
+        return widget::text(fl!("loading-dependencies")).into();
+    };
+
+    let services = match app.current_scope {
+        ServiceScope::System => &app.system_services,
+        ServiceScope::User => &app.user_services,
+    };
+
+    widget::column()
+        .push(dependency_section(fl!("requires"), &dependencies.requires, services))
+        .push(dependency_section(fl!("wants"), &dependencies.wants, services))
+        .push(dependency_section(fl!("after"), &dependencies.after, services))
+        .push(dependency_section(fl!("before"), &dependencies.before, services))
+        .push(dependency_tree(app, services))
+        .spacing(spacing.space_m)
+        .into()
+}
+
+/// The unit's dependencies of the chosen kind and theirs in turn, each level
+/// collapsed until expanded, like `systemctl list-dependencies` prints them.
+fn dependency_tree<'a>(app: &'a AppModel, services: &'a [SystemdService]) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut kinds = widget::row().spacing(spacing.space_xxs);
+    for kind in DependencyKind::ALL {
+        let label = match kind {
+            DependencyKind::Requirements => fl!("requirements"),
+            DependencyKind::After => fl!("after"),
+            DependencyKind::Before => fl!("before"),
+        };
+        let chip = if kind == app.dependency_kind {
+            widget::button::suggested(label)
+        } else {
+            widget::button::standard(label)
+        };
+        kinds = kinds.push(chip.on_press(Message::DependencyKindChanged(kind)));
+    }
+
+    let mut column = widget::column()
+        .push(widget::text::title4(fl!("dependency-tree")))
+        .push(kinds)
+        .spacing(spacing.space_xxs);
+
+    match &app.dependency_tree {
+        None => column = column.push(widget::text(fl!("loading-dependencies"))),
+        Some(Err(error)) => column = column.push(widget::text(error)),
+        Some(Ok(root)) if root.children.is_empty() => column = column.push(widget::text(fl!("none"))),
+        Some(Ok(root)) => {
+            for child in &root.children {
+                column = push_dependency_node(app, services, column, child, &root.name, 0);
+            }
+        }
+    }
+
+    column.into()
+}
+
+/// Adds the row of `node` and, if it's expanded, the rows of its children.
+/// Nodes are told apart by their path from the root, as a unit can appear in several places.
+fn push_dependency_node<'a>(
+    app: &'a AppModel,
+    services: &'a [SystemdService],
+    mut column: widget::Column<'a, Message>,
+    node: &'a DependencyNode,
+    parent_path: &str,
+    level: u16,
+) -> widget::Column<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let path = format!("{parent_path}/{}", node.name);
+    let expanded = app.expanded_dependencies.contains(&path);
+
+    let mut row = widget::row()
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_xxs)
+        .padding(cosmic::iced::Padding::from([0, 0, 0, level * spacing.space_m]));
+
+    // Leaves get an empty slot the size of the expander so names line up.
+    row = if node.children.is_empty() {
+        row.push(widget::horizontal_space().width(Length::Fixed(24.0)))
+    } else {
+        let icon_name = if expanded { "go-down-symbolic" } else { "go-next-symbolic" };
+        row.push(
+            widget::button::icon(widget::icon::from_name(icon_name))
+                .extra_small()
+                .on_press(Message::ToggleDependencyNode(path.clone()))
+        )
+    };
+
+    row = match services.iter().find(|s| s.name == node.name) {
+        Some(service) => row.push(
+            widget::button::link(node.name.clone())
+                .padding(0)
+                .on_press(Message::SelectService(service.clone())),
+        ),
+        None => row.push(widget::text(&node.name)),
+    };
+
+    if node.cycle {
+        row = row.push(widget::text::caption(fl!("dependency-cycle")));
+    }
+
+    column = column.push(row);
+
+    if expanded {
+        for child in &node.children {
+            column = push_dependency_node(app, services, column, child, &path, level + 1);
+        }
+    }
+
+    column
+}
+
+/// Lists the units of one dependency kind. Units that are in the loaded service
+/// list link to their detail page, everything else is plain text.
+fn dependency_section<'a>(
+    title: String,
+    units: &'a [String],
+    services: &'a [SystemdService],
+) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut column = widget::column()
+        .push(widget::text::title4(title))
+        .spacing(spacing.space_xxs);
+
+    if units.is_empty() {
+        return column.push(widget::text(fl!("none"))).into();
+    }
+
+    for unit in units {
+        column = match services.iter().find(|s| &s.name == unit) {
+            Some(service) => column.push(
+                widget::button::link(unit.clone())
+                    .padding(0)
+                    .on_press(Message::SelectService(service.clone())),
+            ),
+            None => column.push(widget::text(unit)),
+        };
+    }
+
+    column.into()
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::DropInEditor;
+use crate::fl;
+use crate::message::Message;
+use cosmic::iced::widget::text_editor;
+use cosmic::iced::Length;
+use cosmic::widget;
+use cosmic::Element;
+
+pub fn view_drop_in_editor(editor: &DropInEditor) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut control = widget::column()
+        .push(widget::text::caption(&editor.path))
+        .push(
+            text_editor(&editor.content)
+                .on_action(Message::DropInEdited)
+                .font(cosmic::font::mono())
+                .height(Length::Fixed(320.0))
+        )
+        .spacing(spacing.space_s);
+
+    if editor.confirm_overwrite {
+        control = control.push(widget::text(fl!("drop-in-overwrite-warning")));
+    }
+
+    if let Some(error) = &editor.error {
+        control = control.push(widget::text(error));
+    }
+
+    let save_label = if editor.confirm_overwrite { fl!("overwrite") } else { fl!("save") };
+    let save = if editor.confirm_overwrite {
+        widget::button::destructive(save_label)
+    } else {
+        widget::button::suggested(save_label)
+    };
+
+    widget::dialog()
+        .title(fl!("edit-drop-in-title", service = editor.service_name.as_str()))
+        .control(control)
+        .primary_action(save.on_press_maybe((!editor.saving).then_some(Message::SaveDropIn)))
+        .secondary_action(
+            widget::button::standard(fl!("cancel"))
+                .on_press(Message::CloseDropInEditor)
+        )
+        .into()
+}
@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Formatting helpers shared by the views.
+
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
+use std::time::Duration;
+
+/// Shown in place of values that don't apply to the service's current state.
+pub const NOT_AVAILABLE: &str = "—";
+
+/// Formats a byte count as MiB or GiB.
+pub fn format_bytes(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+
+    let bytes = bytes as f64;
+    if bytes >= GIB {
+        format!("{:.2} GiB", bytes / GIB)
+    } else {
+        format!("{:.1} MiB", bytes / MIB)
+    }
+}
+
+/// Formats a systemd timestamp (microseconds since the epoch) in local time.
+pub fn format_timestamp(usec: u64) -> String {
+    chrono::DateTime::from_timestamp_micros(usec as i64)
+        .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| NOT_AVAILABLE.to_string())
+}
+
+/// Formats an elapsed time compactly in its largest whole unit, e.g. "12s" or "3m".
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 60 * 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / (60 * 60))
+    }
+}
+
+/// Formats a duration in its two largest units, e.g. "2h 14m" or "3d 4h".
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// The name of a standard Linux signal, e.g. "SIGKILL" for 9.
+pub fn signal_name(signal: i32) -> Option<&'static str> {
+    const NAMES: [&str; 31] = [
+        "SIGHUP", "SIGINT", "SIGQUIT", "SIGILL", "SIGTRAP", "SIGABRT", "SIGBUS", "SIGFPE",
+        "SIGKILL", "SIGUSR1", "SIGSEGV", "SIGUSR2", "SIGPIPE", "SIGALRM", "SIGTERM", "SIGSTKFLT",
+        "SIGCHLD", "SIGCONT", "SIGSTOP", "SIGTSTP", "SIGTTIN", "SIGTTOU", "SIGURG", "SIGXCPU",
+        "SIGXFSZ", "SIGVTALRM", "SIGPROF", "SIGWINCH", "SIGIO", "SIGPWR", "SIGSYS",
+    ];
+    usize::try_from(signal).ok()?.checked_sub(1).and_then(|index| NAMES.get(index)).copied()
+}
+
+/// Where to open a `Documentation=` entry. Man pages, e.g. "man:systemd.exec(5)",
+/// open online since there may be no man viewer to hand them to, other URLs
+/// open as they are.
+pub fn documentation_url(entry: &str) -> String {
+    let Some(page) = entry.strip_prefix("man:") else {
+        return entry.to_string();
+    };
+
+    match page.strip_suffix(')').and_then(|page| page.split_once('(')) {
+        Some((name, section)) => format!("https://man.archlinux.org/man/{name}.{section}"),
+        None => format!("https://man.archlinux.org/man/{page}"),
+    }
+}
+
+/// Formats a systemd timestamp as an absolute local time followed by how long ago it was.
+pub fn format_since(usec: u64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let elapsed = now.saturating_sub(Duration::from_micros(usec));
+    format!("{} ({})", format_timestamp(usec), format_duration(elapsed))
+}
+
+/// Splits the leading timestamp off a line of journalctl's default output, e.g.
+/// "Oct 16 10:23:45 host unit[1]: …", returning it with the rest of the line.
+/// The output has no year, so the latest such date not well past `now` is assumed.
+/// Continuation lines of multi-line entries have no timestamp and give `None`.
+pub fn split_log_timestamp(line: &str, now: DateTime<Local>) -> Option<(DateTime<Local>, &str)> {
+    let timestamp = line.get(..15)?;
+
+    let parse = |year: i32| {
+        NaiveDateTime::parse_from_str(&format!("{year} {timestamp}"), "%Y %b %d %H:%M:%S")
+            .ok()
+            .and_then(|time| Local.from_local_datetime(&time).earliest())
+    };
+
+    let time = parse(now.year())?;
+    // Allow for entries written while `now` was being taken.
+    let time = if time > now + chrono::Duration::days(1) { parse(now.year() - 1)? } else { time };
+
+    Some((time, &line[15..]))
+}
+
+/// `text` cut to at most `max_chars` characters, ending in an ellipsis when it
+/// was cut, or `None` if it already fits.
+pub fn truncate(text: &str, max_chars: usize) -> Option<String> {
+    if text.chars().count() <= max_chars {
+        return None;
+    }
+
+    let kept: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    Some(format!("{}…", kept.trim_end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_log_timestamp() {
+        let now = Local.with_ymd_and_hms(2025, 1, 2, 12, 0, 0).unwrap();
+
+        let (time, rest) = split_log_timestamp("Jan 02 11:58:00 host foo[12]: started", now).unwrap();
+        assert_eq!(now - time, chrono::Duration::minutes(2));
+        assert_eq!(rest, " host foo[12]: started");
+
+        // A date later in the year than `now` is from the year before.
+        let (time, _) = split_log_timestamp("Dec 31 23:00:00 host foo[12]: stopping", now).unwrap();
+        assert_eq!(time.year(), 2024);
+
+        assert_eq!(split_log_timestamp("    at main.rs:12", now), None);
+        assert_eq!(split_log_timestamp("-- No entries --", now), None);
+    }
+
+    #[test]
+    fn test_signal_name() {
+        assert_eq!(signal_name(1), Some("SIGHUP"));
+        assert_eq!(signal_name(9), Some("SIGKILL"));
+        assert_eq!(signal_name(15), Some("SIGTERM"));
+        assert_eq!(signal_name(31), Some("SIGSYS"));
+        assert_eq!(signal_name(0), None);
+        assert_eq!(signal_name(-1), None);
+        assert_eq!(signal_name(34), None);
+    }
+
+    #[test]
+    fn test_documentation_url() {
+        assert_eq!(documentation_url("man:sshd(8)"), "https://man.archlinux.org/man/sshd.8");
+        assert_eq!(documentation_url("man:systemd.exec(5)"), "https://man.archlinux.org/man/systemd.exec.5");
+        assert_eq!(documentation_url("man:dbus-daemon"), "https://man.archlinux.org/man/dbus-daemon");
+        assert_eq!(documentation_url("https://www.freedesktop.org/wiki/Software/systemd"), "https://www.freedesktop.org/wiki/Software/systemd");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("OpenSSH server daemon", 40), None);
+        assert_eq!(truncate("OpenSSH server daemon", 21), None);
+        assert_eq!(truncate("OpenSSH server daemon", 15).as_deref(), Some("OpenSSH server…"));
+        // Cut by characters, not bytes.
+        assert_eq!(truncate("Сървър за отдалечен достъп", 7).as_deref(), Some("Сървър…"));
+    }
+}
@@ -1,7 +1,22 @@
 // SPDX-License-Identifier: MPL-2.0
 
+pub mod command_palette;
+pub mod confirm_dialog;
+pub mod dependencies;
+pub mod drop_in_editor;
+pub mod format;
 pub mod service_list;
 pub mod service_detail;
+pub mod settings;
+pub mod status_bar;
+pub mod transient_service;
 
-pub use service_list::view_services_list;
+pub use command_palette::view_command_palette;
+pub use confirm_dialog::{view_confirm_batch_dialog, view_confirm_dialog, view_confirm_shown_dialog, view_start_preview_dialog, view_unit_file_changes_dialog};
+pub use dependencies::view_dependencies;
+pub use drop_in_editor::view_drop_in_editor;
+pub use service_list::{reconnecting_banner, view_services_list};
 pub use service_detail::view_service_detail;
+pub use settings::view_settings;
+pub use status_bar::view_status_bar;
+pub use transient_service::view_transient_service;
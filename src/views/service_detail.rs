@@ -3,11 +3,15 @@
 use crate::app::AppModel;
 use crate::fl;
 use crate::message::Message;
-use crate::types::Page;
-use crate::systemd::SystemdService;
+use crate::types::{ContextPage, DetailTab, LogTimestampMode, Page};
+use crate::systemd::{parse_unit_name, Boot, CheckResult, KillSignal, LogPriority, MainExit, ServiceScope, SystemdService};
+use cosmic::iced::widget::text::Span;
+use cosmic::iced::widget::{rich_text, span, text_editor};
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget::{self, icon};
 use cosmic::Element;
+use super::format::{documentation_url, format_bytes, format_duration, format_elapsed, format_since, format_timestamp, signal_name, split_log_timestamp, NOT_AVAILABLE};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub fn view_service_detail<'a>(
     app: &'a AppModel,
@@ -18,19 +22,38 @@ pub fn view_service_detail<'a>(
     // Localized strings
     let all_system_services = fl!("all-system-services");
     let all_user_services = fl!("all-user-services");
+    let all_timers = fl!("all-timers");
+    let all_sockets = fl!("all-sockets");
+    let all_targets = fl!("all-targets");
     let description_label = fl!("description-label");
     let load_state_label = fl!("load-state-label");
     let enabled_label = fl!("enabled-label");
     let status_label = fl!("status-label");
     let unit_path_label = fl!("unit-path-label");
+    let main_pid_label = fl!("main-pid-label");
+    let memory_label = fl!("memory-label");
+    let cpu_label = fl!("cpu-label");
+    let command_label = fl!("command-label");
     let start_text = fl!("start");
     let stop_text = fl!("stop");
     let restart_text = fl!("restart");
-    let logs_text = fl!("logs");
+    let reload_text = fl!("reload");
+    let mask_text = fl!("mask");
+    let unmask_text = fl!("unmask");
+    let reset_failed_text = fl!("reset-failed");
+    let loading_unit_file_text = fl!("loading-unit-file");
+    let follow_logs_text = fl!("follow-logs");
+    let log_priority_text = fl!("log-priority");
+    let copy_logs_text = fl!("copy-logs");
+    let search_logs_text = fl!("search-logs");
+    let save_logs_text = fl!("save-logs");
 
     let previous_button_label = match app.nav.active_data::<Page>().unwrap() {
         Page::SystemServices => all_system_services,
         Page::UserServices => all_user_services,
+        Page::Timers => all_timers,
+        Page::Sockets => all_sockets,
+        Page::Targets => all_targets,
         _ => "Back".to_string(),
     };
 
@@ -52,13 +75,38 @@ pub fn view_service_detail<'a>(
 
     let service = service.unwrap();
 
-    let sub_page_header = widget::row::with_capacity(2).push(widget::text::title3(&service.name));
+    let (watch_icon, watch_tooltip) = if app.config.watched_units(app.current_scope).contains(&service.name) {
+        ("notification-alert-symbolic", fl!("stop-watching"))
+    } else {
+        ("notification-disabled-symbolic", fl!("watch"))
+    };
+
+    let copy_button = |text: &str, tooltip: String| {
+        widget::button::icon(icon::from_name("edit-copy-symbolic"))
+            .extra_small()
+            .tooltip(tooltip)
+            .on_press(Message::CopyText(text.to_string()))
+    };
+
+    let sub_page_header = widget::row::with_capacity(4)
+        .push(widget::text::title3(&service.name))
+        .push(copy_button(&service.name, fl!("copy-name")))
+        .push(widget::horizontal_space())
+        .push(
+            widget::button::icon(icon::from_name(watch_icon))
+                .tooltip(watch_tooltip)
+                .on_press(Message::ToggleWatch(service.name.clone()))
+        )
+        .push(
+            widget::button::standard(fl!("dependencies"))
+                .on_press(Message::ToggleContextPage(ContextPage::Dependencies))
+        )
+        .align_y(Alignment::Center);
 
     let header = widget::column::with_capacity(2)
         .push(previous_button)
         .push(sub_page_header)
-        .spacing(6)
-        .width(Length::Shrink);
+        .spacing(6);
 
     let description = widget::row()
         .push(widget::text(description_label).width(Length::Fixed(120.0)))
@@ -70,27 +118,59 @@ pub fn view_service_detail<'a>(
         .push(widget::text(&service.load_state))
         .spacing(spacing.space_s);
 
-    let is_enabled = service.unit_file_state == "enabled";
+    let is_enabled = matches!(service.unit_file_state.as_str(), "enabled" | "enabled-runtime");
+    let is_masked = service.unit_file_state == "masked";
     let can_toggle = service.unit_file_state == "enabled" || service.unit_file_state == "disabled";
     let service_name_for_toggle = service.name.clone();
-    
-    let enabled_toggler = if can_toggle {
-        widget::toggler(is_enabled)
-            .on_toggle(move |enabled| {
-                if enabled {
-                    Message::EnableService(service_name_for_toggle.clone())
-                } else {
-                    Message::DisableService(service_name_for_toggle.clone())
-                }
-            })
+
+    let mut enabled = widget::row()
+        .push(widget::text(enabled_label).width(Length::Fixed(120.0)));
+
+    // A masked unit is neither enabled nor disabled, so it gets no toggler at all.
+    // A static unit has no [Install] section, so it can't be toggled, but it still
+    // starts whenever another unit pulls it in.
+    if is_masked {
+        enabled = enabled.push(widget::text(fl!("masked")));
+    } else if service.unit_file_state == "static" {
+        enabled = enabled
+            .push(widget::toggler(true))
+            .push(widget::text::caption(fl!("static-note")));
     } else {
-        widget::toggler(is_enabled)
-    };
-    
-    let enabled = widget::row()
-        .push(widget::text(enabled_label).width(Length::Fixed(120.0)))
-        .push(enabled_toggler)
-        .push(widget::text(format!("({})", service.unit_file_state)).size(12))
+        let enabled_toggler = if can_toggle {
+            widget::toggler(is_enabled)
+                .on_toggle(move |enabled| {
+                    if enabled {
+                        Message::EnableService(service_name_for_toggle.clone())
+                    } else {
+                        Message::DisableService(service_name_for_toggle.clone())
+                    }
+                })
+        } else {
+            widget::toggler(is_enabled)
+        };
+
+        enabled = enabled.push(enabled_toggler);
+    }
+
+    enabled = enabled.push(widget::tooltip(
+        widget::text(format!("({})", service.unit_file_state)).size(12),
+        widget::text(unit_file_state_description(&service.unit_file_state)),
+        widget::tooltip::Position::Top,
+    ));
+
+    if service.unit_file_state == "enabled-runtime" {
+        enabled = enabled.push(widget::text::caption(fl!("enabled-runtime-note")));
+    }
+
+    // Enabling an instance doesn't enable its template or the template's other instances.
+    let unit_name = parse_unit_name(&service.name);
+    if let (Some(template), Some(instance)) = (unit_name.template(), unit_name.instance) {
+        if !instance.is_empty() && can_toggle {
+            enabled = enabled.push(widget::text::caption(fl!("instance-toggle-note", instance = instance, template = template)));
+        }
+    }
+
+    let enabled = enabled
         .align_y(Alignment::Center)
         .spacing(spacing.space_s);
 
@@ -102,50 +182,843 @@ pub fn view_service_detail<'a>(
     let unit_path = widget::row()
         .push(widget::text(unit_path_label).width(Length::Fixed(120.0)))
         .push(widget::text(&service.unit_path))
+        .push(copy_button(&service.unit_path, fl!("copy-path")))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s);
+
+    // Units without documentation leave the row out entirely.
+    let mut documentation = widget::row();
+    if !service.documentation.is_empty() {
+        let links = service.documentation.iter().fold(widget::column(), |links, entry| {
+            links.push(
+                widget::button::link(entry.clone())
+                    .padding(0)
+                    .on_press(Message::LaunchUrl(documentation_url(entry)))
+            )
+        });
+
+        documentation = documentation
+            .push(widget::text(fl!("documentation-label")).width(Length::Fixed(120.0)))
+            .push(links)
+            .spacing(spacing.space_s);
+    }
+
+    let is_running = service.active_state == "active";
+
+    let (since_label, since) = if is_running {
+        (fl!("running-since-label"), service.active_since)
+    } else {
+        (fl!("inactive-since-label"), service.inactive_since)
+    };
+
+    let since = widget::row()
+        .push(widget::text(since_label).width(Length::Fixed(120.0)))
+        .push(widget::text(since.map_or_else(|| NOT_AVAILABLE.to_string(), format_since)))
         .spacing(spacing.space_s);
 
+    // How the last run ended matters once the service stopped, e.g. a oneshot that failed.
+    let mut last_result = widget::column();
+    if !is_running {
+        if let Some(result) = &service.result {
+            let mut outcome = result_description(result);
+            if let Some(exit) = service.main_exit {
+                outcome = format!("{outcome} · {}", main_exit_description(exit));
+            }
+            last_result = last_result.push(
+                widget::row()
+                    .push(widget::text(fl!("result-label")).width(Length::Fixed(120.0)))
+                    .push(widget::text(outcome))
+                    .spacing(spacing.space_s)
+            );
+        }
+    }
+
+    let mut watchdog = widget::column();
+    let mut restarts = widget::column();
+    if service.name.ends_with(".service") {
+        watchdog = watchdog.push(watchdog_row(service));
+        restarts = restarts.push(restarts_row(service, app.restart_loop));
+    }
+
+    let mut frozen = widget::row()
+        .push(widget::text(fl!("frozen-label")).width(Length::Fixed(120.0)));
+
+    match service.freezer_state.as_deref() {
+        Some(freezer_state) => {
+            let is_frozen = matches!(freezer_state, "frozen" | "freezing");
+            let service_name_for_freeze = service.name.clone();
+
+            // Freezing an inactive unit fails, and a transition can't be interrupted.
+            let mut frozen_toggler = widget::toggler(is_frozen);
+            if is_running && matches!(freezer_state, "running" | "frozen") {
+                frozen_toggler = frozen_toggler.on_toggle(move |freeze| Message::FreezeService(service_name_for_freeze.clone(), freeze));
+            }
+
+            frozen = frozen
+                .push(frozen_toggler)
+                .push(widget::text(format!("({freezer_state})")).size(12));
+        }
+        None => frozen = frozen.push(widget::text(NOT_AVAILABLE)),
+    }
+
+    let frozen = frozen
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s);
+
+    let main_pid = widget::row()
+        .push(widget::text(main_pid_label).width(Length::Fixed(120.0)))
+        .push(widget::text(
+            service.main_pid
+                .filter(|_| is_running)
+                .map_or_else(|| NOT_AVAILABLE.to_string(), |pid| pid.to_string())
+        ))
+        .spacing(spacing.space_s);
+
+    let memory = widget::row()
+        .push(widget::text(memory_label).width(Length::Fixed(120.0)))
+        .push(widget::text(
+            service.memory_current
+                .filter(|_| is_running)
+                .map_or_else(|| NOT_AVAILABLE.to_string(), format_bytes)
+        ))
+        .spacing(spacing.space_s);
+
+    let cpu = widget::row()
+        .push(widget::text(cpu_label).width(Length::Fixed(120.0)))
+        .push(widget::text(
+            app.cpu_percent
+                .filter(|_| is_running)
+                .map_or_else(|| NOT_AVAILABLE.to_string(), |percent| format!("{:.1}%", percent))
+        ))
+        .spacing(spacing.space_s);
+
+    let control_group = widget::row()
+        .push(widget::text(fl!("cgroup-label")).width(Length::Fixed(120.0)))
+        .push(widget::text(service.control_group.as_deref().unwrap_or(NOT_AVAILABLE)).font(cosmic::font::mono()))
+        .spacing(spacing.space_s);
+
+    let mut processes = widget::column().spacing(spacing.space_xxs);
+
+    if !service.processes.is_empty() {
+        let toggle_icon = if app.show_processes { "pan-down-symbolic" } else { "pan-end-symbolic" };
+        processes = processes.push(
+            widget::button::text(fl!("processes", count = service.processes.len()))
+                .leading_icon(icon::from_name(toggle_icon))
+                .on_press(Message::ToggleProcesses)
+        );
+
+        if app.show_processes {
+            for process in &service.processes {
+                processes = processes.push(
+                    widget::row()
+                        .push(widget::text(process.pid.to_string()).font(cosmic::font::mono()).width(Length::Fixed(80.0)))
+                        .push(widget::text(&process.command).font(cosmic::font::mono()))
+                        .spacing(spacing.space_s)
+                );
+            }
+        }
+    }
+
+    let command = widget::row()
+        .push(widget::text(command_label).width(Length::Fixed(120.0)))
+        .push(widget::text(service.exec_start.as_deref().unwrap_or(NOT_AVAILABLE)).font(cosmic::font::mono()))
+        .spacing(spacing.space_s);
+
+    let mut timer_rows = widget::column().spacing(spacing.space_s);
+
+    if service.name.ends_with(".timer") {
+        timer_rows = timer_rows
+            .push(
+                widget::row()
+                    .push(widget::text(fl!("next-run-label")).width(Length::Fixed(120.0)))
+                    .push(widget::text(service.next_elapse.map_or_else(|| NOT_AVAILABLE.to_string(), format_timestamp)))
+                    .spacing(spacing.space_s)
+            )
+            .push(
+                widget::row()
+                    .push(widget::text(fl!("last-run-label")).width(Length::Fixed(120.0)))
+                    .push(widget::text(service.last_trigger.map_or_else(|| NOT_AVAILABLE.to_string(), format_timestamp)))
+                    .spacing(spacing.space_s)
+            );
+    }
+
+    // What a target is part of, like `multi-user.target` being wanted by
+    // `graphical.target`.
+    let mut target_rows = widget::column();
+
+    if service.name.ends_with(".target") {
+        target_rows = target_rows.push(
+            widget::row()
+                .push(widget::text(fl!("wanted-by-label")).width(Length::Fixed(120.0)))
+                .push(widget::text(if service.wanted_by.is_empty() {
+                    NOT_AVAILABLE.to_string()
+                } else {
+                    service.wanted_by.join("\n")
+                }))
+                .spacing(spacing.space_s)
+        );
+    }
+
+    let mut socket_rows = widget::column().spacing(spacing.space_s);
+
+    let listen = widget::row()
+        .push(widget::text(fl!("listen-label")).width(Length::Fixed(120.0)))
+        .push(widget::text(if service.listen.is_empty() {
+            NOT_AVAILABLE.to_string()
+        } else {
+            service.listen.join("\n")
+        }))
+        .spacing(spacing.space_s);
+
+    if service.name.ends_with(".service") {
+        socket_rows = socket_rows.push(listen);
+    } else if service.name.ends_with(".socket") {
+        // The activated service links to its details when it has been loaded.
+        let mut activates = widget::column();
+        for unit in &service.triggers {
+            activates = match app.system_services.iter().find(|s| &s.name == unit) {
+                Some(triggered) => activates.push(
+                    widget::button::link(unit.clone())
+                        .padding(0)
+                        .on_press(Message::SelectService(triggered.clone()))
+                ),
+                None => activates.push(widget::text(unit)),
+            };
+        }
+
+        socket_rows = socket_rows
+            .push(listen)
+            .push(
+                widget::row()
+                    .push(widget::text(fl!("activates-label")).width(Length::Fixed(120.0)))
+                    .push(activates)
+                    .spacing(spacing.space_s)
+            );
+    }
+
     let info_section = widget::column()
         .push(description)
         .push(enabled)
         .push(status)
+        .push(since)
+        .push(last_result)
+        .push(restarts)
+        .push(watchdog)
+        .push(check_row(fl!("conditions-label"), service.condition_result.as_ref()))
+        .push(check_row(fl!("asserts-label"), service.assert_result.as_ref()))
+        .push(frozen)
         .push(load_state)
         .push(unit_path)
+        .push(documentation)
+        .push(timer_rows)
+        .push(socket_rows)
+        .push(target_rows)
+        .push(command)
+        .push(main_pid)
+        .push(memory)
+        .push(cpu)
+        .push(control_group)
+        .push(processes)
         .spacing(spacing.space_s);
 
     let service_name = service.name.clone();
     let service_name2 = service.name.clone();
     let service_name3 = service.name.clone();
+    let service_name4 = service.name.clone();
 
-    let controls;
+    let mut controls;
+
+    // Start/stop/restart calls fail on a masked unit, so the buttons are disabled.
+    // A running unit is reloaded if it can be rather than restarted by default.
+    // Targets have no processes, they're only started, stopped or isolated.
+    if service.name.ends_with(".target") {
+        controls = if service.active_state == "active" {
+            widget::row().push(widget::button::standard(stop_text.clone()).on_press_maybe((!is_masked).then(|| Message::StopService(service_name2))))
+        } else {
+            widget::row().push(widget::button::standard(start_text).on_press_maybe((!is_masked).then(|| Message::StartService(service_name))))
+        }
+        .spacing(spacing.space_s);
 
-    if service.sub_state == "running" {
+        if service.can_isolate {
+            controls = controls.push(
+                widget::button::destructive(fl!("isolate"))
+                    .on_press_maybe((!is_masked).then(|| Message::IsolateTarget(service.name.clone())))
+            );
+        }
+    }
+    else if service.sub_state == "running" {
         controls = widget::row()
-            .push(widget::button::standard(stop_text.clone()).on_press(Message::StopService(service_name2)))
-            .push(widget::button::standard(restart_text.clone()).on_press(Message::RestartService(service_name3)))
+            .push(widget::button::suggested(fl!("reload-or-restart")).on_press_maybe((!is_masked).then(|| Message::ReloadOrRestartService(service.name.clone()))))
+            .push(widget::button::standard(stop_text.clone()).on_press_maybe((!is_masked).then(|| Message::StopService(service_name2))))
+            .push(widget::button::standard(restart_text.clone()).on_press_maybe((!is_masked).then(|| Message::RestartService(service_name3))))
             .spacing(spacing.space_s);
     }
     else {
         controls = widget::row()
-            .push(widget::button::standard(start_text).on_press(Message::StartService(service_name)))
-            .push(widget::button::standard(restart_text).on_press(Message::RestartService(service_name3)))
+            .push(widget::button::standard(start_text).on_press_maybe((!is_masked).then(|| Message::StartService(service_name))))
+            .push(widget::button::standard(restart_text).on_press_maybe((!is_masked).then(|| Message::RestartService(service_name3))))
             .spacing(spacing.space_s);
+
+        // Targets always show what they'd start, other units only when asked.
+        if !service.name.ends_with(".target") {
+            controls = controls.push(
+                widget::button::standard(fl!("preview-start"))
+                    .on_press_maybe((!is_masked).then(|| Message::PreviewStart(service.name.clone())))
+            );
+        }
     }
 
-    let logs = widget::container(
-        widget::text(&app.service_logs)
-            .size(12)
+    // A hung unit is often stuck activating or deactivating, so it can be killed in
+    // any state that may still have processes.
+    let may_have_processes = !matches!(service.active_state.as_str(), "inactive" | "failed");
+    if may_have_processes && !service.name.ends_with(".target") {
+        let kill_button = widget::button::standard(fl!("kill")).on_press(Message::ToggleKillMenu);
+        let mut kill = widget::popover(kill_button).on_close(Message::ToggleKillMenu);
+
+        if app.kill_menu_open {
+            let signals = KillSignal::ALL.iter().fold(widget::column(), |column, signal| {
+                column.push(
+                    widget::button::text(signal.name())
+                        .width(Length::Fill)
+                        .on_press(Message::KillService(service.name.clone(), *signal))
+                )
+            });
+            kill = kill.popup(widget::container(signals.width(Length::Fixed(140.0))).class(cosmic::style::Container::Dropdown));
+        }
+
+        controls = controls.push(kill);
+    }
+
+    if service.can_reload {
+        controls = controls.push(
+            widget::button::standard(reload_text)
+                .on_press_maybe((!is_masked && service.active_state == "active").then(|| Message::ReloadService(service.name.clone())))
+        );
+    }
+
+    if service.active_state == "failed" {
+        controls = controls.push(
+            widget::button::standard(reset_failed_text)
+                .on_press(Message::ResetFailedService(service.name.clone()))
+        );
+    }
+
+    controls = controls.push(
+        widget::button::standard(fl!("edit-drop-in"))
+            .on_press(Message::EditDropIn(service.name.clone()))
+    );
+
+    // Transient units and the like have no file to show.
+    controls = controls.push(
+        widget::button::standard(fl!("open-file-location"))
+            .on_press_maybe((!service.fragment_path.is_empty()).then(|| Message::OpenUnitFileLocation(service.fragment_path.clone())))
     );
 
-    let scrollable_logs = widget::scrollable(logs)
+    if app.current_scope == ServiceScope::System && service.name.ends_with(".service") {
+        controls = controls.push(
+            widget::button::standard(fl!("copy-to-user"))
+                .on_press(Message::CopyToUserScope(service.name.clone()))
+        );
+    }
+
+    if is_masked {
+        controls = controls.push(widget::button::standard(unmask_text).on_press(Message::UnmaskService(service_name4)));
+    } else {
+        controls = controls.push(widget::button::destructive(mask_text).on_press(Message::MaskService(service_name4)));
+    }
+
+    let logs_text = displayed_logs(app);
+    let matches = log_matches(logs_text, &app.log_search);
+
+    let logs: Element<'a, Message> = if matches.is_empty() {
+        widget::text(logs_text)
+            .size(12)
+            .into()
+    } else {
+        highlighted_logs(logs_text, app.log_search.len(), &matches, app.log_match % matches.len())
+    };
+
+    // The latest entries are loaded first, older ones a page at a time from the top.
+    let logs: Element<'a, Message> = if app.log_cursor.is_some() && !app.logs_at_start {
+        let load_older = if app.loading_older_logs {
+            widget::button::text(fl!("loading-older-logs"))
+        } else {
+            widget::button::text(fl!("load-older-logs")).on_press(Message::LoadOlderLogs)
+        };
+        widget::column().push(load_older).push(logs).into()
+    } else if app.log_cursor.is_some() && app.log_range.is_empty() {
+        widget::column()
+            .push(widget::text::caption(fl!("logs-start")))
+            .push(logs)
+            .into()
+    } else {
+        logs
+    };
+
+    let mut scrollable_logs = widget::scrollable(widget::container(logs))
+        .id(logs_scrollable_id())
         .width(Length::Fill)
         .height(Length::Fill);
 
-    widget::column()
-        .push(header)
+    // Anchoring to the bottom keeps the newest lines in view while following.
+    if app.follow_logs {
+        scrollable_logs = scrollable_logs.anchor_bottom();
+    }
+
+    let mut column = widget::column().push(header);
+
+    // A unit that failed to load can't do anything until its file is fixed,
+    // so the reason goes right below the name.
+    if service.failed_to_load() {
+        let error = service.load_error.clone().unwrap_or_else(|| fl!("load-failed", state = service.load_state.as_str()));
+        column = column.push(widget::warning(error));
+    }
+
+    if service.need_daemon_reload {
+        column = column.push(daemon_reload_banner());
+    }
+
+    column = column
         .push(info_section)
         .push(controls)
-        .push(widget::text::title4(logs_text))
+        .push(
+            text_editor(&app.note)
+                .placeholder(fl!("note-placeholder"))
+                .on_action(Message::NoteEdited)
+                .height(Length::Fixed(64.0))
+        );
+
+    if let Some(error) = &app.action_error {
+        column = column.push(
+            widget::warning(error.clone())
+                .on_close(Message::DismissActionError)
+        );
+    }
+
+    let tabs = widget::container(
+        widget::tab_bar::horizontal(&app.detail_tabs)
+            .on_activate(Message::DetailTabSelected)
+            .width(Length::Shrink)
+    )
+    .width(Length::Fill);
+
+    let show_unit_file = app.detail_tabs.active_data::<DetailTab>() == Some(&DetailTab::UnitFile);
+
+    if show_unit_file {
+        let unit_file = widget::scrollable(
+            widget::text(app.unit_file.clone().unwrap_or(loading_unit_file_text))
+                .font(cosmic::font::mono())
+                .size(12)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill);
+
+        return column
+            .push(tabs)
+            .push(unit_file)
+            .spacing(spacing.space_m)
+            .into();
+    }
+
+    let collapse_logs = if app.config.logs_collapsed {
+        widget::button::icon(icon::from_name("pan-end-symbolic"))
+            .tooltip(fl!("expand-logs"))
+            .on_press(Message::CollapseLogs(false))
+    } else {
+        widget::button::icon(icon::from_name("pan-down-symbolic"))
+            .tooltip(fl!("collapse-logs"))
+            .on_press(Message::CollapseLogs(true))
+    };
+
+    // Collapsed, the logs are just their header, which expands them again.
+    if app.config.logs_collapsed {
+        return column
+            .push(
+                widget::row()
+                    .push(collapse_logs)
+                    .push(tabs)
+                    .align_y(Alignment::Center)
+                    .spacing(spacing.space_s)
+            )
+            .spacing(spacing.space_m)
+            .into();
+    }
+
+    let logs_header = widget::row()
+        .push(collapse_logs)
+        .push(tabs)
+        .push(widget::text(log_priority_text))
+        .push(widget::dropdown(
+            &LogPriority::NAMES,
+            LogPriority::ALL.iter().position(|p| *p == app.log_priority),
+            |index| Message::LogPriorityChanged(LogPriority::ALL[index]),
+        ))
+        .push(widget::button::standard(copy_logs_text).on_press(Message::CopyLogs))
+        .push(widget::button::standard(save_logs_text).on_press(Message::SaveLogs))
+        .push(
+            widget::button::icon(widget::icon::from_name("utilities-terminal-symbolic"))
+                .tooltip(fl!("open-in-terminal"))
+                .on_press(Message::OpenLogsInTerminal)
+        )
+        .push(widget::text(fl!("relative-times")))
+        .push(
+            widget::toggler(app.log_timestamp_mode == LogTimestampMode::Relative)
+                .on_toggle(|relative| Message::LogTimestampModeChanged(if relative {
+                    LogTimestampMode::Relative
+                } else {
+                    LogTimestampMode::Absolute
+                }))
+        )
+        .push(widget::text(follow_logs_text))
+        .push(widget::toggler(app.follow_logs).on_toggle(Message::FollowLogs))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s);
+
+    let match_count_text = if matches.is_empty() {
+        String::new()
+    } else {
+        fl!("match-count", current = app.log_match % matches.len() + 1, total = matches.len())
+    };
+
+    let log_search = widget::row()
+        .push(
+            widget::text_input(search_logs_text, &app.log_search)
+                .on_input(Message::LogSearchChanged)
+                .on_submit(|_| Message::NextLogMatch)
+                .width(Length::Fill)
+        )
+        .push(widget::text(match_count_text))
+        .push(
+            widget::button::icon(icon::from_name("go-up-symbolic"))
+                .on_press_maybe((!matches.is_empty()).then_some(Message::PreviousLogMatch))
+        )
+        .push(
+            widget::button::icon(icon::from_name("go-down-symbolic"))
+                .on_press_maybe((!matches.is_empty()).then_some(Message::NextLogMatch))
+        )
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s);
+
+    let boot_index = app.log_range.boot.as_ref().and_then(|id| {
+        app.boots.iter().flatten().position(|boot| &boot.id == id).map(|index| index + 1)
+    });
+
+    let mut log_range = widget::row()
+        .push(widget::text(fl!("boot-label")))
+        .push(widget::dropdown(&app.boot_labels, boot_index.or(Some(0)), Message::SelectBoot))
+        .push(
+            widget::button::icon(icon::from_name("view-refresh-symbolic"))
+                .tooltip(fl!("refresh-boots"))
+                .on_press(Message::RefreshBoots)
+        )
+        .push(widget::text(fl!("since-label")))
+        .push(
+            widget::text_input(fl!("since-placeholder"), &app.log_since)
+                .on_input(Message::LogSinceChanged)
+                .on_submit(|_| Message::ApplyLogRange)
+                .width(Length::Fixed(160.0))
+        )
+        .push(widget::text(fl!("until-label")))
+        .push(
+            widget::text_input(fl!("until-placeholder"), &app.log_until)
+                .on_input(Message::LogUntilChanged)
+                .on_submit(|_| Message::ApplyLogRange)
+                .width(Length::Fixed(160.0))
+        )
+        .push(widget::button::standard(fl!("apply")).on_press(Message::ApplyLogRange))
+        .push(
+            widget::button::standard(fl!("clear"))
+                .on_press_maybe((app.log_range.since.is_some() || app.log_range.until.is_some() || !app.log_since.is_empty() || !app.log_until.is_empty()).then_some(Message::ClearLogRange))
+        )
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s);
+
+    if !app.log_range.is_empty() {
+        log_range = log_range.push(
+            widget::checkbox(fl!("limit-log-lines", lines = app.log_lines), app.log_range_limited)
+                .on_toggle(Message::LimitLogRange)
+        );
+    }
+
+    // Only offered when there's a socket, timer or the like to include.
+    let related_units = service.log_units(true);
+    if related_units.len() > 1 {
+        log_range = log_range.push(widget::tooltip(
+            widget::checkbox(fl!("include-related-units"), app.include_related_logs)
+                .on_toggle(Message::IncludeRelatedLogs),
+            widget::text(related_units[1..].join(", ")),
+            widget::tooltip::Position::Top,
+        ));
+    }
+
+    column = column
+        .push(logs_header)
+        .push(log_search)
+        .push(log_range);
+
+    if let Some(error) = &app.log_range_error {
+        column = column.push(widget::text::caption(error.clone()));
+    }
+
+    column
         .push(scrollable_logs)
         .spacing(spacing.space_m)
         .into()
 }
+
+pub(crate) fn logs_scrollable_id() -> widget::Id {
+    widget::Id::new("service-logs")
+}
+
+/// The boot selector's options: every boot's entries, then each of `boots`.
+pub(crate) fn boot_labels(boots: &[Boot]) -> Vec<String> {
+    std::iter::once(fl!("all-boots"))
+        .chain(boots.iter().map(|boot| {
+            let started = format_timestamp(boot.started);
+            match boot.offset {
+                0 => fl!("current-boot", started = started),
+                offset => fl!("previous-boot", offset = offset, started = started),
+            }
+        }))
+        .collect()
+}
+
+/// A service's `Result` in words, e.g. "timeout" as "Timed out".
+fn result_description(result: &str) -> String {
+    match result {
+        "success" => fl!("result-success"),
+        "exit-code" => fl!("result-exit-code"),
+        "signal" => fl!("result-signal"),
+        "core-dump" => fl!("result-core-dump"),
+        "timeout" => fl!("result-timeout"),
+        "watchdog" => fl!("result-watchdog"),
+        "start-limit-hit" => fl!("result-start-limit-hit"),
+        "resources" => fl!("result-resources"),
+        "oom-kill" => fl!("result-oom-kill"),
+        "protocol" => fl!("result-protocol"),
+        other => other.to_string(),
+    }
+}
+
+fn main_exit_description(exit: MainExit) -> String {
+    let signal = |signal: i32| signal_name(signal).map_or_else(|| signal.to_string(), str::to_string);
+
+    match exit {
+        MainExit::Exited(status) => fl!("exited-with-status", status = status),
+        MainExit::Killed(number) => fl!("killed-by-signal", signal = signal(number)),
+        MainExit::Dumped(number) => fl!("dumped-core", signal = signal(number)),
+    }
+}
+
+/// Whether a running service pinged its watchdog in time, marked by a green or
+/// red dot, with how often it has to and when it last did.
+fn watchdog_row<'a>(service: &SystemdService) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut value = widget::row().align_y(Alignment::Center).spacing(spacing.space_xxs);
+
+    match service.watchdog {
+        None => value = value.push(widget::text(fl!("watchdog-not-configured"))),
+        Some(watchdog) => {
+            let mut details = vec![fl!("watchdog-interval", interval = format_duration(Duration::from_micros(watchdog.interval_usec)))];
+            if let Some(ping) = watchdog.last_ping {
+                details.push(fl!("watchdog-last-ping", time = format_since(ping)));
+            }
+
+            // A stopped service isn't expected to ping.
+            if service.active_state == "active" {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_micros() as u64;
+                let (healthy, class) = if watchdog.is_healthy(now) {
+                    (fl!("watchdog-healthy"), cosmic::theme::Text::Custom(|theme| widget::text::Style {
+                        color: Some(theme.cosmic().success_color().into()),
+                    }))
+                } else {
+                    (fl!("watchdog-late"), cosmic::theme::Text::Custom(|theme| widget::text::Style {
+                        color: Some(theme.cosmic().destructive_color().into()),
+                    }))
+                };
+                value = value.push(widget::text("●").class(class));
+                details.insert(0, healthy);
+            }
+
+            value = value.push(widget::text(details.join(" · ")));
+        }
+    }
+
+    widget::row()
+        .push(widget::text(fl!("watchdog-label")).width(Length::Fixed(120.0)))
+        .push(value)
+        .spacing(spacing.space_s)
+        .into()
+}
+
+/// How often systemd restarted the service. A count that climbed while shown or
+/// is high stands out in red, with how the last run ended, as it's likely crash looping.
+fn restarts_row<'a>(service: &SystemdService, restart_loop: bool) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut value = widget::row().align_y(Alignment::Center).spacing(spacing.space_xxs);
+
+    match service.n_restarts {
+        None => value = value.push(widget::text(NOT_AVAILABLE)),
+        Some(restarts) if restart_loop => {
+            let mut details = fl!("restart-loop");
+            if let Some(result) = &service.result {
+                details = format!("{details} · {}", result_description(result));
+            }
+            value = value
+                .push(widget::text::heading(restarts.to_string()).class(cosmic::theme::Text::Custom(|theme| {
+                    widget::text::Style {
+                        color: Some(theme.cosmic().destructive_color().into()),
+                    }
+                })))
+                .push(widget::text(details));
+        }
+        Some(restarts) => value = value.push(widget::text(restarts.to_string())),
+    }
+
+    widget::row()
+        .push(widget::text(fl!("restarts-label")).width(Length::Fixed(120.0)))
+        .push(value)
+        .spacing(spacing.space_s)
+        .into()
+}
+
+/// Whether the unit's conditions or asserts held on its last start, with the ones
+/// that didn't below, which explain why a started unit may have done nothing.
+fn check_row<'a>(label: String, result: Option<&'a CheckResult>) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut value = widget::column().spacing(spacing.space_xxs);
+
+    match result {
+        None => value = value.push(widget::text(fl!("not-checked"))),
+        Some(result) => {
+            let outcome = if result.passed { fl!("check-passed") } else { fl!("check-failed") };
+            value = value.push(widget::text(format!("{outcome} · {}", format_since(result.checked_at))));
+
+            if !result.passed {
+                for check in &result.failed {
+                    value = value.push(widget::text(check).font(cosmic::font::mono()));
+                }
+            }
+        }
+    }
+
+    widget::row()
+        .push(widget::text(label).width(Length::Fixed(120.0)))
+        .push(value)
+        .spacing(spacing.space_s)
+        .into()
+}
+
+/// What a `UnitFileState` value means for whether the unit starts on boot.
+fn unit_file_state_description(state: &str) -> String {
+    match state {
+        "enabled" => fl!("unit-file-state-enabled"),
+        "enabled-runtime" => fl!("unit-file-state-enabled-runtime"),
+        "linked" | "linked-runtime" => fl!("unit-file-state-linked"),
+        "alias" => fl!("unit-file-state-alias"),
+        "masked" | "masked-runtime" => fl!("unit-file-state-masked"),
+        "static" => fl!("unit-file-state-static"),
+        "indirect" => fl!("unit-file-state-indirect"),
+        "disabled" => fl!("unit-file-state-disabled"),
+        "generated" => fl!("unit-file-state-generated"),
+        "transient" => fl!("unit-file-state-transient"),
+        _ => fl!("unit-file-state-unknown"),
+    }
+}
+
+/// The selected service's logs as shown, with each timestamp made relative if
+/// `log_timestamp_mode` asks for it, see `relative_logs`.
+pub(crate) fn displayed_logs(app: &AppModel) -> &str {
+    match app.log_timestamp_mode {
+        LogTimestampMode::Absolute => &app.service_logs,
+        LogTimestampMode::Relative => &app.relative_logs,
+    }
+}
+
+/// `service_logs` with each line's timestamp made relative to now. Lines without one,
+/// like the rest of a multi-line entry, are left as they are.
+pub(crate) fn relative_logs(service_logs: &str) -> String {
+    let now = chrono::Local::now();
+    let mut logs = String::with_capacity(service_logs.len());
+
+    for line in service_logs.lines() {
+        match split_log_timestamp(line, now) {
+            Some((time, rest)) => {
+                let elapsed = (now - time).to_std().unwrap_or_default();
+                logs.push_str(&fl!("time-ago", elapsed = format_elapsed(elapsed)));
+                logs.push_str(rest);
+            }
+            None => logs.push_str(line),
+        }
+        logs.push('\n');
+    }
+
+    logs
+}
+
+/// Byte offsets of the case-insensitive occurrences of `query` in `logs`.
+pub(crate) fn log_matches(logs: &str, query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    // ASCII lowercasing keeps byte offsets valid in the original text.
+    let haystack = logs.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack.match_indices(&needle).map(|(start, _)| start).collect()
+}
+
+/// The logs with every match highlighted in the theme's accent color, and the
+/// current one additionally given a background.
+fn highlighted_logs<'a>(logs: &'a str, len: usize, matches: &[usize], current: usize) -> Element<'a, Message> {
+    let accent: cosmic::iced::Color = cosmic::theme::active().cosmic().accent_color().into();
+
+    let mut spans: Vec<Span<'a, (), cosmic::iced::Font>> = Vec::new();
+    let mut end = 0;
+
+    for (index, &start) in matches.iter().enumerate() {
+        if start < end {
+            continue;
+        }
+
+        spans.push(span(&logs[end..start]));
+
+        let highlight = span(&logs[start..start + len]).color(accent);
+        spans.push(if index == current {
+            highlight.color(cosmic::iced::Color::WHITE).background(accent)
+        } else {
+            highlight
+        });
+
+        end = start + len;
+    }
+
+    spans.push(span(&logs[end..]));
+
+    rich_text(spans).size(12).into()
+}
+
+/// Tells that the unit's files changed on disk since systemd loaded them, which
+/// edits only take effect after, and offers to reload them.
+fn daemon_reload_banner<'a>() -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    widget::container(
+        widget::row()
+            .push(widget::icon::from_name("dialog-warning-symbolic").size(16))
+            .push(widget::text::heading(fl!("needs-daemon-reload")).width(Length::Fill))
+            .push(
+                widget::button::text(fl!("reload-unit-files"))
+                    .on_press(Message::DaemonReload)
+            )
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_s)
+    )
+    .padding(spacing.space_s)
+    .class(cosmic::theme::Container::Card)
+    .width(Length::Fill)
+    .into()
+}
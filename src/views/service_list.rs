@@ -2,116 +2,1063 @@
 
 use crate::app::AppModel;
 use crate::fl;
+use crate::loading::UnitList;
 use crate::message::Message;
-use crate::systemd::SystemdService;
+use crate::paging::{group_services, page_items, page_of};
+use crate::search::Query;
+use crate::systemd::{parse_unit_name, ServiceAction, ServiceScope, SystemdService};
+use crate::types::{FileStateFilter, ListColumn, ListDensity, Page, SortColumn, StateFilter};
+use super::format::{format_elapsed, format_timestamp, truncate, NOT_AVAILABLE};
+use cosmic::iced::widget::text::Span;
+use cosmic::iced::widget::{rich_text, span};
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget;
 use cosmic::Element;
-use cosmic::iced::mouse::Interaction;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+/// The width of the start/stop and restart buttons at the end of every row,
+/// kept free in the header so the columns line up.
+const ROW_ACTIONS_WIDTH: f32 = 56.0;
+
+/// Below this width the list's columns get too cramped, and its rows turn into cards.
+const NARROW_WIDTH: f32 = 640.0;
+
+/// Descriptions longer than this are cut short, with the rest shown on hover.
+const MAX_DESCRIPTION_CHARS: usize = 60;
 
 pub fn view_services_list<'a>(
     app: &'a AppModel,
     services: &'a [SystemdService],
+    list: UnitList,
     title: String,
 ) -> Element<'a, Message> {
     let spacing = cosmic::theme::spacing();
+    let is_loading = app.loading.is_loading(list);
     
     // Localized strings
     let search_placeholder = fl!("search-placeholder");
 
-    let search_input = widget::text_input(search_placeholder, &app.search_filter)
+    let search_input = widget::text_input(search_placeholder, &app.search.input)
         .on_input(Message::SearchFilterChanged)
+        .on_submit(|_| Message::SubmitSearch)
+        .on_focus(Message::OpenSearchHistory)
         .width(Length::Fill);
+    let search_input = search_history(app, search_input);
+
+    let refresh_button: Element<'a, Message> = if is_loading {
+        widget::text::caption(fl!("refreshing")).into()
+    } else {
+        widget::button::icon(widget::icon::from_name("view-refresh-symbolic"))
+            .tooltip(fl!("refresh"))
+            .on_press(Message::RefreshServices)
+            .into()
+    };
+
+    let mut header = widget::row().push(widget::text::title3(title));
+
+    // Timers and sockets aren't tied to a scope like the services pages are.
+    if matches!(list, UnitList::Timers | UnitList::Sockets) {
+        header = header.push(scope_chips(app));
+    }
 
-    let header = widget::row()
-        .push(widget::text::title3(title))
+    let mut header = header
+        .push(state_summary(services))
         .push(search_input)
+        .push(
+            widget::tooltip(
+                widget::row()
+                    .push(widget::text(fl!("hide-vendor-units")))
+                    .push(widget::toggler(app.hide_vendor_units).on_toggle(Message::HideVendorUnits))
+                    .align_y(Alignment::Center)
+                    .spacing(spacing.space_s),
+                widget::text(fl!("hide-vendor-units-tooltip")),
+                widget::tooltip::Position::Bottom,
+            )
+        )
+        .push(
+            widget::row()
+                .push(widget::text(fl!("group-services")))
+                .push(widget::toggler(app.group_services).on_toggle(Message::GroupServices))
+                .align_y(Alignment::Center)
+                .spacing(spacing.space_s)
+        )
         .spacing(spacing.space_l)
         .align_y(Alignment::Center);
 
+    if let Some(instant) = app.last_refreshed {
+        header = header.push(widget::text::caption(fl!(
+            "updated-ago",
+            elapsed = format_elapsed(instant.elapsed())
+        )));
+    }
 
-    let filtered_services: Vec<&SystemdService> = if app.search_filter.is_empty() {
-        services.iter().collect()
-    } else {
-        let filter_lower = app.search_filter.to_lowercase();
-        services
-            .iter()
-            .filter(|s| {
-                s.name.to_lowercase().contains(&filter_lower)
-                    || s.description.to_lowercase().contains(&filter_lower)
-            })
-            .collect()
-    };
+    let header = header.push(refresh_button);
+
+    // The table turns into a column of cards when it's too narrow for its columns.
+    let table = widget::responsive(move |size| {
+        services_table(app, services, is_loading, size.width < NARROW_WIDTH)
+    });
+
+    let mut content = widget::column()
+        .push(header)
+        .push(state_filter_chips(app))
+        .spacing(spacing.space_m);
+
+    if app.system_state.as_deref() == Some("degraded") {
+        content = content.push(degraded_banner(app.current_scope));
+    }
+
+    if app.system_needs_authentication && app.current_scope == ServiceScope::System {
+        content = content.push(authentication_banner());
+    }
+
+    if let Some((action, results)) = &app.batch_results {
+        content = content.push(batch_results(*action, results));
+    }
+
+    if !app.selected_names.is_empty() {
+        content = content.push(batch_action_bar(app));
+    } else if is_filtered(app) {
+        content = content.push(shown_action_bar(shown_rows(app, services).len()));
+    }
+
+    content
+        .push(table)
+        .into()
+}
+
+/// The header and rows of the list, as cards when `narrow`, with a pager below
+/// the flat list if it has more than a page of rows.
+fn services_table<'a>(app: &'a AppModel, services: &'a [SystemdService], is_loading: bool, narrow: bool) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let filtered_services = filter_services(app, services);
+    let row = |service| if narrow { service_card(app, service) } else { service_row(app, service) };
 
     // Localized table headers
     let service_text = fl!("service");
     let description_text = fl!("description");
     let active_state_text = fl!("active-state");
     let sub_state_text = fl!("sub-state");
-    let loading_text = fl!("loading-services");
+    let next_run_text = fl!("next-run");
+    let listen_text = fl!("listen");
     let no_services_text = fl!("no-services-found");
     let no_match_text = fl!("no-services-match");
 
-    let list_header = widget::row()
-        .push(widget::text(service_text).width(Length::FillPortion(3)))
-        .push(widget::text(description_text).width(Length::FillPortion(3)))
-        .push(widget::text(active_state_text).width(Length::FillPortion(1)))
-        .push(widget::text(sub_state_text).width(Length::FillPortion(1)))
-        .padding(cosmic::iced::Padding::from([0, spacing.space_m]));
+    let shows = |column: ListColumn| !app.hidden_columns.contains(&column);
+
+    // Timers show when they fire next and sockets what they listen on
+    // instead of their sub state. A target's sub state only repeats its
+    // active state, so it has none.
+    let last_column_header = match app.current_page {
+        Page::Timers => Some(sort_header(app, SortColumn::NextElapse, next_run_text, 2)),
+        Page::Sockets => Some(widget::text(listen_text).width(Length::FillPortion(2)).into()),
+        Page::Targets => None,
+        _ if shows(ListColumn::SubState) => Some(sort_header(app, SortColumn::SubState, sub_state_text, 1)),
+        _ => None,
+    };
+
+    let shown_names: Vec<String> = filtered_services.iter().map(|s| s.name.clone()).collect();
+    let all_selected = !shown_names.is_empty()
+        && shown_names.iter().all(|name| app.selected_names.contains(name));
 
-    let mut list = widget::list_column().spacing(spacing.space_xs);
+    let select_all = widget::checkbox("", all_selected)
+        .on_toggle(move |selected| Message::SetServicesSelected(shown_names.clone(), selected));
 
-    if app.is_loading {
-        list = list.add(widget::text(loading_text));
+    // Compact rows and header keep their horizontal padding, so they still line up.
+    let (row_spacing, row_padding, header_padding) = match app.config.list_density {
+        ListDensity::Comfortable => (spacing.space_xs, None, spacing.space_xxs),
+        ListDensity::Compact => (spacing.space_xxxs, Some(spacing.space_xxxs), 0),
+    };
+
+    let mut list_header = widget::row()
+        .push(select_all)
+        .push(sort_header(app, SortColumn::Name, service_text, 3));
+
+    // Cards have no columns to head, only the name to sort by is kept.
+    if !narrow {
+        if shows(ListColumn::Description) {
+            list_header = list_header.push(sort_header(app, SortColumn::Description, description_text, 3));
+        }
+        if shows(ListColumn::ActiveState) {
+            list_header = list_header.push(sort_header(app, SortColumn::ActiveState, active_state_text, 1));
+        }
+        if let Some(last_column_header) = last_column_header {
+            list_header = list_header.push(last_column_header);
+        }
+    }
+
+    // The menu sits above the rows' action buttons.
+    let list_header = list_header
+        .push(widget::container(column_menu(app)).width(Length::Fixed(ROW_ACTIONS_WIDTH)))
+        .align_y(Alignment::Center)
+        .padding(cosmic::iced::Padding::from([header_padding, spacing.space_m]));
+
+    let mut list = widget::list_column().spacing(row_spacing);
+    if let Some(padding) = row_padding {
+        list = list.list_item_padding([padding, spacing.space_m]);
+    }
+    let mut page = 0;
+    let mut page_count = 1;
+
+    // A refresh keeps showing the previous list until the new one arrives.
+    if is_loading && services.is_empty() {
+        for index in 0..SKELETON_ROWS {
+            list = list.add(skeleton_row(app, index, narrow));
+        }
+    } else if let Some(error) = app.scope_errors.get(&app.current_scope).filter(|_| services.is_empty()) {
+        list = list.add(widget::text(fl!("scope-unavailable", error = error.as_str())));
     } else if filtered_services.is_empty() {
-        if app.search_filter.is_empty() {
+        if !is_filtered(app) && !app.hide_vendor_units {
             list = list.add(widget::text(no_services_text));
         } else {
             list = list.add(widget::text(no_match_text));
         }
     } else {
-        for service in filtered_services {
-            let row_content = widget::row()
-                .push(
-                    widget::text(&service.name)
-                        .width(Length::FillPortion(3))
-                        .wrapping(cosmic::iced::widget::text::Wrapping::WordOrGlyph)
-                )
-                .push(
-                    widget::text(&service.description)
-                        .width(Length::FillPortion(3))
-                        .wrapping(cosmic::iced::widget::text::Wrapping::Word)
-                )
-                .push(
-                    widget::text(&service.active_state)
-                        .width(Length::FillPortion(1))
-                )
-                .push(
-                    widget::text(&service.sub_state)
-                        .width(Length::FillPortion(1))
-                );
+        // Favorites are pinned above the rest rather than listed twice.
+        let (favorites, others): (Vec<&SystemdService>, Vec<&SystemdService>) = filtered_services
+            .into_iter()
+            .partition(|service| app.favorites.contains(&service.name));
+
+        if !favorites.is_empty() {
+            list = list.add(widget::text::heading(fl!("favorites")));
+            for service in favorites {
+                list = list.add(row(service));
+            }
+            if !others.is_empty() {
+                list = list.add(widget::text::heading(fl!("all-units")));
+            }
+        }
 
-            let service_clone = service.clone();
+        // Only one page of rows is built at a time, since building and laying
+        // out every row of a long list each frame makes it sluggish. Grouped,
+        // the pages hold groups rather than rows.
+        let page_size = list_page_size(app);
+        if app.group_services {
+            let groups = group_services(&others);
+            (page, page_count) = page_of(groups.len(), app.list_page, page_size);
 
-            list = list.add(
-                widget::mouse_area(row_content)
-                    .interaction(Interaction::Pointer)
-                    .on_press(Message::SelectService(service_clone))
-            )
+            for (group, members) in page_items(groups, app.list_page, page_size) {
+                // Buckets of a single unit aren't worth a header of their own.
+                if members.len() == 1 {
+                    list = list.add(row(members[0]));
+                    continue;
+                }
+
+                let expanded = app.expanded_groups.contains(group);
+                list = list.add(group_header(group, members.len(), expanded));
+
+                if expanded {
+                    for service in members {
+                        list = list.add(
+                            widget::container(row(service))
+                                .padding(cosmic::iced::Padding::from([0, 0, 0, spacing.space_l]))
+                        );
+                    }
+                }
+            }
+        } else {
+            (page, page_count) = page_of(others.len(), app.list_page, page_size);
+
+            for service in page_items(others, app.list_page, page_size) {
+                list = list.add(row(service));
+            }
         }
     }
 
     let scrollable = widget::scrollable(list)
+        .id(list_scrollable_id())
+        .on_scroll(|viewport| Message::ListScrolled(viewport.absolute_offset()))
         .height(Length::Fill);
 
-    let services_table = widget::column()
+    let mut services_table = widget::column()
         .push(list_header)
         .push(scrollable)
-        .spacing(spacing.space_xs);
+        .spacing(row_spacing);
+
+    if page_count > 1 {
+        services_table = services_table.push(pager(page, page_count));
+    }
+
+    services_table.into()
+}
+
+/// The number of rows, or groups when grouped, on a page of the list.
+fn list_page_size(app: &AppModel) -> usize {
+    app.config.list_page_size.max(1) as usize
+}
+
+/// The units with a row in the list, in the order shown, for moving through them
+/// with the keyboard. Units in collapsed groups and on other pages are left out.
+pub(crate) fn shown_rows<'a>(app: &AppModel, services: &'a [SystemdService]) -> Vec<&'a SystemdService> {
+    let (mut rows, others): (Vec<&SystemdService>, Vec<&SystemdService>) = filter_services(app, services)
+        .into_iter()
+        .partition(|service| app.favorites.contains(&service.name));
+
+    let page_size = list_page_size(app);
+    if app.group_services {
+        for (group, members) in page_items(group_services(&others), app.list_page, page_size) {
+            if members.len() == 1 || app.expanded_groups.contains(group) {
+                rows.extend(members);
+            }
+        }
+    } else {
+        rows.extend(page_items(others, app.list_page, page_size));
+    }
+
+    rows
+}
+
+/// The name with the characters at `indices` in the theme's accent color.
+fn highlighted_name<'a>(name: &str, indices: &[usize]) -> Element<'a, Message> {
+    let accent: cosmic::iced::Color = cosmic::theme::active().cosmic().accent_color().into();
+
+    // Consecutive characters that are either all matched or all not share a span.
+    let mut runs: Vec<(bool, String)> = Vec::new();
+    for (index, c) in name.chars().enumerate() {
+        let matched = indices.contains(&index);
+        match runs.last_mut() {
+            Some((run_matched, text)) if *run_matched == matched => text.push(c),
+            _ => runs.push((matched, c.to_string())),
+        }
+    }
+
+    let spans: Vec<Span<'a, (), cosmic::iced::Font>> = runs
+        .into_iter()
+        .map(|(matched, text)| if matched { span(text).color(accent) } else { span(text) })
+        .collect();
+
+    widget::container(rich_text(spans).wrapping(cosmic::iced::widget::text::Wrapping::WordOrGlyph))
+        .width(Length::FillPortion(3))
+        .into()
+}
+
+pub(crate) fn list_scrollable_id() -> widget::Id {
+    widget::Id::new("service-list")
+}
+
+/// Buttons to move between the pages of the list.
+fn pager<'a>(page: usize, page_count: usize) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    widget::row()
+        .push(
+            widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                .on_press_maybe((page > 0).then(|| Message::ListPage(page - 1)))
+        )
+        .push(widget::text(fl!("page-of", page = page + 1, total = page_count)))
+        .push(
+            widget::button::icon(widget::icon::from_name("go-next-symbolic"))
+                .on_press_maybe((page + 1 < page_count).then(|| Message::ListPage(page + 1)))
+        )
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s)
+        .into()
+}
+
+/// The unit's description, which systemd only reads once the unit is loaded.
+fn description_cell(service: &SystemdService) -> Element<'_, Message> {
+    if service.load_state == "stub" {
+        return widget::text::caption(fl!("not-loaded"))
+            .width(Length::FillPortion(3))
+            .into();
+    }
+
+    // Kept to one line so a long description can't push the state columns out
+    // of line with the other rows, the whole of it shown on hover instead.
+    let Some(truncated) = truncate(&service.description, MAX_DESCRIPTION_CHARS) else {
+        return widget::container(
+            widget::text(&service.description).wrapping(cosmic::iced::widget::text::Wrapping::None)
+        )
+        .width(Length::FillPortion(3))
+        .clip(true)
+        .into();
+    };
+
+    widget::container(widget::tooltip(
+        widget::text(truncated).wrapping(cosmic::iced::widget::text::Wrapping::None),
+        widget::text(&service.description),
+        widget::tooltip::Position::Top,
+    ))
+    .width(Length::FillPortion(3))
+    .clip(true)
+    .into()
+}
+
+/// The unit's active state, marked when its unit file failed to load.
+fn state_cell(service: &SystemdService) -> Element<'_, Message> {
+    let state = widget::text(&service.active_state).class(state_class(service));
+
+    if !service.failed_to_load() {
+        return state.width(Length::FillPortion(1)).into();
+    }
+
+    let marked = widget::row()
+        .push(widget::icon::from_name("dialog-error-symbolic").size(16))
+        .push(state)
+        .align_y(Alignment::Center)
+        .spacing(cosmic::theme::spacing().space_xxxs);
+
+    widget::container(widget::tooltip(
+        marked,
+        widget::text(fl!("load-failed", state = service.load_state.as_str())),
+        widget::tooltip::Position::Top,
+    ))
+    .width(Length::FillPortion(1))
+    .into()
+}
+
+/// A row of the table, opening the unit's details when clicked.
+fn service_row<'a>(app: &AppModel, service: &'a SystemdService) -> Element<'a, Message> {
+    let shows = |column: ListColumn| !app.hidden_columns.contains(&column);
+
+    let mut row_content = widget::row().push(name_cell(app, service));
+    if shows(ListColumn::Description) {
+        row_content = row_content.push(description_cell(service));
+    }
+    if shows(ListColumn::ActiveState) {
+        row_content = row_content.push(state_cell(service));
+    }
+    if let Some(last_cell) = last_cell(app, service) {
+        row_content = row_content.push(last_cell);
+    }
+
+    selectable_row(app, service, row_content.into())
+}
+
+/// How many placeholder rows stand in for the list while it first loads.
+const SKELETON_ROWS: usize = 8;
+
+/// How much of its cell a placeholder bar fills, out of 10, varied from row to
+/// row so the placeholders look like text rather than a grid.
+const SKELETON_WIDTHS: [u16; 5] = [7, 5, 9, 6, 8];
+
+/// A placeholder for a row of the list still loading, with a greyed bar in each
+/// of the columns shown.
+fn skeleton_row<'a>(app: &AppModel, index: usize, narrow: bool) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let shows = |column: ListColumn| !app.hidden_columns.contains(&column);
+    let width = |offset: usize| SKELETON_WIDTHS[(index + offset) % SKELETON_WIDTHS.len()];
+
+    if narrow {
+        return widget::column()
+            .push(skeleton_bar(width(0), 3))
+            .push(skeleton_bar(width(1), 3))
+            .spacing(spacing.space_xs)
+            .padding(spacing.space_s)
+            .width(Length::Fill)
+            .into();
+    }
+
+    // Lined up with the header, which has the select all checkbox in front and
+    // the column menu at the end.
+    let mut row = widget::row()
+        .push(widget::Space::with_width(Length::Fixed(20.0)))
+        .push(skeleton_bar(width(0), 3));
+    if shows(ListColumn::Description) {
+        row = row.push(skeleton_bar(width(1), 3));
+    }
+    if shows(ListColumn::ActiveState) {
+        row = row.push(skeleton_bar(width(2), 1));
+    }
+    match app.current_page {
+        Page::Timers | Page::Sockets => row = row.push(skeleton_bar(width(3), 2)),
+        Page::Targets => {}
+        _ if shows(ListColumn::SubState) => row = row.push(skeleton_bar(width(3), 1)),
+        _ => {}
+    }
+
+    row.push(widget::Space::with_width(Length::Fixed(ROW_ACTIONS_WIDTH)))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s)
+        .into()
+}
+
+/// A greyed bar filling `filled` tenths of a cell `portion` wide.
+fn skeleton_bar<'a>(filled: u16, portion: u16) -> Element<'a, Message> {
+    let bar = widget::container(widget::Space::new(Length::Fill, Length::Fixed(12.0)))
+        .width(Length::FillPortion(filled))
+        .class(cosmic::theme::Container::custom(|theme| widget::container::Style {
+            background: Some(cosmic::iced::Background::Color(theme.cosmic().bg_component_color().into())),
+            border: cosmic::iced::Border {
+                radius: theme.cosmic().corner_radii.radius_xs.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }));
+
+    widget::row()
+        .push(bar)
+        .push(widget::Space::with_width(Length::FillPortion(10 - filled)))
+        .width(Length::FillPortion(portion))
+        .into()
+}
+
+/// The narrow layout's version of a row, with the cells stacked instead of
+/// side by side, otherwise behaving the same.
+fn service_card<'a>(app: &AppModel, service: &'a SystemdService) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let shows = |column: ListColumn| !app.hidden_columns.contains(&column);
+
+    let mut card = widget::column()
+        .push(name_cell(app, service))
+        .spacing(spacing.space_xxxs);
+    if shows(ListColumn::Description) {
+        card = card.push(description_cell(service));
+    }
+
+    let mut states = widget::row().spacing(spacing.space_xs);
+    if shows(ListColumn::ActiveState) {
+        states = states.push(state_cell(service));
+    }
+    if let Some(last_cell) = last_cell(app, service) {
+        states = states.push(last_cell);
+    }
+
+    selectable_row(app, service, card.push(states).into())
+}
+
+/// The unit's name, highlighting what matched the search.
+fn name_cell<'a>(app: &AppModel, service: &'a SystemdService) -> Element<'a, Message> {
+    // Instances are labeled by their template, with the instance below it,
+    // unless the name has to show what matched the search.
+    let unit_name = parse_unit_name(&service.name);
+    let search_match = Query::parse(&app.search.filter).name_indices(&SkimMatcherV2::default(), &service.name);
+
+    match (search_match, unit_name.template(), unit_name.instance) {
+        (Some(indices), _, _) => highlighted_name(&service.name, &indices),
+        (None, Some(template), Some(instance)) if !instance.is_empty() => widget::column()
+            .push(widget::text(template).wrapping(cosmic::iced::widget::text::Wrapping::WordOrGlyph))
+            .push(widget::text::caption(instance).wrapping(cosmic::iced::widget::text::Wrapping::WordOrGlyph))
+            .width(Length::FillPortion(3))
+            .into(),
+        _ => widget::text(&service.name)
+            .width(Length::FillPortion(3))
+            .wrapping(cosmic::iced::widget::text::Wrapping::WordOrGlyph)
+            .into(),
+    }
+}
+
+/// When a timer fires next, what a socket listens on, or else the sub state
+/// unless that column is hidden or the unit is a target.
+fn last_cell<'a>(app: &AppModel, service: &'a SystemdService) -> Option<Element<'a, Message>> {
+    match app.current_page {
+        Page::Timers => Some(
+            widget::text(service.next_elapse.map_or_else(|| NOT_AVAILABLE.to_string(), format_timestamp))
+                .width(Length::FillPortion(2))
+                .into()
+        ),
+        Page::Sockets => Some(
+            widget::text(service.listen.join(", "))
+                .width(Length::FillPortion(2))
+                .wrapping(cosmic::iced::widget::text::Wrapping::WordOrGlyph)
+                .into()
+        ),
+        Page::Targets => None,
+        _ if !app.hidden_columns.contains(&ListColumn::SubState) => Some(
+            widget::text(&service.sub_state)
+                .class(state_class(service))
+                .width(Length::FillPortion(1))
+                .into()
+        ),
+        _ => None,
+    }
+}
+
+/// Wraps a row's content with its checkbox, favorite star and actions, the
+/// content being a button opening the unit's details. The row focused from the
+/// keyboard is highlighted.
+fn selectable_row<'a>(app: &AppModel, service: &'a SystemdService, content: Element<'a, Message>) -> Element<'a, Message> {
+    let service_clone = service.clone();
+    let name = service.name.clone();
+    let is_focused = app.focused_row.as_ref() == Some(&service.name);
+
+    // The checkbox sits outside the button so checking a row doesn't open it.
+    let checkbox = widget::checkbox("", app.selected_names.contains(&service.name))
+        .on_toggle(move |selected| Message::SetServicesSelected(vec![name.clone()], selected));
+
+    let star_icon = if app.favorites.contains(&service.name) {
+        "starred-symbolic"
+    } else {
+        "non-starred-symbolic"
+    };
+
+    let favorite = widget::button::icon(widget::icon::from_name(star_icon))
+        .extra_small()
+        .on_press(Message::ToggleFavorite(service.name.clone()));
+
+    // Like the checkbox, the inline actions sit outside the button so they
+    // don't open the row, and are disabled for masked units as in the detail view.
+    let is_masked = service.unit_file_state == "masked";
+    let action = |icon_name: &'static str, tooltip: String, message: Message| {
+        widget::button::icon(widget::icon::from_name(icon_name))
+            .extra_small()
+            .tooltip(tooltip)
+            .on_press_maybe((!is_masked).then_some(message))
+    };
+
+    let start_stop = if service.sub_state == "running" {
+        action("media-playback-stop-symbolic", fl!("stop"), Message::StopService(service.name.clone()))
+    } else {
+        action("media-playback-start-symbolic", fl!("start"), Message::StartService(service.name.clone()))
+    };
+
+    let actions = widget::row()
+        .push(start_stop)
+        .push(action("view-refresh-symbolic", fl!("restart"), Message::RestartService(service.name.clone())))
+        .width(Length::Fixed(ROW_ACTIONS_WIDTH));
+
+    let mut row = widget::row()
+        .push(checkbox)
+        .push(favorite);
+
+    // Hovering the marker shows the note, which is edited in the detail view.
+    if let Some(note) = app.config.notes(app.current_scope).get(&service.name) {
+        row = row.push(widget::tooltip(
+            widget::icon::from_name("accessories-text-editor-symbolic").size(16),
+            widget::text(note.clone()),
+            widget::tooltip::Position::Top,
+        ));
+    }
+
+    row.push(
+        widget::button::custom(content)
+            .class(if is_focused { widget::button::ButtonClass::Standard } else { widget::button::ButtonClass::Text })
+            .padding(0)
+            .width(Length::Fill)
+            .name(format!("{}, {}, {}", service.name, service.active_state, service.sub_state))
+            .on_press(Message::SelectService(service_clone))
+    )
+    .push(actions)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// A collapsible header for the units sharing a name prefix.
+fn group_header<'a>(group: &str, count: usize, expanded: bool) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let icon_name = if expanded { "go-down-symbolic" } else { "go-next-symbolic" };
+
+    widget::button::custom(
+        widget::row()
+            .push(widget::icon::from_name(icon_name).size(16))
+            .push(widget::text::heading(group.to_string()))
+            .push(widget::text(format!("({count})")))
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xs)
+    )
+    .class(widget::button::ButtonClass::Text)
+    .on_press(Message::ToggleGroup(group.to_string()))
+    .into()
+}
+
+/// The recent searches matching what's typed, under the search box while it's focused.
+fn search_history<'a>(app: &AppModel, search_input: widget::TextInput<'a, Message>) -> Element<'a, Message> {
+    let input = app.search.input.to_lowercase();
+    let suggestions: Vec<&String> = app
+        .config
+        .search_history
+        .iter()
+        .filter(|term| **term != app.search.input && term.to_lowercase().contains(&input))
+        .collect();
+
+    let mut popover = widget::popover(search_input)
+        .position(widget::popover::Position::Bottom)
+        .on_close(Message::CloseSearchHistory);
+
+    if app.search.history_open && !suggestions.is_empty() {
+        let terms = suggestions.into_iter().fold(widget::column(), |menu, term| {
+            menu.push(
+                widget::button::text(term.clone())
+                    .width(Length::Fill)
+                    .on_press(Message::SearchFromHistory(term.clone()))
+            )
+        });
+
+        popover = popover.popup(
+            widget::container(
+                widget::column()
+                    .push(widget::text::caption(fl!("recent-searches")))
+                    .push(terms)
+                    .spacing(cosmic::theme::spacing().space_xxs)
+                    .width(Length::Fixed(240.0))
+            )
+            .padding(cosmic::theme::spacing().space_xs)
+            .class(cosmic::style::Container::Dropdown)
+        );
+    }
+
+    popover.into()
+}
+
+/// A menu of checkboxes showing or hiding the list's optional columns.
+fn column_menu<'a>(app: &AppModel) -> Element<'a, Message> {
+    let button = widget::button::icon(widget::icon::from_name("view-more-symbolic"))
+        .extra_small()
+        .tooltip(fl!("columns"))
+        .on_press(Message::ToggleColumnMenu);
+    let mut menu = widget::popover(button).on_close(Message::ToggleColumnMenu);
+
+    if app.column_menu_open {
+        // Timers, sockets and targets have no sub state column to hide.
+        let columns = ListColumn::ALL
+            .into_iter()
+            .filter(|column| {
+                *column != ListColumn::SubState || !matches!(app.current_page, Page::Timers | Page::Sockets | Page::Targets)
+            })
+            .fold(widget::column(), |menu, column| {
+                let label = match column {
+                    ListColumn::Description => fl!("description"),
+                    ListColumn::ActiveState => fl!("active-state"),
+                    ListColumn::SubState => fl!("sub-state"),
+                };
+                menu.push(
+                    widget::checkbox(label, !app.hidden_columns.contains(&column))
+                        .on_toggle(move |visible| Message::SetColumnVisible(column, visible))
+                )
+            });
+
+        menu = menu.popup(
+            widget::container(columns.spacing(cosmic::theme::spacing().space_xxs))
+                .padding(cosmic::theme::spacing().space_xs)
+                .class(cosmic::style::Container::Dropdown)
+        );
+    }
+
+    menu.into()
+}
+
+/// Buttons that switch the list between the system's and the user's units.
+fn scope_chips<'a>(app: &AppModel) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut chips = widget::row().spacing(spacing.space_xs);
+    for (scope, label) in [(ServiceScope::System, fl!("system")), (ServiceScope::User, fl!("user"))] {
+        let chip = if scope == app.unit_scope {
+            widget::button::suggested(label)
+        } else {
+            widget::button::standard(label)
+        };
+        chips = chips.push(chip.on_press(Message::SetUnitScope(scope)));
+    }
+
+    chips.into()
+}
+
+/// Buttons that limit the list to units in one active state.
+fn state_filter_chips<'a>(app: &AppModel) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut chips = widget::row().spacing(spacing.space_xs);
+    for filter in StateFilter::ALL {
+        let chip = if filter == app.state_filter {
+            widget::button::suggested(state_filter_label(filter))
+        } else {
+            widget::button::standard(state_filter_label(filter))
+        };
+        chips = chips.push(chip.on_press(Message::SetStateFilter(filter)));
+    }
+
+    chips
+        .push(widget::horizontal_space())
+        .push(widget::text::body(fl!("file-state-label")))
+        .push(widget::dropdown(
+            &app.file_state_labels,
+            FileStateFilter::ALL.iter().position(|filter| *filter == app.file_state_filter),
+            |index| Message::SetFileStateFilter(FileStateFilter::ALL[index]),
+        ))
+        .align_y(Alignment::Center)
+        .into()
+}
+
+/// The unit file state dropdown's labels, which it borrows from `AppModel`.
+pub(crate) fn file_state_labels() -> Vec<String> {
+    FileStateFilter::ALL
+        .iter()
+        .map(|filter| match filter {
+            FileStateFilter::All => fl!("state-all"),
+            FileStateFilter::Enabled => fl!("file-state-enabled"),
+            FileStateFilter::Disabled => fl!("file-state-disabled"),
+            FileStateFilter::Masked => fl!("file-state-masked"),
+            FileStateFilter::Static => fl!("file-state-static"),
+        })
+        .collect()
+}
+
+/// Whether the search or either state filter leaves units out of the list.
+fn is_filtered(app: &AppModel) -> bool {
+    !app.search.filter.is_empty()
+        || app.state_filter != StateFilter::All
+        || app.file_state_filter != FileStateFilter::All
+}
+
+fn state_filter_label(filter: StateFilter) -> String {
+    match filter {
+        StateFilter::All => fl!("state-all"),
+        StateFilter::Active => fl!("state-active"),
+        StateFilter::Inactive => fl!("state-inactive"),
+        StateFilter::Failed => fl!("state-failed"),
+    }
+}
 
-    widget::column()
+/// How many units the scope has, and how many of them failed or are active,
+/// the failed count applying the failed filter when clicked.
+fn state_summary<'a>(services: &[SystemdService]) -> Element<'a, Message> {
+    let failed = services.iter().filter(|s| StateFilter::Failed.matches(s)).count();
+    let active = services.iter().filter(|s| StateFilter::Active.matches(s)).count();
+
+    let failed_text = fl!("summary-failed", count = failed);
+    let failed: Element<'a, Message> = if failed > 0 {
+        widget::button::link(failed_text)
+            .on_press(Message::SetStateFilter(StateFilter::Failed))
+            .into()
+    } else {
+        widget::text::caption(failed_text).into()
+    };
+
+    widget::row()
+        .push(widget::text::caption(fl!("summary-total", count = services.len())))
+        .push(widget::text::caption("·"))
+        .push(failed)
+        .push(widget::text::caption("·"))
+        .push(widget::text::caption(fl!("summary-active", count = active)))
+        .align_y(Alignment::Center)
+        .spacing(cosmic::theme::spacing().space_xxs)
+        .into()
+}
+
+/// Tells that the connection to systemd was lost and is being re-established,
+/// until then nothing shown is current.
+pub fn reconnecting_banner<'a>() -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    widget::container(
+        widget::row()
+            .push(widget::icon::from_name("network-error-symbolic").size(16))
+            .push(widget::text::heading(fl!("reconnecting")))
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_s)
+    )
+    .padding(spacing.space_s)
+    .class(cosmic::theme::Container::Card)
+    .width(Length::Fill)
+    .into()
+}
+
+/// Tells that some units failed, as `systemctl is-system-running` reports
+/// "degraded", and offers to list them.
+fn degraded_banner<'a>(scope: ServiceScope) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    // A failed user unit degrades the user's session manager, which is only
+    // worth a note rather than a warning.
+    let (icon, text) = match scope {
+        ServiceScope::System => ("dialog-warning-symbolic", widget::text::heading(fl!("system-degraded"))),
+        ServiceScope::User => ("dialog-information-symbolic", widget::text::body(fl!("user-session-degraded"))),
+    };
+
+    widget::container(
+        widget::row()
+            .push(widget::icon::from_name(icon).size(16))
+            .push(text.width(Length::Fill))
+            .push(
+                widget::button::text(fl!("show-failed-units"))
+                    .on_press(Message::SetStateFilter(StateFilter::Failed))
+            )
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_s)
+    )
+    .padding(spacing.space_s)
+    .class(cosmic::theme::Container::Card)
+    .width(Length::Fill)
+    .into()
+}
+
+/// Tells that starting, stopping and the like will ask for a password, since
+/// polkit doesn't let the user manage system units outright.
+fn authentication_banner<'a>() -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    widget::container(
+        widget::row()
+            .push(widget::icon::from_name("dialog-password-symbolic").size(16))
+            .push(widget::text::heading(fl!("system-needs-authentication")).width(Length::Fill))
+            .push(
+                widget::button::text(fl!("dismiss"))
+                    .on_press(Message::DismissAuthenticationNotice)
+            )
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_s)
+    )
+    .padding(spacing.space_s)
+    .class(cosmic::theme::Container::Card)
+    .width(Length::Fill)
+    .into()
+}
+
+/// Actions applied to every checked service at once.
+fn batch_action_bar<'a>(app: &AppModel) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    widget::row()
+        .push(widget::text(fl!("selected-count", count = app.selected_names.len())).width(Length::Fill))
+        .push(widget::button::standard(fl!("start")).on_press(Message::BatchAction(ServiceAction::Start)))
+        .push(widget::button::standard(fl!("stop")).on_press(Message::BatchAction(ServiceAction::Stop)))
+        .push(widget::button::standard(fl!("restart")).on_press(Message::BatchAction(ServiceAction::Restart)))
+        .push(widget::button::standard(fl!("enable")).on_press(Message::BatchAction(ServiceAction::Enable)))
+        .push(widget::button::standard(fl!("disable")).on_press(Message::BatchAction(ServiceAction::Disable)))
+        .push(widget::button::text(fl!("clear-selection")).on_press(Message::ClearSelection))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s)
+        .into()
+}
+
+/// Starts or stops everything a search or state filter narrowed the list down to,
+/// as far as it has rows: units on other pages or in collapsed groups are left alone.
+fn shown_action_bar<'a>(shown: usize) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let enabled = shown > 0;
+
+    widget::row()
+        .push(widget::text(fl!("shown-count", count = shown)).width(Length::Fill))
+        .push(
+            widget::button::standard(fl!("start-all-shown"))
+                .on_press_maybe(enabled.then_some(Message::ActOnShown(ServiceAction::Start)))
+        )
+        .push(
+            widget::button::standard(fl!("stop-all-shown"))
+                .on_press_maybe(enabled.then_some(Message::ActOnShown(ServiceAction::Stop)))
+        )
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_s)
+        .into()
+}
+
+/// Colors a unit's state from the theme's palette: failed units and enabled
+/// units that aren't running stand out, running ones get a subtle accent.
+fn state_class(service: &SystemdService) -> cosmic::theme::Text {
+    if service.active_state == "failed" {
+        cosmic::theme::Text::Custom(|theme| widget::text::Style {
+            color: Some(theme.cosmic().destructive_color().into()),
+        })
+    } else if service.sub_state == "dead" && service.unit_file_state == "enabled" {
+        cosmic::theme::Text::Custom(|theme| widget::text::Style {
+            color: Some(theme.cosmic().warning_color().into()),
+        })
+    } else if service.active_state == "active" && service.sub_state == "running" {
+        cosmic::theme::Text::Custom(|theme| widget::text::Style {
+            color: Some(theme.cosmic().success_color().into()),
+        })
+    } else {
+        cosmic::theme::Text::Default
+    }
+}
+
+/// The outcome of the last batch action for every service it was applied to.
+fn batch_results<'a>(
+    action: ServiceAction,
+    results: &'a [(String, Result<Vec<String>, String>)],
+) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    let summary = fl!(
+        "batch-summary",
+        action = action.label(),
+        succeeded = results.len() - failed,
+        failed = failed
+    );
+
+    let mut header = widget::row()
+        .push(widget::text::heading(summary).width(Length::Fill))
+        .align_y(Alignment::Center);
+    if matches!(action, ServiceAction::Enable | ServiceAction::Disable) {
+        header = header.push(widget::button::text(fl!("show-changes")).on_press(Message::ShowUnitFileChanges(true)));
+    }
+    header = header.push(widget::button::text(fl!("dismiss")).on_press(Message::DismissBatchResults));
+
+    let mut column = widget::column()
         .push(header)
-        .push(services_table)
-        .spacing(spacing.space_m)
+        .spacing(spacing.space_xxs);
+
+    for (name, result) in results {
+        let line = match result {
+            Ok(_) => format!("✓ {}", name),
+            Err(error) => format!("✗ {}: {}", name, error),
+        };
+        column = column.push(widget::text(line).size(12));
+    }
+
+    widget::container(column)
+        .padding(spacing.space_s)
+        .class(cosmic::theme::Container::Card)
+        .width(Length::Fill)
+        .into()
+}
+
+/// A column header that sorts the list by `column` when clicked.
+fn sort_header<'a>(
+    app: &AppModel,
+    column: SortColumn,
+    label: String,
+    portion: u16,
+) -> Element<'a, Message> {
+    let label = if app.sort_column == column {
+        let indicator = if app.sort_ascending { "▲" } else { "▼" };
+        format!("{} {}", label, indicator)
+    } else {
+        label
+    };
+
+    widget::button::custom(widget::text(label))
+        .class(widget::button::ButtonClass::Text)
+        .padding(0)
+        .width(Length::FillPortion(portion))
+        .on_press(Message::SortBy(column))
         .into()
 }
+
+/// The services in the state filter's state matching the search filter,
+/// without vendor units if those are hidden, best matches first, otherwise in
+/// the list's sort order.
+pub(crate) fn filter_services<'a>(app: &AppModel, services: &'a [SystemdService]) -> Vec<&'a SystemdService> {
+    let mut filtered_services: Vec<&SystemdService> = services
+        .iter()
+        .filter(|s| app.state_filter.matches(s) && app.file_state_filter.matches(s))
+        .filter(|s| !(app.hide_vendor_units && s.is_vendor_managed()))
+        .collect();
+    sort_services(&mut filtered_services, app.sort_column, app.sort_ascending);
+
+    if app.search.filter.is_empty() {
+        return filtered_services;
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let query = Query::parse(&app.search.filter);
+    let mut scored: Vec<(i64, &SystemdService)> = filtered_services
+        .into_iter()
+        .filter_map(|s| query.score(&matcher, &s.name, &s.description).map(|score| (score, s)))
+        .collect();
+
+    // The sort is stable, so equally good matches keep the column order.
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, s)| s).collect()
+}
+
+/// How well `service` matches the search `query` by name or description, `None` if it doesn't.
+pub(crate) fn match_score(matcher: &SkimMatcherV2, service: &SystemdService, query: &str) -> Option<i64> {
+    Query::parse(query).score(matcher, &service.name, &service.description)
+}
+
+fn sort_services(services: &mut [&SystemdService], column: SortColumn, ascending: bool) {
+    services.sort_by(|a, b| {
+        let ordering = match column {
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::Description => a.description.to_lowercase().cmp(&b.description.to_lowercase()),
+            SortColumn::ActiveState => a.active_state.cmp(&b.active_state),
+            SortColumn::SubState => a.sub_state.cmp(&b.sub_state),
+            SortColumn::NextElapse => a.next_elapse.cmp(&b.next_elapse),
+        };
+
+        if ascending { ordering } else { ordering.reverse() }
+    });
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::AppModel;
+use crate::fl;
+use crate::message::Message;
+use crate::systemd::ServiceScope;
+use crate::types::{AppTheme, ListDensity, Page};
+use cosmic::widget::{self, settings};
+use cosmic::Element;
+
+/// The pages that can be opened at startup, `None` being the one shown last.
+pub const STARTUP_PAGES: [Option<Page>; 6] = [
+    None,
+    Some(Page::SystemServices),
+    Some(Page::UserServices),
+    Some(Page::Timers),
+    Some(Page::Sockets),
+    Some(Page::Targets),
+];
+
+/// Seconds between reloads to choose from when the app has to poll, 0 being off.
+pub const POLL_INTERVALS: [u32; 6] = [0, 1, 2, 5, 10, 30];
+
+/// How many log lines to load to choose from.
+pub const LOG_LINE_CHOICES: [u32; 5] = [50, 100, 500, 1000, 5000];
+
+/// The labels of the settings' dropdowns, which borrow them for as long as
+/// they're shown, so they're built once rather than on every view.
+pub struct SettingsLabels {
+    themes: Vec<String>,
+    startup_pages: Vec<String>,
+    poll_intervals: Vec<String>,
+    log_lines: Vec<String>,
+}
+
+impl Default for SettingsLabels {
+    fn default() -> Self {
+        Self {
+            themes: AppTheme::ALL
+                .iter()
+                .map(|theme| match theme {
+                    AppTheme::System => fl!("match-desktop"),
+                    AppTheme::Dark => fl!("dark"),
+                    AppTheme::Light => fl!("light"),
+                })
+                .collect(),
+            startup_pages: STARTUP_PAGES
+                .iter()
+                .map(|page| match page {
+                    None => fl!("last-opened-page"),
+                    Some(Page::UserServices) => fl!("user-services"),
+                    Some(Page::Timers) => fl!("timers"),
+                    Some(Page::Sockets) => fl!("sockets"),
+                    Some(Page::Targets) => fl!("targets"),
+                    Some(_) => fl!("system-services"),
+                })
+                .collect(),
+            poll_intervals: POLL_INTERVALS
+                .iter()
+                .map(|seconds| match seconds {
+                    0 => fl!("polling-off"),
+                    _ => fl!("every-seconds", seconds = seconds),
+                })
+                .collect(),
+            log_lines: LOG_LINE_CHOICES.iter().map(|lines| lines.to_string()).collect(),
+        }
+    }
+}
+
+pub fn view_settings(app: &AppModel) -> Element<'_, Message> {
+    let labels = &app.settings_labels;
+
+    let appearance = settings::section()
+        .title(fl!("appearance"))
+        .add(settings::item(
+            fl!("theme"),
+            widget::dropdown(
+                &labels.themes,
+                AppTheme::ALL.iter().position(|theme| *theme == app.config.app_theme),
+                |index| Message::SetAppTheme(AppTheme::ALL[index]),
+            ),
+        ));
+
+    let notifications = settings::section()
+        .title(fl!("failure-notifications"))
+        .add(settings::item(
+            fl!("notify-system-failures"),
+            widget::toggler(app.config.notify_system_failures)
+                .on_toggle(|enabled| Message::SetFailureNotifications(ServiceScope::System, enabled)),
+        ))
+        .add(settings::item(
+            fl!("notify-user-failures"),
+            widget::toggler(app.config.notify_user_failures)
+                .on_toggle(|enabled| Message::SetFailureNotifications(ServiceScope::User, enabled)),
+        ))
+        .add(settings::item_row(vec![
+            widget::column()
+                .push(widget::text(fl!("run-in-background")))
+                .push(widget::text::caption(fl!("run-in-background-note")))
+                .width(cosmic::iced::Length::Fill)
+                .into(),
+            widget::toggler(app.config.run_in_background)
+                .on_toggle(Message::SetRunInBackground)
+                .into(),
+        ]));
+
+    let list = settings::section()
+        .title(fl!("unit-list"))
+        .add(settings::item(
+            fl!("startup-page"),
+            widget::dropdown(
+                &labels.startup_pages,
+                STARTUP_PAGES.iter().position(|page| *page == app.config.startup_page),
+                |index| Message::SetStartupPage(STARTUP_PAGES[index]),
+            ),
+        ))
+        .add(settings::item(
+            fl!("compact-list"),
+            widget::toggler(app.config.list_density == ListDensity::Compact)
+                .on_toggle(|compact| Message::SetListDensity(if compact {
+                    ListDensity::Compact
+                } else {
+                    ListDensity::Comfortable
+                })),
+        ))
+        .add(settings::item(
+            fl!("keep-search"),
+            widget::toggler(app.config.keep_search_on_page_change)
+                .on_toggle(Message::SetKeepSearch),
+        ))
+        .add(settings::item_row(vec![
+            widget::column()
+                .push(widget::text(fl!("poll-interval")))
+                .push(widget::text::caption(fl!("poll-interval-note")))
+                .width(cosmic::iced::Length::Fill)
+                .into(),
+            widget::dropdown(
+                &labels.poll_intervals,
+                POLL_INTERVALS.iter().position(|seconds| *seconds == app.config.poll_interval),
+                |index| Message::SetPollInterval(POLL_INTERVALS[index]),
+            )
+            .into(),
+        ]));
+
+    let logs = settings::section()
+        .title(fl!("logs"))
+        .add(settings::item(
+            fl!("log-lines"),
+            widget::dropdown(
+                &labels.log_lines,
+                LOG_LINE_CHOICES.iter().position(|lines| *lines == app.log_lines),
+                |index| Message::SetLogLines(LOG_LINE_CHOICES[index]),
+            ),
+        ));
+
+    settings::view_column(vec![
+        appearance.into(),
+        list.into(),
+        logs.into(),
+        notifications.into(),
+    ])
+    .into()
+}
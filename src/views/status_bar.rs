@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::AppModel;
+use crate::fl;
+use crate::message::Message;
+use crate::systemd::ServiceScope;
+use crate::types::StateFilter;
+use super::format::format_elapsed;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget;
+use cosmic::Element;
+
+/// A line below every page with the scope, how many units its list has and how
+/// many of them failed, whether systemd's signals are being received, and when it was last loaded.
+pub fn view_status_bar(app: &AppModel) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let scope = match app.current_scope {
+        ServiceScope::System => fl!("system"),
+        ServiceScope::User => fl!("user"),
+    };
+    let units = app.current_units();
+    let failed = units.iter().filter(|s| StateFilter::Failed.matches(s)).count();
+
+    let (connection_icon, connection) = if !app.reconnecting.is_empty() {
+        ("network-error-symbolic", fl!("status-reconnecting"))
+    } else if app.signals_unavailable {
+        ("view-refresh-symbolic", fl!("status-polling"))
+    } else {
+        ("network-transmit-receive-symbolic", fl!("status-connected"))
+    };
+
+    let refreshed = match app.last_refreshed {
+        Some(instant) => fl!("updated-ago", elapsed = format_elapsed(instant.elapsed())),
+        None => fl!("status-not-loaded"),
+    };
+
+    let bar = widget::row()
+        .push(widget::text::caption(scope))
+        .push(widget::text::caption("·"))
+        .push(widget::text::caption(fl!("summary-total", count = units.len())))
+        .push(widget::text::caption("·"))
+        .push(widget::text::caption(fl!("summary-failed", count = failed)))
+        .push(widget::horizontal_space())
+        .push(widget::icon::from_name(connection_icon).size(12))
+        .push(widget::text::caption(connection))
+        .push(widget::text::caption("·"))
+        .push(widget::text::caption(refreshed))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_xxs);
+
+    widget::column()
+        .push(widget::divider::horizontal::default())
+        .push(
+            widget::container(bar)
+                .width(Length::Fill)
+                .padding([spacing.space_xxxs, spacing.space_m])
+        )
+        .into()
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::TransientService;
+use crate::fl;
+use crate::message::Message;
+use crate::systemd::ServiceScope;
+use cosmic::widget;
+use cosmic::Element;
+
+/// Collects a command to run as a transient service, like `systemd-run` does.
+pub fn view_transient_service(transient: &TransientService) -> Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let scope = widget::row()
+        .push(widget::radio(fl!("system"), ServiceScope::System, Some(transient.scope), Message::TransientScopeChanged))
+        .push(widget::radio(fl!("user"), ServiceScope::User, Some(transient.scope), Message::TransientScopeChanged))
+        .spacing(spacing.space_m);
+
+    let mut control = widget::column()
+        .push(widget::text::heading(fl!("transient-command")))
+        .push(
+            widget::text_input(fl!("transient-command-placeholder"), &transient.command)
+                .on_input(Message::TransientCommandChanged)
+                .on_submit(|_| Message::StartTransientService)
+        )
+        .push(widget::text::heading(fl!("description")))
+        .push(
+            widget::text_input(fl!("transient-description-placeholder"), &transient.description)
+                .on_input(Message::TransientDescriptionChanged)
+                .on_submit(|_| Message::StartTransientService)
+        )
+        .push(widget::text::heading(fl!("transient-scope")))
+        .push(scope)
+        .spacing(spacing.space_xs);
+
+    if let Some(error) = &transient.error {
+        control = control.push(widget::text(error));
+    }
+
+    widget::dialog()
+        .title(fl!("new-transient-service"))
+        .body(fl!("transient-service-body"))
+        .control(control)
+        .primary_action(
+            widget::button::suggested(fl!("start"))
+                .on_press_maybe((!transient.starting).then_some(Message::StartTransientService))
+        )
+        .secondary_action(
+            widget::button::standard(fl!("cancel"))
+                .on_press(Message::CloseTransientService)
+        )
+        .into()
+}